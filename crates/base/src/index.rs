@@ -3,6 +3,7 @@ use crate::vector::*;
 use base_macros::Alter;
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroU128;
+use std::time::Duration;
 use thiserror::Error;
 use validator::{Validate, ValidationError};
 
@@ -54,6 +55,52 @@ pub enum VbaseError {
     InvalidSearchOptions { reason: String },
 }
 
+#[must_use]
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+pub enum RangeError {
+    #[error("Index not found.")]
+    NotExist,
+    #[error("Invalid vector.")]
+    InvalidVector,
+    #[error("Invalid search options.")]
+    InvalidSearchOptions { reason: String },
+}
+
+impl From<VbaseError> for RangeError {
+    fn from(value: VbaseError) -> Self {
+        match value {
+            VbaseError::NotExist => RangeError::NotExist,
+            VbaseError::InvalidVector => RangeError::InvalidVector,
+            VbaseError::InvalidSearchOptions { reason } => {
+                RangeError::InvalidSearchOptions { reason }
+            }
+        }
+    }
+}
+
+#[must_use]
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+pub enum VbaseMultiError {
+    #[error("Index not found.")]
+    NotExist,
+    #[error("Invalid vector.")]
+    InvalidVector,
+    #[error("Invalid search options.")]
+    InvalidSearchOptions { reason: String },
+}
+
+impl From<VbaseError> for VbaseMultiError {
+    fn from(value: VbaseError) -> Self {
+        match value {
+            VbaseError::NotExist => VbaseMultiError::NotExist,
+            VbaseError::InvalidVector => VbaseMultiError::InvalidVector,
+            VbaseError::InvalidSearchOptions { reason } => {
+                VbaseMultiError::InvalidSearchOptions { reason }
+            }
+        }
+    }
+}
+
 #[must_use]
 #[derive(Debug, Clone, Error, Serialize, Deserialize)]
 pub enum ListError {
@@ -79,6 +126,31 @@ pub enum AlterError {
     InvalidIndexOptions { reason: String },
 }
 
+#[must_use]
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+pub enum TuneError {
+    #[error("Index not found.")]
+    NotExist,
+    #[error("Invalid vector.")]
+    InvalidVector,
+    #[error("Invalid search options.")]
+    InvalidSearchOptions { reason: String },
+    #[error("Invalid tune options: {reason}.")]
+    InvalidTuneOptions { reason: String },
+}
+
+impl From<VbaseError> for TuneError {
+    fn from(value: VbaseError) -> Self {
+        match value {
+            VbaseError::NotExist => TuneError::NotExist,
+            VbaseError::InvalidVector => TuneError::InvalidVector,
+            VbaseError::InvalidSearchOptions { reason } => {
+                TuneError::InvalidSearchOptions { reason }
+            }
+        }
+    }
+}
+
 #[must_use]
 #[derive(Debug, Clone, Error, Serialize, Deserialize)]
 pub enum StopError {
@@ -101,14 +173,40 @@ pub struct IndexOptions {
     pub vector: VectorOptions,
     #[validate(nested)]
     pub indexing: IndexingOptions,
+    #[serde(default)]
+    #[validate(nested)]
+    pub truncation: Option<TruncationOptions>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub partition: Option<PartitionOptions>,
 }
 
 impl IndexOptions {
     fn validate_self(&self) -> Result<(), ValidationError> {
+        if let Some(truncation) = &self.truncation {
+            if !matches!(
+                self.vector.v,
+                VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Vecbf16
+            ) {
+                return Err(ValidationError::new(
+                    "truncation is only supported for dense vectors",
+                ));
+            }
+            if truncation.dims >= self.vector.dims {
+                return Err(ValidationError::new(
+                    "truncated dimensions must be less than vector dimensions",
+                ));
+            }
+        }
         match &self.indexing {
             IndexingOptions::Flat(FlatIndexingOptions { quantization }) => {
                 if quantization.is_some()
-                    && !matches!(self.vector.v, VectorKind::Vecf32 | VectorKind::Vecf16)
+                    && !matches!(
+                        self.vector.v,
+                        VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Vecbf16
+                    )
                 {
                     return Err(ValidationError::new(
                         "quantization is only supported for dense vectors",
@@ -116,13 +214,19 @@ impl IndexOptions {
                 }
             }
             IndexingOptions::Ivf(IvfIndexingOptions { quantization, .. }) => {
-                if !matches!(self.vector.v, VectorKind::Vecf32 | VectorKind::Vecf16) {
+                if !matches!(
+                    self.vector.v,
+                    VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Vecbf16
+                ) {
                     return Err(ValidationError::new(
                         "ivf is only supported for dense vectors",
                     ));
                 }
                 if quantization.is_some()
-                    && !matches!(self.vector.v, VectorKind::Vecf32 | VectorKind::Vecf16)
+                    && !matches!(
+                        self.vector.v,
+                        VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Vecbf16
+                    )
                 {
                     return Err(ValidationError::new(
                         "quantization is only supported for dense vectors",
@@ -131,7 +235,10 @@ impl IndexOptions {
             }
             IndexingOptions::Hnsw(HnswIndexingOptions { quantization, .. }) => {
                 if quantization.is_some()
-                    && !matches!(self.vector.v, VectorKind::Vecf32 | VectorKind::Vecf16)
+                    && !matches!(
+                        self.vector.v,
+                        VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Vecbf16
+                    )
                 {
                     return Err(ValidationError::new(
                         "quantization is only supported for dense vectors",
@@ -139,7 +246,7 @@ impl IndexOptions {
                 }
             }
             IndexingOptions::SparseInvertedIndex(_) => {
-                if !matches!(self.vector.v, VectorKind::SVecf32) {
+                if !matches!(self.vector.v, VectorKind::SVecf32 | VectorKind::SVecf16) {
                     return Err(ValidationError::new(
                         "sparse_inverted_index is only supported for sparse vectors",
                     ));
@@ -164,6 +271,9 @@ pub struct IndexAlterableOptions {
     #[serde(default)]
     #[validate(nested)]
     pub optimizing: OptimizingOptions,
+    #[serde(default)]
+    #[validate(nested)]
+    pub search: SearchOptions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -186,8 +296,12 @@ impl VectorOptions {
             (VectorKind::Vecf32, DistanceKind::Dot, 1..65536) => Ok(()),
             (VectorKind::Vecf16, DistanceKind::L2, 1..65536) => Ok(()),
             (VectorKind::Vecf16, DistanceKind::Dot, 1..65536) => Ok(()),
+            (VectorKind::Vecbf16, DistanceKind::L2, 1..65536) => Ok(()),
+            (VectorKind::Vecbf16, DistanceKind::Dot, 1..65536) => Ok(()),
             (VectorKind::SVecf32, DistanceKind::L2, 1..1048576) => Ok(()),
             (VectorKind::SVecf32, DistanceKind::Dot, 1..1048576) => Ok(()),
+            (VectorKind::SVecf16, DistanceKind::L2, 1..1048576) => Ok(()),
+            (VectorKind::SVecf16, DistanceKind::Dot, 1..1048576) => Ok(()),
             (VectorKind::BVector, DistanceKind::Dot, 1..65536) => Ok(()),
             (VectorKind::BVector, DistanceKind::Hamming, 1..65536) => Ok(()),
             (VectorKind::BVector, DistanceKind::Jaccard, 1..65536) => Ok(()),
@@ -196,8 +310,48 @@ impl VectorOptions {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct TruncationOptions {
+    // the index is built on the first `dims` dimensions and reranked on full vectors
+    #[validate(range(min = 1, max = 65535))]
+    pub dims: u32,
+    #[serde(default = "TruncationOptions::default_renormalize")]
+    pub renormalize: bool,
+}
+
+impl TruncationOptions {
+    fn default_renormalize() -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct PartitionOptions {
+    // partitions with fewer vectors in a segment are searched by a flat scan
+    #[serde(default = "PartitionOptions::default_flat_threshold")]
+    #[validate(range(min = 0, max = 4_000_000_000u32))]
+    pub flat_threshold: u32,
+}
+
+impl PartitionOptions {
+    fn default_flat_threshold() -> u32 {
+        10_000
+    }
+}
+
+impl Default for PartitionOptions {
+    fn default() -> Self {
+        Self {
+            flat_threshold: Self::default_flat_threshold(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, Alter)]
 #[serde(deny_unknown_fields)]
+#[validate(schema(function = "Self::validate_self"))]
 pub struct SegmentOptions {
     #[serde(default = "SegmentOptions::default_max_growing_segment_size")]
     #[validate(range(min = 1, max = 4_000_000_000u32))]
@@ -205,6 +359,10 @@ pub struct SegmentOptions {
     #[serde(default = "SegmentOptions::default_max_sealed_segment_size")]
     #[validate(range(min = 1, max = 4_000_000_000u32))]
     pub max_sealed_segment_size: u32,
+    // maximum number of segments searched concurrently by one query
+    #[serde(default = "SegmentOptions::default_search_threads")]
+    #[validate(range(min = 1, max = 65535))]
+    pub search_threads: u16,
 }
 
 impl SegmentOptions {
@@ -214,6 +372,18 @@ impl SegmentOptions {
     fn default_max_sealed_segment_size() -> u32 {
         4_000_000_000u32
     }
+    fn default_search_threads() -> u16 {
+        1
+    }
+    fn validate_self(&self) -> Result<(), ValidationError> {
+        let parallelism = std::thread::available_parallelism().map_or(1, |x| x.get());
+        if self.search_threads as usize > parallelism {
+            return Err(ValidationError::new(
+                "search threads must not exceed available parallelism",
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl Default for SegmentOptions {
@@ -221,6 +391,7 @@ impl Default for SegmentOptions {
         Self {
             max_growing_segment_size: Self::default_max_growing_segment_size(),
             max_sealed_segment_size: Self::default_max_sealed_segment_size(),
+            search_threads: Self::default_search_threads(),
         }
     }
 }
@@ -351,6 +522,7 @@ impl Default for FlatIndexingOptions {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[serde(deny_unknown_fields)]
+#[validate(schema(function = "Self::validate_self"))]
 pub struct IvfIndexingOptions {
     #[serde(default = "IvfIndexingOptions::default_nlist")]
     #[validate(range(min = 1, max = 1_000_000))]
@@ -360,6 +532,14 @@ pub struct IvfIndexingOptions {
     #[serde(default = "IvfIndexingOptions::default_residual_quantization")]
     pub residual_quantization: bool,
     #[serde(default)]
+    pub kmeans: KMeansAlgorithm,
+    #[serde(default = "IvfIndexingOptions::default_hierarchical_centroids")]
+    pub hierarchical_centroids: bool,
+    // lists are probed by searching a HNSW graph over centroids if it's set
+    #[serde(default)]
+    #[validate(nested)]
+    pub hnsw_centroids: Option<HnswIndexingOptions>,
+    #[serde(default)]
     #[validate(nested)]
     pub quantization: Option<QuantizationOptions>,
 }
@@ -374,6 +554,24 @@ impl IvfIndexingOptions {
     fn default_residual_quantization() -> bool {
         false
     }
+    fn default_hierarchical_centroids() -> bool {
+        false
+    }
+    fn validate_self(&self) -> Result<(), ValidationError> {
+        if let Some(hnsw_centroids) = &self.hnsw_centroids {
+            if self.hierarchical_centroids {
+                return Err(ValidationError::new(
+                    "hierarchical centroids and HNSW over centroids cannot be used together",
+                ));
+            }
+            if hnsw_centroids.quantization.is_some() {
+                return Err(ValidationError::new(
+                    "quantization is not supported for HNSW over centroids",
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for IvfIndexingOptions {
@@ -382,11 +580,22 @@ impl Default for IvfIndexingOptions {
             nlist: Self::default_nlist(),
             spherical_centroids: false,
             residual_quantization: false,
+            kmeans: Default::default(),
+            hierarchical_centroids: false,
+            hnsw_centroids: None,
             quantization: Default::default(),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KMeansAlgorithm {
+    #[default]
+    Lloyd,
+    MiniBatch,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[serde(deny_unknown_fields)]
 pub struct HnswIndexingOptions {
@@ -398,6 +607,15 @@ pub struct HnswIndexingOptions {
     #[serde(default = "HnswIndexingOptions::default_ef_construction")]
     #[validate(range(min = 10, max = 2000))]
     pub ef_construction: u32,
+    // the level-0 graph keeps `2 * m * gamma` neighbors, so that filtered
+    // searches still find neighbors passing the filter
+    #[serde(default = "HnswIndexingOptions::default_gamma")]
+    #[validate(range(min = 1, max = 16))]
+    pub gamma: u32,
+    // the nearest `m_beta` neighbors in the level-0 graph are never pruned
+    #[serde(default = "HnswIndexingOptions::default_m_beta")]
+    #[validate(range(min = 0, max = 4096))]
+    pub m_beta: u32,
     #[serde(default)]
     #[validate(nested)]
     pub quantization: Option<QuantizationOptions>,
@@ -410,6 +628,12 @@ impl HnswIndexingOptions {
     fn default_ef_construction() -> u32 {
         300
     }
+    fn default_gamma() -> u32 {
+        1
+    }
+    fn default_m_beta() -> u32 {
+        0
+    }
 }
 
 impl Default for HnswIndexingOptions {
@@ -417,6 +641,8 @@ impl Default for HnswIndexingOptions {
         Self {
             m: Self::default_m(),
             ef_construction: Self::default_ef_construction(),
+            gamma: Self::default_gamma(),
+            m_beta: Self::default_m_beta(),
             quantization: Default::default(),
         }
     }
@@ -429,6 +655,7 @@ pub enum QuantizationOptions {
     Scalar(ScalarQuantizationOptions),
     Product(ProductQuantizationOptions),
     Rabitq(RabitqQuantizationOptions),
+    Binary(BinaryQuantizationOptions),
 }
 
 impl Validate for QuantizationOptions {
@@ -437,6 +664,7 @@ impl Validate for QuantizationOptions {
             Self::Scalar(x) => x.validate(),
             Self::Product(x) => x.validate(),
             Self::Rabitq(x) => x.validate(),
+            Self::Binary(x) => x.validate(),
         }
     }
 }
@@ -447,6 +675,8 @@ impl Validate for QuantizationOptions {
 pub struct ScalarQuantizationOptions {
     #[serde(default = "ScalarQuantizationOptions::default_bits")]
     pub bits: u32,
+    #[serde(default)]
+    pub codebook: ScalarQuantizationCodebook,
 }
 
 impl ScalarQuantizationOptions {
@@ -465,10 +695,23 @@ impl Default for ScalarQuantizationOptions {
     fn default() -> Self {
         Self {
             bits: Self::default_bits(),
+            codebook: Default::default(),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScalarQuantizationCodebook {
+    // equal bins between the minimum and the maximum of every dimension
+    #[default]
+    Uniform,
+    // equal bins between the 1st and the 99th percentiles of every dimension
+    Percentile,
+    // bins found by 1-D k-means on every dimension
+    Kmeans,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[serde(deny_unknown_fields)]
 #[validate(schema(function = "Self::validate_self"))]
@@ -478,6 +721,8 @@ pub struct ProductQuantizationOptions {
     pub ratio: u32,
     #[serde(default = "ProductQuantizationOptions::default_bits")]
     pub bits: u32,
+    #[serde(default)]
+    pub opq: bool,
 }
 
 impl ProductQuantizationOptions {
@@ -500,19 +745,62 @@ impl Default for ProductQuantizationOptions {
         Self {
             ratio: Self::default_ratio(),
             bits: Self::default_bits(),
+            opq: false,
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[serde(deny_unknown_fields)]
-pub struct RabitqQuantizationOptions {}
+pub struct RabitqQuantizationOptions {
+    #[serde(default = "RabitqQuantizationOptions::default_bits")]
+    #[validate(range(min = 1, max = 8))]
+    pub bits: u32,
+    /// Seed of the random projection. It wins over the seed of the index.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
 
-impl RabitqQuantizationOptions {}
+impl RabitqQuantizationOptions {
+    fn default_bits() -> u32 {
+        1
+    }
+}
 
 impl Default for RabitqQuantizationOptions {
     fn default() -> Self {
-        Self {}
+        Self {
+            bits: Self::default_bits(),
+            seed: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+#[validate(schema(function = "Self::validate_self"))]
+pub struct BinaryQuantizationOptions {
+    #[serde(default = "BinaryQuantizationOptions::default_bits")]
+    pub bits: u32,
+}
+
+impl BinaryQuantizationOptions {
+    fn default_bits() -> u32 {
+        1
+    }
+    fn validate_self(&self) -> Result<(), ValidationError> {
+        match self.bits {
+            1 | 2 | 4 => Ok(()),
+            _ => Err(ValidationError::new("invalid quantization bits")),
+        }
+    }
+}
+
+impl Default for BinaryQuantizationOptions {
+    fn default() -> Self {
+        Self {
+            bits: Self::default_bits(),
+        }
     }
 }
 
@@ -531,12 +819,36 @@ pub struct SearchOptions {
     pub pq_fast_scan: bool,
     #[serde(default = "SearchOptions::default_rq_fast_scan")]
     pub rq_fast_scan: bool,
+    #[serde(default = "SearchOptions::default_rq_rerank_size")]
+    #[validate(range(min = 0, max = 65535))]
+    pub rq_rerank_size: u32,
+    #[serde(default = "SearchOptions::default_bq_rerank_size")]
+    #[validate(range(min = 0, max = 65535))]
+    pub bq_rerank_size: u32,
     #[serde(default = "SearchOptions::default_ivf_nprobe")]
     #[validate(range(min = 1, max = 65535))]
     pub ivf_nprobe: u32,
+    #[serde(default = "SearchOptions::default_ivf_coarse_nprobe")]
+    #[validate(range(min = 1, max = 65535))]
+    pub ivf_coarse_nprobe: u32,
+    #[serde(default = "SearchOptions::default_ivf_adaptive_patience")]
+    #[validate(range(min = 0, max = 65535))]
+    pub ivf_adaptive_patience: u32,
     #[serde(default = "SearchOptions::default_hnsw_ef_search")]
     #[validate(range(min = 1, max = 65535))]
     pub hnsw_ef_search: u32,
+    #[serde(default = "SearchOptions::default_hnsw_adaptive_patience")]
+    #[validate(range(min = 0, max = 65535))]
+    pub hnsw_adaptive_patience: u32,
+    #[serde(default = "SearchOptions::default_adaptive_k")]
+    #[validate(range(min = 1, max = 65535))]
+    pub adaptive_k: u32,
+    #[serde(default = "SearchOptions::default_truncation_rerank_size")]
+    #[validate(range(min = 1, max = 65535))]
+    pub truncation_rerank_size: u32,
+    #[serde(default = "SearchOptions::default_multivector_candidates")]
+    #[validate(range(min = 1, max = 65535))]
+    pub multivector_candidates: u32,
 }
 
 impl SearchOptions {
@@ -555,12 +867,36 @@ impl SearchOptions {
     pub const fn default_rq_fast_scan() -> bool {
         true
     }
+    pub const fn default_rq_rerank_size() -> u32 {
+        0
+    }
+    pub const fn default_bq_rerank_size() -> u32 {
+        100
+    }
     pub const fn default_ivf_nprobe() -> u32 {
         10
     }
+    pub const fn default_ivf_coarse_nprobe() -> u32 {
+        10
+    }
+    pub const fn default_ivf_adaptive_patience() -> u32 {
+        0
+    }
     pub const fn default_hnsw_ef_search() -> u32 {
         100
     }
+    pub const fn default_hnsw_adaptive_patience() -> u32 {
+        0
+    }
+    pub const fn default_adaptive_k() -> u32 {
+        10
+    }
+    pub const fn default_truncation_rerank_size() -> u32 {
+        100
+    }
+    pub const fn default_multivector_candidates() -> u32 {
+        100
+    }
 }
 
 impl Default for SearchOptions {
@@ -571,8 +907,118 @@ impl Default for SearchOptions {
             pq_rerank_size: Self::default_pq_rerank_size(),
             pq_fast_scan: Self::default_pq_fast_scan(),
             rq_fast_scan: Self::default_rq_fast_scan(),
+            rq_rerank_size: Self::default_rq_rerank_size(),
+            bq_rerank_size: Self::default_bq_rerank_size(),
             ivf_nprobe: Self::default_ivf_nprobe(),
+            ivf_coarse_nprobe: Self::default_ivf_coarse_nprobe(),
+            ivf_adaptive_patience: Self::default_ivf_adaptive_patience(),
             hnsw_ef_search: Self::default_hnsw_ef_search(),
+            hnsw_adaptive_patience: Self::default_hnsw_adaptive_patience(),
+            adaptive_k: Self::default_adaptive_k(),
+            truncation_rerank_size: Self::default_truncation_rerank_size(),
+            multivector_candidates: Self::default_multivector_candidates(),
+        }
+    }
+}
+
+/// Search options set by a session or by a query. Options that are not set are
+/// taken from the `search` options of the index.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct PartialSearchOptions {
+    #[serde(default)]
+    #[validate(range(min = 0, max = 65535))]
+    pub sq_rerank_size: Option<u32>,
+    #[serde(default)]
+    pub sq_fast_scan: Option<bool>,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 65535))]
+    pub pq_rerank_size: Option<u32>,
+    #[serde(default)]
+    pub pq_fast_scan: Option<bool>,
+    #[serde(default)]
+    pub rq_fast_scan: Option<bool>,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 65535))]
+    pub rq_rerank_size: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 65535))]
+    pub bq_rerank_size: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 1, max = 65535))]
+    pub ivf_nprobe: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 1, max = 65535))]
+    pub ivf_coarse_nprobe: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 65535))]
+    pub ivf_adaptive_patience: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 1, max = 65535))]
+    pub hnsw_ef_search: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 65535))]
+    pub hnsw_adaptive_patience: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 1, max = 65535))]
+    pub adaptive_k: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 1, max = 65535))]
+    pub truncation_rerank_size: Option<u32>,
+    #[serde(default)]
+    #[validate(range(min = 1, max = 65535))]
+    pub multivector_candidates: Option<u32>,
+}
+
+impl PartialSearchOptions {
+    /// Options that are set in `self`, and the rest from `fallback`.
+    pub fn or(&self, fallback: &SearchOptions) -> SearchOptions {
+        macro_rules! or {
+            ($field:ident) => {
+                self.$field.unwrap_or(fallback.$field)
+            };
+        }
+        SearchOptions {
+            sq_rerank_size: or!(sq_rerank_size),
+            sq_fast_scan: or!(sq_fast_scan),
+            pq_rerank_size: or!(pq_rerank_size),
+            pq_fast_scan: or!(pq_fast_scan),
+            rq_fast_scan: or!(rq_fast_scan),
+            rq_rerank_size: or!(rq_rerank_size),
+            bq_rerank_size: or!(bq_rerank_size),
+            ivf_nprobe: or!(ivf_nprobe),
+            ivf_coarse_nprobe: or!(ivf_coarse_nprobe),
+            ivf_adaptive_patience: or!(ivf_adaptive_patience),
+            hnsw_ef_search: or!(hnsw_ef_search),
+            hnsw_adaptive_patience: or!(hnsw_adaptive_patience),
+            adaptive_k: or!(adaptive_k),
+            truncation_rerank_size: or!(truncation_rerank_size),
+            multivector_candidates: or!(multivector_candidates),
+        }
+    }
+    /// Options that are set in `self`, and the rest from `fallback`.
+    pub fn or_partial(&self, fallback: &PartialSearchOptions) -> PartialSearchOptions {
+        macro_rules! or {
+            ($field:ident) => {
+                self.$field.or(fallback.$field)
+            };
+        }
+        PartialSearchOptions {
+            sq_rerank_size: or!(sq_rerank_size),
+            sq_fast_scan: or!(sq_fast_scan),
+            pq_rerank_size: or!(pq_rerank_size),
+            pq_fast_scan: or!(pq_fast_scan),
+            rq_fast_scan: or!(rq_fast_scan),
+            rq_rerank_size: or!(rq_rerank_size),
+            bq_rerank_size: or!(bq_rerank_size),
+            ivf_nprobe: or!(ivf_nprobe),
+            ivf_coarse_nprobe: or!(ivf_coarse_nprobe),
+            ivf_adaptive_patience: or!(ivf_adaptive_patience),
+            hnsw_ef_search: or!(hnsw_ef_search),
+            hnsw_adaptive_patience: or!(hnsw_adaptive_patience),
+            adaptive_k: or!(adaptive_k),
+            truncation_rerank_size: or!(truncation_rerank_size),
+            multivector_candidates: or!(multivector_candidates),
         }
     }
 }
@@ -592,6 +1038,24 @@ pub struct SegmentStat {
     pub size: u64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct TuneOptions {
+    #[validate(range(min = 1, max = 65535))]
+    pub queries: u32,
+    #[validate(range(min = 1, max = 65535))]
+    pub k: u32,
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub target_recall: f32,
+}
+
+/// Recall and average latency of searches with some search options.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TuneTrial {
+    pub options: SearchOptions,
+    pub recall: f32,
+    pub latency: Duration,
+}
+
 pub trait Alter {
     fn alter(&mut self, key: &[&str], value: &str) -> Result<(), AlterError>;
 }
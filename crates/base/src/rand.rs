@@ -1,4 +1,14 @@
+use rand::rngs::StdRng;
 use rand::Rng;
+use rand::SeedableRng;
+
+/// A generator from `seed`, or from entropy if `seed` is not set.
+pub fn rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
 
 pub fn sample_u32<R>(rng: &mut R, length: u32, amount: u32) -> Vec<u32>
 where
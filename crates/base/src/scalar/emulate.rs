@@ -142,3 +142,25 @@ pub unsafe fn emulate_mm256_reduce_max_ps(x: std::arch::x86_64::__m256) -> f32 {
         f32::max(f32::max(x.0[0], x.0[1]), f32::max(x.0[2], x.0[3]))
     }
 }
+
+// AVX512-BF16 conversion emulation.
+// A bfloat16 value is the upper half of the bits of a float32.
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[detect::target_cpu(enable = "v4")]
+pub unsafe fn emulate_mm512_cvtpbh_ps(x: std::arch::x86_64::__m256i) -> std::arch::x86_64::__m512 {
+    unsafe {
+        use std::arch::x86_64::*;
+        _mm512_castsi512_ps(_mm512_slli_epi32(_mm512_cvtepu16_epi32(x), 16))
+    }
+}
+
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[detect::target_cpu(enable = "v3")]
+pub unsafe fn emulate_mm256_cvtpbh_ps(x: std::arch::x86_64::__m128i) -> std::arch::x86_64::__m256 {
+    unsafe {
+        use std::arch::x86_64::*;
+        _mm256_castsi256_ps(_mm256_slli_epi32(_mm256_cvtepu16_epi32(x), 16))
+    }
+}
@@ -37,6 +37,11 @@ impl ScalarLike for f16 {
         lhs * rhs
     }
 
+    #[inline(always)]
+    fn scalar_div(lhs: Self, rhs: Self) -> Self {
+        lhs / rhs
+    }
+
     #[inline(always)]
     fn scalar_is_sign_positive(self) -> bool {
         self.is_sign_positive()
@@ -216,6 +221,40 @@ impl ScalarLike for f16 {
         }
     }
 
+    #[detect::multiversion(v4, v3, v2, neon, fallback)]
+    fn vector_div(lhs: &[f16], rhs: &[f16]) -> Vec<f16> {
+        assert_eq!(lhs.len(), rhs.len());
+        let n = lhs.len();
+        let mut r = Vec::<f16>::with_capacity(n);
+        for i in 0..n {
+            unsafe {
+                r.as_mut_ptr().add(i).write(lhs[i] / rhs[i]);
+            }
+        }
+        unsafe {
+            r.set_len(n);
+        }
+        r
+    }
+
+    #[detect::multiversion(v4, v3, v2, neon, fallback)]
+    fn vector_abs_inplace(this: &mut [f16]) {
+        let n = this.len();
+        for i in 0..n {
+            this[i] = f16::from_bits(this[i].to_bits() & 0x7fff);
+        }
+    }
+
+    #[detect::multiversion(v4, v3, v2, neon, fallback)]
+    fn vector_clamp_inplace(this: &mut [f16], min: f32, max: f32) {
+        let min = f16::from_f32(min);
+        let max = f16::from_f32(max);
+        let n = this.len();
+        for i in 0..n {
+            this[i] = this[i].max(min).min(max);
+        }
+    }
+
     #[detect::multiversion(v4, v3, v2, neon, fallback)]
     fn vector_from_f32(this: &[f32]) -> Vec<f16> {
         let n = this.len();
@@ -36,6 +36,11 @@ impl ScalarLike for f32 {
         lhs * rhs
     }
 
+    #[inline(always)]
+    fn scalar_div(lhs: Self, rhs: Self) -> Self {
+        lhs / rhs
+    }
+
     #[inline(always)]
     fn scalar_is_sign_positive(self) -> bool {
         self.is_sign_positive()
@@ -187,6 +192,38 @@ impl ScalarLike for f32 {
         }
     }
 
+    #[detect::multiversion(v4, v3, v2, neon, fallback)]
+    fn vector_div(lhs: &[f32], rhs: &[f32]) -> Vec<f32> {
+        assert_eq!(lhs.len(), rhs.len());
+        let n = lhs.len();
+        let mut r = Vec::<f32>::with_capacity(n);
+        for i in 0..n {
+            unsafe {
+                r.as_mut_ptr().add(i).write(lhs[i] / rhs[i]);
+            }
+        }
+        unsafe {
+            r.set_len(n);
+        }
+        r
+    }
+
+    #[detect::multiversion(v4, v3, v2, neon, fallback)]
+    fn vector_abs_inplace(this: &mut [f32]) {
+        let n = this.len();
+        for i in 0..n {
+            this[i] = this[i].abs();
+        }
+    }
+
+    #[detect::multiversion(v4, v3, v2, neon, fallback)]
+    fn vector_clamp_inplace(this: &mut [f32], min: f32, max: f32) {
+        let n = this.len();
+        for i in 0..n {
+            this[i] = this[i].max(min).min(max);
+        }
+    }
+
     #[detect::multiversion(v4, v3, v2, neon, fallback)]
     fn vector_from_f32(this: &[f32]) -> Vec<f32> {
         this.to_vec()
@@ -39,6 +39,10 @@ impl ScalarLike for Impossible {
         unimplemented!()
     }
 
+    fn scalar_div(_: Self, _: Self) -> Self {
+        unimplemented!()
+    }
+
     fn scalar_is_sign_positive(self) -> bool {
         unimplemented!()
     }
@@ -138,6 +142,18 @@ impl ScalarLike for Impossible {
         unimplemented!()
     }
 
+    fn vector_div(_lhs: &[Self], _rhs: &[Self]) -> Vec<Self> {
+        unimplemented!()
+    }
+
+    fn vector_abs_inplace(_this: &mut [Self]) {
+        unimplemented!()
+    }
+
+    fn vector_clamp_inplace(_this: &mut [Self], _min: f32, _max: f32) {
+        unimplemented!()
+    }
+
     fn kmeans_helper(_this: &mut [Self], _x: f32, _y: f32) {
         unimplemented!()
     }
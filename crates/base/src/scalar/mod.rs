@@ -1,3 +1,4 @@
+mod bf16;
 pub mod bit;
 pub mod emulate;
 mod f16;
@@ -24,6 +25,7 @@ pub trait ScalarLike:
     fn scalar_add(lhs: Self, rhs: Self) -> Self;
     fn scalar_sub(lhs: Self, rhs: Self) -> Self;
     fn scalar_mul(lhs: Self, rhs: Self) -> Self;
+    fn scalar_div(lhs: Self, rhs: Self) -> Self;
     fn scalar_is_sign_positive(self) -> bool;
     fn scalar_is_sign_negative(self) -> bool;
 
@@ -51,6 +53,9 @@ pub trait ScalarLike:
     fn vector_mul(lhs: &[Self], rhs: &[Self]) -> Vec<Self>;
     fn vector_mul_scalar(lhs: &[Self], rhs: f32) -> Vec<Self>;
     fn vector_mul_scalar_inplace(lhs: &mut [Self], rhs: f32);
+    fn vector_div(lhs: &[Self], rhs: &[Self]) -> Vec<Self>;
+    fn vector_abs_inplace(this: &mut [Self]);
+    fn vector_clamp_inplace(this: &mut [Self], min: f32, max: f32);
 
     fn kmeans_helper(this: &mut [Self], x: f32, y: f32);
 }
@@ -61,10 +61,22 @@ impl Payload {
     }
 }
 
+/// A predicate over payloads, so that a search only returns vectors passing it.
+pub trait Filter: Sync {
+    fn check(&self, payload: Payload) -> bool;
+}
+
+impl<F: Fn(Payload) -> bool + Sync> Filter for F {
+    fn check(&self, payload: Payload) -> bool {
+        self(payload)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Element {
     pub distance: Distance,
     pub payload: AlwaysEqual<Payload>,
+    pub key: AlwaysEqual<u32>,
 }
 
 pub trait Vectors<V: VectorOwned> {
@@ -207,6 +207,26 @@ impl<'a> VectorBorrowed for BVectBorrowed<'a> {
         BVectOwned::new(self.dims, data)
     }
 
+    fn operator_div(&self, _: Self) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn function_mul_scalar(&self, _: f32) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn function_abs(&self) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn function_clamp(&self, _: f32, _: f32) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn function_concat(&self, _: Self) -> Self::Owned {
+        unimplemented!()
+    }
+
     #[inline(always)]
     fn subvector(&self, bounds: impl RangeBounds<u32>) -> Option<Self::Owned> {
         let start = match bounds.start_bound().cloned() {
@@ -239,6 +259,10 @@ impl<'a> VectorBorrowed for BVectBorrowed<'a> {
         }
         Self::Owned::new_checked(dims, data)
     }
+
+    fn subvector_step(&self, _: u32, _: u32, _: u32) -> Option<Self::Owned> {
+        unimplemented!()
+    }
 }
 
 impl<'a> PartialEq for BVectBorrowed<'a> {
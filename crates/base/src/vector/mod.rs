@@ -7,7 +7,7 @@ pub use svect::{SVectBorrowed, SVectOwned};
 pub use vect::{VectBorrowed, VectOwned};
 
 use crate::distance::Distance;
-use half::f16;
+use half::{bf16, f16};
 use serde::{Deserialize, Serialize};
 use std::ops::RangeBounds;
 
@@ -16,11 +16,13 @@ use std::ops::RangeBounds;
 pub enum VectorKind {
     Vecf32,
     Vecf16,
+    Vecbf16,
     SVecf32,
+    SVecf16,
     BVector,
 }
 
-pub trait VectorOwned: Clone + Serialize + for<'a> Deserialize<'a> + 'static {
+pub trait VectorOwned: Clone + Serialize + for<'a> Deserialize<'a> + Send + Sync + 'static {
     type Borrowed<'a>: VectorBorrowed<Owned = Self>;
 
     fn as_borrowed(&self) -> Self::Borrowed<'_>;
@@ -28,7 +30,7 @@ pub trait VectorOwned: Clone + Serialize + for<'a> Deserialize<'a> + 'static {
     fn zero(dims: u32) -> Self;
 }
 
-pub trait VectorBorrowed: Copy + PartialEq + PartialOrd {
+pub trait VectorBorrowed: Copy + PartialEq + PartialOrd + Send + Sync {
     type Owned: VectorOwned;
 
     fn own(&self) -> Self::Owned;
@@ -61,14 +63,30 @@ pub trait VectorBorrowed: Copy + PartialEq + PartialOrd {
 
     fn operator_xor(&self, rhs: Self) -> Self::Owned;
 
+    fn operator_div(&self, rhs: Self) -> Self::Owned;
+
+    fn function_mul_scalar(&self, rhs: f32) -> Self::Owned;
+
+    fn function_abs(&self) -> Self::Owned;
+
+    /// `min` must not be greater than `max`.
+    fn function_clamp(&self, min: f32, max: f32) -> Self::Owned;
+
+    fn function_concat(&self, rhs: Self) -> Self::Owned;
+
     fn subvector(&self, bounds: impl RangeBounds<u32>) -> Option<Self::Owned>;
+
+    /// Every `step`-th element of `start..end`, or `None` if it is empty.
+    fn subvector_step(&self, start: u32, end: u32, step: u32) -> Option<Self::Owned>;
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OwnedVector {
     Vecf32(VectOwned<f32>),
     Vecf16(VectOwned<f16>),
+    Vecbf16(VectOwned<bf16>),
     SVecf32(SVectOwned<f32>),
+    SVecf16(SVectOwned<f16>),
     BVector(BVectOwned),
 }
 
@@ -77,7 +95,9 @@ impl OwnedVector {
         match self {
             OwnedVector::Vecf32(x) => BorrowedVector::Vecf32(x.as_borrowed()),
             OwnedVector::Vecf16(x) => BorrowedVector::Vecf16(x.as_borrowed()),
+            OwnedVector::Vecbf16(x) => BorrowedVector::Vecbf16(x.as_borrowed()),
             OwnedVector::SVecf32(x) => BorrowedVector::SVecf32(x.as_borrowed()),
+            OwnedVector::SVecf16(x) => BorrowedVector::SVecf16(x.as_borrowed()),
             OwnedVector::BVector(x) => BorrowedVector::BVector(x.as_borrowed()),
         }
     }
@@ -99,7 +119,9 @@ impl PartialOrd for OwnedVector {
 pub enum BorrowedVector<'a> {
     Vecf32(VectBorrowed<'a, f32>),
     Vecf16(VectBorrowed<'a, f16>),
+    Vecbf16(VectBorrowed<'a, bf16>),
     SVecf32(SVectBorrowed<'a, f32>),
+    SVecf16(SVectBorrowed<'a, f16>),
     BVector(BVectBorrowed<'a>),
 }
 
@@ -109,7 +131,9 @@ impl PartialEq for BorrowedVector<'_> {
         match (self, other) {
             (Vecf32(lhs), Vecf32(rhs)) => lhs == rhs,
             (Vecf16(lhs), Vecf16(rhs)) => lhs == rhs,
+            (Vecbf16(lhs), Vecbf16(rhs)) => lhs == rhs,
             (SVecf32(lhs), SVecf32(rhs)) => lhs == rhs,
+            (SVecf16(lhs), SVecf16(rhs)) => lhs == rhs,
             (BVector(lhs), BVector(rhs)) => lhs == rhs,
             _ => false,
         }
@@ -122,7 +146,9 @@ impl PartialOrd for BorrowedVector<'_> {
         match (self, other) {
             (Vecf32(lhs), Vecf32(rhs)) => lhs.partial_cmp(rhs),
             (Vecf16(lhs), Vecf16(rhs)) => lhs.partial_cmp(rhs),
+            (Vecbf16(lhs), Vecbf16(rhs)) => lhs.partial_cmp(rhs),
             (SVecf32(lhs), SVecf32(rhs)) => lhs.partial_cmp(rhs),
+            (SVecf16(lhs), SVecf16(rhs)) => lhs.partial_cmp(rhs),
             (BVector(lhs), BVector(rhs)) => lhs.partial_cmp(rhs),
             _ => None,
         }
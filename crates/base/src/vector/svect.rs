@@ -350,6 +350,111 @@ impl<'a, S: ScalarLike> VectorBorrowed for SVectBorrowed<'a, S> {
         unimplemented!()
     }
 
+    fn operator_div(&self, rhs: Self) -> Self::Owned {
+        // positions that are zero in both operands are skipped, so callers should
+        // check that the divisor has no zero element
+        assert_eq!(self.dims, rhs.dims);
+        let size1 = self.len();
+        let size2 = rhs.len();
+        let mut pos1 = 0;
+        let mut pos2 = 0;
+        let mut indexes = Vec::with_capacity((size1 + size2) as _);
+        let mut values = Vec::with_capacity((size1 + size2) as _);
+        while pos1 < size1 || pos2 < size2 {
+            let lhs_index = self.indexes.get(pos1 as usize).copied().unwrap_or(u32::MAX);
+            let rhs_index = rhs.indexes.get(pos2 as usize).copied().unwrap_or(u32::MAX);
+            let index = lhs_index.min(rhs_index);
+            let lhs_value = if lhs_index == index {
+                pos1 += 1;
+                self.values[pos1 as usize - 1]
+            } else {
+                S::zero()
+            };
+            let rhs_value = if rhs_index == index {
+                pos2 += 1;
+                rhs.values[pos2 as usize - 1]
+            } else {
+                S::zero()
+            };
+            let value = S::scalar_div(lhs_value, rhs_value);
+            if value != S::zero() {
+                indexes.push(index);
+                values.push(value);
+            }
+        }
+        SVectOwned::new(self.dims, indexes, values)
+    }
+
+    fn function_mul_scalar(&self, rhs: f32) -> Self::Owned {
+        let mut indexes = self.indexes.to_vec();
+        let mut values = S::vector_mul_scalar(self.values, rhs);
+        let n = indexes.len();
+        let mut j = 0_usize;
+        for i in 0..n {
+            if values[i] != S::zero() {
+                indexes[j] = indexes[i];
+                values[j] = values[i];
+                j += 1;
+            }
+        }
+        indexes.truncate(j);
+        values.truncate(j);
+        SVectOwned::new(self.dims, indexes, values)
+    }
+
+    fn function_abs(&self) -> Self::Owned {
+        let mut values = self.values.to_vec();
+        S::vector_abs_inplace(&mut values);
+        SVectOwned::new(self.dims, self.indexes.to_vec(), values)
+    }
+
+    fn function_clamp(&self, min: f32, max: f32) -> Self::Owned {
+        if min <= 0.0 && 0.0 <= max {
+            // zeros are not changed, so only stored values are clamped
+            let mut indexes = self.indexes.to_vec();
+            let mut values = self.values.to_vec();
+            S::vector_clamp_inplace(&mut values, min, max);
+            let n = indexes.len();
+            let mut j = 0_usize;
+            for i in 0..n {
+                if values[i] != S::zero() {
+                    indexes[j] = indexes[i];
+                    values[j] = values[i];
+                    j += 1;
+                }
+            }
+            indexes.truncate(j);
+            values.truncate(j);
+            SVectOwned::new(self.dims, indexes, values)
+        } else {
+            // every zero is clamped to a non-zero bound, so the result is dense
+            let mut dense = vec![S::zero(); self.dims as usize];
+            for (&index, &value) in self.indexes.iter().zip(self.values) {
+                dense[index as usize] = value;
+            }
+            S::vector_clamp_inplace(&mut dense, min, max);
+            let mut indexes = Vec::with_capacity(self.dims as usize);
+            let mut values = Vec::with_capacity(self.dims as usize);
+            for (i, value) in dense.into_iter().enumerate() {
+                if value != S::zero() {
+                    indexes.push(i as u32);
+                    values.push(value);
+                }
+            }
+            SVectOwned::new(self.dims, indexes, values)
+        }
+    }
+
+    fn function_concat(&self, rhs: Self) -> Self::Owned {
+        let mut indexes = Vec::with_capacity((self.len() + rhs.len()) as usize);
+        indexes.extend_from_slice(self.indexes);
+        indexes.extend(rhs.indexes.iter().map(|x| x + self.dims));
+        let mut values = Vec::with_capacity((self.len() + rhs.len()) as usize);
+        values.extend_from_slice(self.values);
+        values.extend_from_slice(rhs.values);
+        SVectOwned::new(self.dims + rhs.dims, indexes, values)
+    }
+
     #[inline(always)]
     fn subvector(&self, bounds: impl RangeBounds<u32>) -> Option<Self::Owned> {
         let start = match bounds.start_bound().cloned() {
@@ -377,6 +482,26 @@ impl<'a, S: ScalarLike> VectorBorrowed for SVectBorrowed<'a, S> {
         let values = self.values[s..e].to_vec();
         Self::Owned::new_checked(dims, indexes, values)
     }
+
+    fn subvector_step(&self, start: u32, end: u32, step: u32) -> Option<Self::Owned> {
+        assert!(step > 0);
+        let end = end.min(self.dims);
+        if start >= end {
+            return None;
+        }
+        let dims = (end - start).div_ceil(step);
+        let s = self.indexes.partition_point(|&x| x < start);
+        let e = self.indexes.partition_point(|&x| x < end);
+        let mut indexes = Vec::new();
+        let mut values = Vec::new();
+        for (&index, &value) in self.indexes[s..e].iter().zip(&self.values[s..e]) {
+            if (index - start) % step == 0 {
+                indexes.push((index - start) / step);
+                values.push(value);
+            }
+        }
+        Self::Owned::new_checked(dims, indexes, values)
+    }
 }
 
 impl<'a, S: ScalarLike> PartialEq for SVectBorrowed<'a, S> {
@@ -170,6 +170,33 @@ impl<'a, S: ScalarLike> VectorBorrowed for VectBorrowed<'a, S> {
         unimplemented!()
     }
 
+    fn operator_div(&self, rhs: Self) -> Self::Owned {
+        VectOwned::new(S::vector_div(self.slice(), rhs.slice()))
+    }
+
+    fn function_mul_scalar(&self, rhs: f32) -> Self::Owned {
+        VectOwned::new(S::vector_mul_scalar(self.slice(), rhs))
+    }
+
+    fn function_abs(&self) -> Self::Owned {
+        let mut data = self.0.to_vec();
+        S::vector_abs_inplace(&mut data);
+        VectOwned::new(data)
+    }
+
+    fn function_clamp(&self, min: f32, max: f32) -> Self::Owned {
+        let mut data = self.0.to_vec();
+        S::vector_clamp_inplace(&mut data, min, max);
+        VectOwned::new(data)
+    }
+
+    fn function_concat(&self, rhs: Self) -> Self::Owned {
+        let mut data = Vec::with_capacity(self.0.len() + rhs.0.len());
+        data.extend_from_slice(self.0);
+        data.extend_from_slice(rhs.0);
+        VectOwned::new(data)
+    }
+
     #[inline(always)]
     fn subvector(&self, bounds: impl RangeBounds<u32>) -> Option<Self::Owned> {
         let start_bound = bounds.start_bound().map(|x| *x as usize);
@@ -180,6 +207,20 @@ impl<'a, S: ScalarLike> VectorBorrowed for VectBorrowed<'a, S> {
         }
         Self::Owned::new_checked(slice.to_vec())
     }
+
+    fn subvector_step(&self, start: u32, end: u32, step: u32) -> Option<Self::Owned> {
+        assert!(step > 0);
+        let end = end.min(self.dims());
+        if start >= end {
+            return None;
+        }
+        let data = self.0[start as usize..end as usize]
+            .iter()
+            .step_by(step as usize)
+            .copied()
+            .collect();
+        Self::Owned::new_checked(data)
+    }
 }
 
 impl<'a, S: ScalarLike> PartialEq for VectBorrowed<'a, S> {
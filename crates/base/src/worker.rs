@@ -17,24 +17,49 @@ pub trait WorkerOperations {
         handle: Handle,
         vector: OwnedVector,
         pointer: Pointer,
+        partition: Option<u64>,
     ) -> Result<(), InsertError>;
     fn delete(&self, handle: Handle, pointer: Pointer) -> Result<(), DeleteError>;
     fn view_vbase(&self, handle: Handle) -> Result<impl ViewVbaseOperations, VbaseError>;
     fn view_list(&self, handle: Handle) -> Result<impl ViewListOperations, ListError>;
     fn stat(&self, handle: Handle) -> Result<IndexStat, StatError>;
     fn alter(&self, handle: Handle, key: &str, value: &str) -> Result<(), AlterError>;
+    fn view_tune(&self, handle: Handle) -> Result<impl ViewTuneOperations, TuneError>;
     fn stop(&self, handle: Handle) -> Result<(), StopError>;
     fn start(&self, handle: Handle) -> Result<(), StartError>;
 }
 
 pub trait ViewVbaseOperations {
+    fn search_options(&self, opts: &PartialSearchOptions) -> SearchOptions;
+    /// The view restricted to vectors of the given partitions.
+    fn partition(self, keys: &[u64]) -> Self
+    where
+        Self: Sized;
     fn vbase<'a>(
         &'a self,
         vector: &'a OwnedVector,
         opts: &'a SearchOptions,
     ) -> Result<Box<dyn Iterator<Item = (Distance, Pointer)> + 'a>, VbaseError>;
+    fn vbase_vector<'a>(
+        &'a self,
+        vector: &'a OwnedVector,
+        opts: &'a SearchOptions,
+    ) -> Result<Box<dyn Iterator<Item = (Distance, Pointer, OwnedVector)> + 'a>, VbaseError>;
+    fn vbase_multi<'a>(
+        &'a self,
+        vectors: &'a [OwnedVector],
+        opts: &'a SearchOptions,
+    ) -> Result<Box<dyn Iterator<Item = (Distance, Pointer)> + 'a>, VbaseError>;
 }
 
 pub trait ViewListOperations {
     fn list(&self) -> Result<Box<dyn Iterator<Item = Pointer> + '_>, ListError>;
 }
+
+pub trait ViewTuneOperations {
+    fn tune<'a>(
+        &'a self,
+        opts: &PartialSearchOptions,
+        tune: &TuneOptions,
+    ) -> Result<Box<dyn Iterator<Item = Result<TuneTrial, TuneError>> + 'a>, TuneError>;
+}
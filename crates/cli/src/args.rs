@@ -15,7 +15,9 @@ fn vec_type_from_str(s: &str) -> Result<VectorKind, ArgumentParseError> {
     match s.trim() {
         "Vecf32" => Ok(VectorKind::Vecf32),
         "Vecf16" => Ok(VectorKind::Vecf16),
+        "Vecbf16" => Ok(VectorKind::Vecbf16),
         "SVecf32" => Ok(VectorKind::SVecf32),
+        "SVecf16" => Ok(VectorKind::SVecf16),
         "BVector" => Ok(VectorKind::BVector),
         _ => Err(ArgumentParseError),
     }
@@ -48,7 +50,7 @@ pub struct CreateArguments {
     #[argh(option)]
     dim: u32,
 
-    /// vector type: [`Vecf32`, `Vecf16`, `SVecf32`, `BVector`, `Veci8`]
+    /// vector type: [`Vecf32`, `Vecf16`, `Vecbf16`, `SVecf32`, `SVecf16`, `BVector`, `Veci8`]
     #[argh(option, default = "String::from(\"Vecf32\")")]
     vim_type: String,
 
@@ -86,6 +88,9 @@ impl CreateArguments {
                 d: distance_from_str(&self.distance)?,
             },
             indexing,
+            truncation: None,
+            seed: None,
+            partition: None,
         };
         let mut optimizing = OptimizingOptions::default();
         if let Some(num) = self.threads {
@@ -94,6 +99,7 @@ impl CreateArguments {
         let alterable_options = IndexAlterableOptions {
             optimizing,
             segment: SegmentOptions::default(),
+            search: SearchOptions::default(),
         };
         Ok((index_options, alterable_options))
     }
@@ -136,8 +142,16 @@ impl QueryArguments {
             sq_fast_scan: false,
             pq_fast_scan: false,
             rq_fast_scan: true,
+            rq_rerank_size: 0,
+            bq_rerank_size: 100,
             hnsw_ef_search: self.ef,
             ivf_nprobe: self.probe,
+            ivf_coarse_nprobe: 10,
+            ivf_adaptive_patience: 0,
+            hnsw_adaptive_patience: 0,
+            adaptive_k: 10,
+            truncation_rerank_size: 100,
+            multivector_candidates: 100,
         }
     }
 }
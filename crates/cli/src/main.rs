@@ -62,7 +62,7 @@ fn main() {
                 }
                 let owned_vec = convert_to_owned_vec(&vectors[i]);
                 let pointer = Pointer::new(count as u64);
-                match view.insert(owned_vec, pointer) {
+                match view.insert(owned_vec, pointer, None) {
                     Ok(res) => {
                         if res.is_err() {
                             info!("refresh the instance to insert vector {i}");
@@ -41,24 +41,31 @@ impl<O: OperatorFlat, Q: Quantizer<O>> Flat<O, Q> {
 
     pub fn vbase<'a>(
         &'a self,
-        vector: Borrowed<'a, O>,
+        vector: Borrowed<'_, O>,
         opts: &'a SearchOptions,
-    ) -> Box<dyn Iterator<Item = Element> + 'a> {
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
         let mut heap = Q::flat_rerank_start();
         let lut = self
             .quantization
             .flat_rerank_preprocess(self.quantization.project(vector).as_borrowed(), opts);
         self.quantization
             .flat_rerank_continue(&lut, 0..self.storage.len(), &mut heap);
+        let vector = vector.own();
         let mut reranker = self.quantization.flat_rerank_break(
             heap,
-            move |u| (O::distance(vector, self.storage.vector(u)), ()),
+            move |u| {
+                (
+                    O::distance(vector.as_borrowed(), self.storage.vector(u)),
+                    (),
+                )
+            },
             opts,
         );
         Box::new(std::iter::from_fn(move || {
             reranker.pop().map(|(dis_u, u, ())| Element {
                 distance: dis_u,
                 payload: AlwaysEqual(self.payload(u)),
+                key: AlwaysEqual(u),
             })
         }))
     }
@@ -92,6 +99,7 @@ fn from_nothing<O: OperatorFlat, Q: Quantizer<O>>(
         path.as_ref().join("quantization"),
         options.vector,
         flat_indexing_options.quantization,
+        options.seed,
         collection,
         |vector| vector.own(),
     );
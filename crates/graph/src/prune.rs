@@ -6,6 +6,20 @@ pub fn prune(
     edges: &mut Vec<(Distance, u32)>,
     add: &[(Distance, u32)],
     m: u32,
+) {
+    prune_keeping(dist, u, edges, add, 0, m)
+}
+
+/// Like [`prune`], but the nearest `keep` neighbors are kept even if they are
+/// closer to another neighbor, so that the graph stays connected among
+/// vertices a filter lets through.
+pub fn prune_keeping(
+    dist: impl Fn(u32, u32) -> Distance,
+    u: u32,
+    edges: &mut Vec<(Distance, u32)>,
+    add: &[(Distance, u32)],
+    keep: u32,
+    m: u32,
 ) {
     let mut trace = add.to_vec();
     trace.extend(edges.as_slice());
@@ -18,10 +32,11 @@ pub fn prune(
         if res.len() == m as usize {
             break;
         }
-        let check = res
-            .iter()
-            .map(|&(_, v)| dist(u, v))
-            .all(|dist| dist > dis_u);
+        let check = res.len() < keep as usize
+            || res
+                .iter()
+                .map(|&(_, v)| dist(u, v))
+                .all(|dist| dist > dis_u);
         if check {
             res.push((dis_u, u));
         }
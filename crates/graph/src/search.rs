@@ -155,19 +155,109 @@ where
     })
 }
 
+/// Like [`vbase_internal`], but only yields vertices passing `check`.
+///
+/// A neighbor failing `check` is not visited itself; its neighbors are
+/// visited instead, so that a search is not stuck in a subgraph where few
+/// vertices pass.
+pub fn vbase_internal_filtered<'a, G, E, F, T>(
+    visited: &'a VisitedPool,
+    s: u32,
+    mut reranker: G,
+    read_outs: impl Fn(u32) -> F + 'a,
+    check: impl Fn(u32) -> bool + 'a,
+) -> impl Iterator<Item = (Distance, u32, T)> + 'a
+where
+    G: RerankerPush + RerankerPop<(E, T)> + 'a,
+    E: Iterator<Item = u32>,
+    F: Iterator<Item = u32>,
+    T: 'a,
+{
+    let mut visited = visited.fetch_guard_checker();
+    {
+        visited.mark(s);
+        reranker.push(s);
+    }
+    std::iter::from_fn(move || loop {
+        let (dis_u, u, (outs_u, pay_u)) = reranker.pop()?;
+        for v in outs_u {
+            if !visited.check(v) {
+                continue;
+            }
+            visited.mark(v);
+            if check(v) {
+                reranker.push(v);
+                continue;
+            }
+            for w in read_outs(v) {
+                if !visited.check(w) || !check(w) {
+                    continue;
+                }
+                visited.mark(w);
+                reranker.push(w);
+            }
+        }
+        // the entry point is visited even if it fails
+        if u != s || check(u) {
+            return Some((dis_u, u, pay_u));
+        }
+    })
+}
+
+/// Searches with `ef_search` candidates.
+///
+/// If `patience` is not zero, the first stage also ends once `patience`
+/// vertices in a row fail to improve the nearest `adaptive_k` ones, and the
+/// rest is searched lazily.
 pub fn vbase_generic<'a, G, E, T>(
     visited: &'a VisitedPool,
     s: u32,
     reranker: G,
     ef_search: u32,
+    adaptive_k: u32,
+    patience: u32,
+) -> impl Iterator<Item = (Distance, u32, T)> + 'a
+where
+    G: RerankerPush + RerankerPop<(E, T)> + 'a,
+    E: Iterator<Item = u32>,
+    T: 'a,
+{
+    let iter = vbase_internal(visited, s, reranker);
+    staged(iter, ef_search, adaptive_k, patience)
+}
+
+/// Like [`vbase_generic`], but only yields vertices passing `check`, with
+/// two-hop expansion over vertices failing it.
+#[allow(clippy::too_many_arguments)]
+pub fn vbase_generic_filtered<'a, G, E, F, T>(
+    visited: &'a VisitedPool,
+    s: u32,
+    reranker: G,
+    read_outs: impl Fn(u32) -> F + 'a,
+    check: impl Fn(u32) -> bool + 'a,
+    ef_search: u32,
+    adaptive_k: u32,
+    patience: u32,
 ) -> impl Iterator<Item = (Distance, u32, T)> + 'a
 where
     G: RerankerPush + RerankerPop<(E, T)> + 'a,
     E: Iterator<Item = u32>,
+    F: Iterator<Item = u32>,
     T: 'a,
 {
-    let mut iter = vbase_internal(visited, s, reranker);
+    let iter = vbase_internal_filtered(visited, s, reranker, read_outs, check);
+    staged(iter, ef_search, adaptive_k, patience)
+}
+
+fn staged<'a, T: 'a>(
+    mut iter: impl Iterator<Item = (Distance, u32, T)> + 'a,
+    ef_search: u32,
+    adaptive_k: u32,
+    patience: u32,
+) -> impl Iterator<Item = (Distance, u32, T)> + 'a {
     let mut results = Results::new(ef_search as _);
+    let mut nearest = Results::new(adaptive_k as _);
+    let mut stale = 0_u32;
     let mut stage1 = Vec::new();
     for (dis_u, u, pay_u) in &mut iter {
         if results.check(dis_u) {
@@ -177,6 +267,17 @@ where
             stage1.push((dis_u, u, pay_u));
             break;
         }
+        if patience != 0 {
+            if nearest.check(dis_u) {
+                nearest.push((dis_u, AlwaysEqual(u)));
+                stale = 0;
+            } else {
+                stale += 1;
+                if stale >= patience {
+                    break;
+                }
+            }
+        }
     }
     stage1.sort_unstable_by_key(|x| x.0);
     let mut stage1 = stage1.into_iter().peekable();
@@ -31,6 +31,7 @@ impl VisitedPool {
             buffer.version = buffer.version.wrapping_add(1);
             if buffer.version == 0 {
                 buffer.data.fill(0);
+                buffer.version = 1;
             }
         }
         VisitedGuardChecker { buffer, pool: self }
@@ -45,8 +46,10 @@ pub struct VisitedGuard<'a> {
 impl<'a> VisitedGuard<'a> {
     pub fn fetch_checker(&mut self) -> VisitedChecker<'_> {
         self.buffer.version = self.buffer.version.wrapping_add(1);
+        // cleared data is `0`, so `0` would mark every vertex as visited
         if self.buffer.version == 0 {
             self.buffer.data.fill(0);
+            self.buffer.version = 1;
         }
         VisitedChecker {
             buffer: &mut self.buffer,
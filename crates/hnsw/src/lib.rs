@@ -38,6 +38,7 @@ pub struct Hnsw<O: OperatorHnsw, Q: Quantizer<O>> {
     hyper_graph_outs: MmapArray<u32>,
     hyper_graph_weights: MmapArray<Distance>,
     m: Json<u32>,
+    gamma: Json<u32>,
     s: Option<u32>,
     visited: VisitedPool,
 }
@@ -66,9 +67,9 @@ impl<O: OperatorHnsw, Q: Quantizer<O>> Hnsw<O, Q> {
 
     pub fn vbase<'a>(
         &'a self,
-        vector: Borrowed<'a, O>,
+        vector: Borrowed<'_, O>,
         opts: &'a SearchOptions,
-    ) -> Box<dyn Iterator<Item = Element> + 'a> {
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
         let Some(s) = self.s else {
             return Box::new(std::iter::empty());
         };
@@ -80,19 +81,72 @@ impl<O: OperatorHnsw, Q: Quantizer<O>> Hnsw<O, Q> {
             1..=hierarchy_for_a_vertex(*self.m, s) - 1,
             s,
         );
+        let vector = vector.own();
         let reranker = self.quantization.graph_rerank(lut, move |u| {
             (
-                O::distance(self.storage.vector(u), vector),
+                O::distance(self.storage.vector(u), vector.as_borrowed()),
                 (base_outs(self, u), ()),
             )
         });
         Box::new(
-            graph::search::vbase_generic(&self.visited, s, reranker, opts.hnsw_ef_search).map(
-                |(dis_u, u, ())| Element {
-                    distance: dis_u,
-                    payload: AlwaysEqual(self.payload(u)),
-                },
-            ),
+            graph::search::vbase_generic(
+                &self.visited,
+                s,
+                reranker,
+                opts.hnsw_ef_search,
+                opts.adaptive_k,
+                opts.hnsw_adaptive_patience,
+            )
+            .map(|(dis_u, u, ())| Element {
+                distance: dis_u,
+                payload: AlwaysEqual(self.payload(u)),
+                key: AlwaysEqual(u),
+            }),
+        )
+    }
+
+    /// Searches among vectors whose keys pass `filter`. Neighbors failing it
+    /// are not visited, but their neighbors are.
+    pub fn vbase_filtered<'a>(
+        &'a self,
+        vector: Borrowed<'_, O>,
+        opts: &'a SearchOptions,
+        filter: impl Fn(u32) -> bool + Send + 'a,
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
+        let Some(s) = self.s else {
+            return Box::new(std::iter::empty());
+        };
+        let projected_vector = self.quantization.project(vector);
+        let lut = self.quantization.preprocess(projected_vector.as_borrowed());
+        let s = fast_search(
+            |u| self.quantization.process(&self.storage, &lut, u),
+            |x, i| hyper_outs(self, x, i),
+            1..=hierarchy_for_a_vertex(*self.m, s) - 1,
+            s,
+        );
+        let vector = vector.own();
+        let reranker = self.quantization.graph_rerank(lut, move |u| {
+            (
+                O::distance(self.storage.vector(u), vector.as_borrowed()),
+                (base_outs(self, u), ()),
+            )
+        });
+        Box::new(
+            graph::search::vbase_generic_filtered(
+                &self.visited,
+                s,
+                reranker,
+                move |u| base_outs(self, u),
+                filter,
+                opts.hnsw_ef_search,
+                opts.adaptive_k,
+                opts.hnsw_adaptive_patience,
+            )
+            .map(|(dis_u, u, ())| Element {
+                distance: dis_u,
+                payload: AlwaysEqual(self.payload(u)),
+                key: AlwaysEqual(u),
+            }),
         )
     }
 
@@ -122,6 +176,8 @@ fn from_nothing<O: OperatorHnsw, Q: Quantizer<O>>(
     let HnswIndexingOptions {
         m,
         ef_construction,
+        gamma,
+        m_beta,
         quantization: quantization_options,
     } = options.indexing.clone().unwrap_hnsw();
     let mut g = fresh(collection.len(), m);
@@ -131,16 +187,20 @@ fn from_nothing<O: OperatorHnsw, Q: Quantizer<O>>(
         collection.len(),
         ef_construction,
         m,
+        gamma,
+        m_beta,
+        options.seed.is_some(),
         &mut g,
     );
     rayon::check();
-    finish(&mut g, m);
+    finish(&mut g, m, gamma);
     let storage = O::Storage::create(path.as_ref().join("storage"), collection);
     rayon::check();
     let quantization = Quantization::<O, Q>::create(
         path.as_ref().join("quantization"),
         options.vector,
         quantization_options,
+        options.seed,
         collection,
         |vector| vector.own(),
     );
@@ -181,6 +241,7 @@ fn from_nothing<O: OperatorHnsw, Q: Quantizer<O>>(
     );
     rayon::check();
     let m = Json::create(path.as_ref().join("m"), m);
+    let gamma = Json::create(path.as_ref().join("gamma"), gamma);
     Hnsw {
         storage,
         quantization,
@@ -190,6 +251,7 @@ fn from_nothing<O: OperatorHnsw, Q: Quantizer<O>>(
         hyper_graph_outs,
         hyper_graph_weights,
         m,
+        gamma,
         s: start(collection.len(), *m),
         visited: VisitedPool::new(collection.len()),
     }
@@ -205,6 +267,8 @@ fn from_main<O: OperatorHnsw, Q: Quantizer<O>>(
     let HnswIndexingOptions {
         m,
         ef_construction,
+        gamma,
+        m_beta,
         quantization: quantization_options,
     } = options.indexing.clone().unwrap_hnsw();
     let mut g = fresh(remapped.len(), m);
@@ -220,6 +284,8 @@ fn from_main<O: OperatorHnsw, Q: Quantizer<O>>(
         },
         remapped.len(),
         m,
+        gamma,
+        m_beta,
         &mut g,
     );
     rayon::check();
@@ -229,16 +295,20 @@ fn from_main<O: OperatorHnsw, Q: Quantizer<O>>(
         remapped.len(),
         ef_construction,
         m,
+        gamma,
+        m_beta,
+        options.seed.is_some(),
         &mut g,
     );
     rayon::check();
-    finish(&mut g, m);
+    finish(&mut g, m, gamma);
     let storage = O::Storage::create(path.as_ref().join("storage"), remapped);
     rayon::check();
     let quantization = Quantization::<O, Q>::create(
         path.as_ref().join("quantization"),
         options.vector,
         quantization_options,
+        options.seed,
         remapped,
         |vector| vector.own(),
     );
@@ -279,6 +349,7 @@ fn from_main<O: OperatorHnsw, Q: Quantizer<O>>(
     );
     rayon::check();
     let m = Json::create(path.as_ref().join("m"), m);
+    let gamma = Json::create(path.as_ref().join("gamma"), gamma);
     rayon::check();
     Hnsw {
         storage,
@@ -289,6 +360,7 @@ fn from_main<O: OperatorHnsw, Q: Quantizer<O>>(
         hyper_graph_outs,
         hyper_graph_weights,
         m,
+        gamma,
         s: start(remapped.len(), *m),
         visited: VisitedPool::new(remapped.len()),
     }
@@ -303,6 +375,12 @@ fn open<O: OperatorHnsw, Q: Quantizer<O>>(path: impl AsRef<Path>) -> Hnsw<O, Q>
     let hyper_graph_outs = MmapArray::open(path.as_ref().join("hyper_graph_outs"));
     let hyper_graph_weights = MmapArray::open(path.as_ref().join("hyper_graph_weights"));
     let m = Json::open(path.as_ref().join("m"));
+    // segments built before `gamma` was introduced have no such file
+    let gamma = if path.as_ref().join("gamma").exists() {
+        Json::open(path.as_ref().join("gamma"))
+    } else {
+        Json(1)
+    };
     let n = storage.len();
     Hnsw {
         storage,
@@ -313,6 +391,7 @@ fn open<O: OperatorHnsw, Q: Quantizer<O>>(path: impl AsRef<Path>) -> Hnsw<O, Q>
         hyper_graph_outs,
         hyper_graph_weights,
         m,
+        gamma,
         s: start(n, *m),
         visited: VisitedPool::new(n),
     }
@@ -356,12 +435,15 @@ fn fresh(n: u32, m: u32) -> Vec<Vec<RwLock<Vec<(Distance, u32)>>>> {
     g
 }
 
+#[allow(clippy::too_many_arguments)]
 fn patch_deletions<E>(
     dist: impl Fn(u32, u32) -> Distance + Copy + Sync,
     skip: impl Fn(u32) -> bool + Sync,
     read_edges: impl Fn(u32, u8) -> E + Sync,
     n: u32,
     m: u32,
+    gamma: u32,
+    m_beta: u32,
     g: &mut [Vec<RwLock<Vec<(Distance, u32)>>>],
 ) where
     E: Iterator<Item = (Distance, u32)>,
@@ -383,18 +465,29 @@ fn patch_deletions<E>(
                 let v_ori = read_edges(v, level).map(|(_, w)| w);
                 add.extend(v_ori.filter(|&w| skip(w)).map(|w| (dist(u, w), w)));
             }
-            graph::prune::prune(dist, u, &mut base, &add, m);
+            graph::prune::prune_keeping(
+                dist,
+                u,
+                &mut base,
+                &add,
+                keep_for_a_hierarchy(m_beta, level),
+                capacity_for_a_hierarchy(m, gamma, level),
+            );
             *g[u as usize][level as usize].write() = base;
         }
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn patch_insertions(
     dist: impl Fn(u32, u32) -> Distance + Copy + Sync,
     skip: impl Fn(u32) -> bool + Sync,
     n: u32,
     ef_construction: u32,
     m: u32,
+    gamma: u32,
+    m_beta: u32,
+    deterministic: bool,
     g: &mut [Vec<RwLock<Vec<(Distance, u32)>>>],
 ) {
     #[repr(C)]
@@ -455,36 +548,12 @@ fn patch_insertions(
         .into_u64(),
     );
     let visited = VisitedPool::new(n);
-    (0..n).into_par_iter().for_each(|u| {
-        rayon::check();
-        if skip(u) {
-            return;
-        }
+    // neighbours of `u` at each level, searched from `start`
+    let search = |u: u32, start: Option<u32>| {
         let mut visited = visited.fetch_guard();
         let l = hierarchy_for_a_vertex(m, u);
-        let mut start = Start::from_u64(s.load(Ordering::Acquire));
-        let update_start = loop {
-            if start.is_holding() {
-                rayon::check();
-                std::thread::yield_now();
-                continue;
-            }
-            if start.is_empty() || g[start.val as usize].len() < g[u as usize].len() {
-                match s.compare_exchange_weak(
-                    start.into_u64(),
-                    Start::new(u, true).into_u64(),
-                    Ordering::AcqRel,
-                    Ordering::Acquire,
-                ) {
-                    Ok(_) => break true,
-                    Err(val) => start = Start::from_u64(val),
-                }
-                continue;
-            }
-            break false;
-        };
         let mut temp = vec![vec![]; l as usize];
-        if let Some(mut cursor) = start.start() {
+        if let Some(mut cursor) = start {
             let t = hierarchy_for_a_vertex(m, cursor);
             if t > l {
                 cursor = fast_search(
@@ -514,12 +583,13 @@ fn patch_insertions(
                     cursor,
                     ef_construction,
                 );
-                graph::prune::prune(
+                graph::prune::prune_keeping(
                     dist,
                     u,
                     &mut temp[j as usize],
                     &scope,
-                    capacity_for_a_hierarchy(m, j),
+                    keep_for_a_hierarchy(m_beta, j),
+                    capacity_for_a_hierarchy(m, gamma, j),
                 );
                 cursor = if let Some(x) = scope.first() {
                     x.1
@@ -529,6 +599,10 @@ fn patch_insertions(
                 temp[j as usize] = scope;
             }
         }
+        temp
+    };
+    let link = |u: u32, temp: &[Vec<(Distance, u32)>]| {
+        let l = hierarchy_for_a_vertex(m, u);
         for j in 0..l {
             g[u as usize][j as usize]
                 .write()
@@ -545,27 +619,86 @@ fn patch_insertions(
                     lock.insert(index, (dis_v, u));
                     continue;
                 }
-                graph::prune::prune(
+                graph::prune::prune_keeping(
                     dist,
                     v,
                     &mut lock,
                     &[(dis_v, u)],
-                    capacity_for_a_hierarchy(m, j),
+                    keep_for_a_hierarchy(m_beta, j),
+                    capacity_for_a_hierarchy(m, gamma, j),
                 );
             }
         }
+    };
+    if deterministic {
+        // Edges depend on the order of insertions, so vertices are inserted in
+        // batches. Vertices of a batch are searched in parallel in the graph of
+        // previous batches, and then linked one by one in a fixed order. A batch
+        // is small relative to the graph, so that missing edges between its
+        // vertices hardly matter.
+        let mut start = Start::from_u64(s.load(Ordering::Acquire)).start();
+        let mut i = 0;
+        while i < n {
+            rayon::check();
+            let batch = i..std::cmp::min(n, i + (i / 16).clamp(1, 1024));
+            let temps = batch
+                .clone()
+                .into_par_iter()
+                .map(|u| (!skip(u)).then(|| search(u, start)))
+                .collect::<Vec<_>>();
+            for (u, temp) in batch.clone().zip(temps) {
+                let Some(temp) = temp else {
+                    continue;
+                };
+                link(u, &temp);
+                if start.map_or(true, |x| g[x as usize].len() < g[u as usize].len()) {
+                    start = Some(u);
+                }
+            }
+            i = batch.end;
+        }
+        return;
+    }
+    (0..n).into_par_iter().for_each(|u| {
+        rayon::check();
+        if skip(u) {
+            return;
+        }
+        let mut start = Start::from_u64(s.load(Ordering::Acquire));
+        let update_start = loop {
+            if start.is_holding() {
+                rayon::check();
+                std::thread::yield_now();
+                continue;
+            }
+            if start.is_empty() || g[start.val as usize].len() < g[u as usize].len() {
+                match s.compare_exchange_weak(
+                    start.into_u64(),
+                    Start::new(u, true).into_u64(),
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => break true,
+                    Err(val) => start = Start::from_u64(val),
+                }
+                continue;
+            }
+            break false;
+        };
+        let temp = search(u, start.start());
+        link(u, &temp);
         if update_start {
             s.store(Start::new(u, false).into_u64(), Ordering::Release);
         }
     });
 }
 
-fn finish(g: &mut [Vec<RwLock<Vec<(Distance, u32)>>>], m: u32) {
+fn finish(g: &mut [Vec<RwLock<Vec<(Distance, u32)>>>], m: u32, gamma: u32) {
     for u in 0..g.len() as u32 {
         let l = hierarchy_for_a_vertex(m, u);
         for j in 0..l {
             g[u as usize][j as usize].get_mut().resize(
-                capacity_for_a_hierarchy(m, j) as usize,
+                capacity_for_a_hierarchy(m, gamma, j) as usize,
                 (Distance::INFINITY, u32::MAX),
             );
         }
@@ -582,27 +715,33 @@ fn hierarchy_for_a_vertex(m: u32, u: u32) -> u8 {
     ans
 }
 
-fn capacity_for_a_hierarchy(m: u32, level: u8) -> u32 {
+fn capacity_for_a_hierarchy(m: u32, gamma: u32, level: u8) -> u32 {
     if level == 0 {
-        m * 2
+        m * 2 * gamma
     } else {
         m
     }
 }
 
+fn keep_for_a_hierarchy(m_beta: u32, level: u8) -> u32 {
+    if level == 0 {
+        m_beta
+    } else {
+        0
+    }
+}
+
 fn base_edges<O: OperatorHnsw, Q: Quantizer<O>>(
     hnsw: &Hnsw<O, Q>,
     u: u32,
 ) -> impl Iterator<Item = (Distance, u32)> + '_ {
-    let m = *hnsw.m;
-    let offset = 2 * m as usize * u as usize;
-    let edges_outs = hnsw.base_graph_outs[offset..offset + 2 * m as usize]
+    let c = capacity_for_a_hierarchy(*hnsw.m, *hnsw.gamma, 0) as usize;
+    let offset = c * u as usize;
+    let edges_outs = hnsw.base_graph_outs[offset..offset + c]
         .iter()
         .take_while(|v| **v != u32::MAX)
         .copied();
-    let edges_weights = hnsw.base_graph_weights[offset..offset + 2 * m as usize]
-        .iter()
-        .copied();
+    let edges_weights = hnsw.base_graph_weights[offset..offset + c].iter().copied();
     edges_weights.zip(edges_outs)
 }
 
@@ -610,9 +749,9 @@ fn base_outs<O: OperatorHnsw, Q: Quantizer<O>>(
     hnsw: &Hnsw<O, Q>,
     u: u32,
 ) -> impl Iterator<Item = u32> + '_ {
-    let m = *hnsw.m;
-    let offset = 2 * m as usize * u as usize;
-    hnsw.base_graph_outs[offset..offset + 2 * m as usize]
+    let c = capacity_for_a_hierarchy(*hnsw.m, *hnsw.gamma, 0) as usize;
+    let offset = c * u as usize;
+    hnsw.base_graph_outs[offset..offset + c]
         .iter()
         .take_while(|v| **v != u32::MAX)
         .copied()
@@ -4,6 +4,7 @@ pub mod delete;
 pub mod optimizing;
 pub mod segment;
 
+mod tune;
 mod utils;
 
 use self::delete::Delete;
@@ -12,6 +13,7 @@ use self::segment::sealed::SealedSegment;
 use crate::optimizing::Optimizing;
 use crate::utils::tournament_tree::LoserTree;
 use arc_swap::ArcSwap;
+use base::always_equal::AlwaysEqual;
 use base::distance::Distance;
 use base::index::*;
 use base::operator::*;
@@ -26,6 +28,7 @@ use crossbeam::atomic::AtomicCell;
 use crossbeam::channel::Sender;
 use indexing::OperatorIndexing;
 use parking_lot::Mutex;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -33,6 +36,7 @@ use std::convert::Infallible;
 use std::num::NonZeroU128;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::LazyLock;
 use std::thread::JoinHandle;
 use std::time::Instant;
 use thiserror::Error;
@@ -115,6 +119,7 @@ impl<O: Op> Index<O> {
                 read_segments: HashMap::new(),
                 delete: delete.clone(),
                 write_segment: None,
+                partitions: None,
             })),
             instant_indexed: AtomicCell::new(Instant::now()),
             instant_written: AtomicCell::new(Instant::now()),
@@ -201,6 +206,7 @@ impl<O: Op> Index<O> {
                 sealed_segments,
                 read_segments,
                 write_segment: None,
+                partitions: None,
             })),
             instant_indexed: AtomicCell::new(Instant::now()),
             instant_written: AtomicCell::new(Instant::now()),
@@ -317,6 +323,7 @@ impl<O: Op> Index<O> {
     pub fn create_sealed_segment(
         &self,
         source: &(impl Vectors<O::Vector> + Collection + Source + Sync),
+        partition: impl Fn(u32) -> Option<u64>,
         sealed_segment_ids: &[NonZeroU128],
         growing_segment_ids: &[NonZeroU128],
     ) -> Option<Arc<SealedSegment<O>>> {
@@ -333,6 +340,7 @@ impl<O: Op> Index<O> {
             id,
             self.options.clone(),
             source,
+            partition,
         );
         sync_walk_from_dir(self.path.join("sealed_segments").join(id.to_string()));
         sync_dir(self.path.join("sealed_segments"));
@@ -374,6 +382,47 @@ impl Drop for IndexTracker {
     }
 }
 
+type Stage2<'a, T> = Box<dyn Iterator<Item = (Element, AlwaysEqual<T>)> + Send + 'a>;
+
+type Task<'a, T> = Box<dyn FnOnce() -> Stage2<'a, T> + Send + 'a>;
+
+// One pool is shared by searches of all indexes, so the number of search
+// threads is bounded by the available parallelism.
+static SEARCH_POOL: LazyLock<rayon::ThreadPool> = LazyLock::new(|| {
+    rayon::ThreadPoolBuilder::new()
+        .thread_name(|i| format!("search-{i}"))
+        .build()
+        .expect("failed to build the search thread pool")
+});
+
+// Segment searches do most of their work before yielding the first element, so
+// peeking each iterator in the pool moves that work off the calling thread.
+// Tasks are split into at most `threads` chunks and a chunk is searched on one
+// thread, so that a query never runs on more threads than it's allowed to.
+fn search_parallel<'a, T: Send + 'a>(
+    tasks: Vec<Task<'a, T>>,
+    threads: usize,
+) -> Vec<std::iter::Peekable<Stage2<'a, T>>> {
+    let size = tasks.len().div_ceil(threads);
+    let mut chunks = Vec::with_capacity(threads);
+    let mut tasks = tasks.into_iter();
+    while tasks.len() != 0 {
+        chunks.push(tasks.by_ref().take(size).collect::<Vec<_>>());
+    }
+    SEARCH_POOL.install(|| {
+        chunks
+            .into_par_iter()
+            .flat_map_iter(|chunk| {
+                chunk.into_iter().map(|task| {
+                    let mut iterator = task().peekable();
+                    iterator.peek();
+                    iterator
+                })
+            })
+            .collect()
+    })
+}
+
 pub struct IndexView<O: Op> {
     pub options: IndexOptions,
     pub alterable_options: IndexAlterableOptions,
@@ -381,14 +430,100 @@ pub struct IndexView<O: Op> {
     pub sealed_segments: HashMap<NonZeroU128, Arc<SealedSegment<O>>>,
     pub read_segments: HashMap<NonZeroU128, Arc<GrowingSegment<O>>>,
     pub write_segment: Option<(NonZeroU128, Arc<GrowingSegment<O>>)>,
+    // if it's set, only vectors of these partitions are searched
+    pub partitions: Option<Vec<u64>>,
 }
 
 impl<O: Op> IndexView<O> {
+    /// The view restricted to vectors of the given partitions. Sealed segments
+    /// without vectors of them are left out.
+    pub fn partition(&self, keys: &[u64]) -> IndexView<O> {
+        IndexView {
+            options: self.options.clone(),
+            alterable_options: self.alterable_options.clone(),
+            delete: self.delete.clone(),
+            sealed_segments: self
+                .sealed_segments
+                .iter()
+                .filter(|(_, x)| x.has_partitions(keys))
+                .map(|(id, x)| (*id, x.clone()))
+                .collect(),
+            read_segments: self.read_segments.clone(),
+            write_segment: self.write_segment.clone(),
+            partitions: Some(keys.to_vec()),
+        }
+    }
+    /// Search options, where options that `opts` leaves unset are taken from
+    /// the index.
+    pub fn search_options(&self, opts: &PartialSearchOptions) -> SearchOptions {
+        opts.or(&self.alterable_options.search)
+    }
     pub fn vbase<'a>(
         &'a self,
         vector: Borrowed<'a, O>,
         opts: &'a SearchOptions,
     ) -> Result<impl Iterator<Item = (Distance, Pointer)> + 'a, VbaseError> {
+        Ok(self
+            .vbase_generic(vector, opts, false)?
+            .map(|(distance, pointer, _)| (distance, pointer)))
+    }
+    pub fn vbase_vector<'a>(
+        &'a self,
+        vector: Borrowed<'a, O>,
+        opts: &'a SearchOptions,
+    ) -> Result<impl Iterator<Item = (Distance, Pointer, O::Vector)> + 'a, VbaseError> {
+        Ok(self
+            .vbase_generic(vector, opts, true)?
+            .map(|(distance, pointer, vector)| (distance, pointer, vector.unwrap())))
+    }
+    /// Searches with every vector of a multivector query and sums, for each
+    /// row, the nearest distance of its sub-vectors. A row missing from the
+    /// candidates of a query vector is given the farthest candidate distance.
+    /// As long as every search returns sub-vectors in the order of distance,
+    /// the sum is a lower bound of the distance of the row. Rows are sorted by
+    /// it, and a row is returned only if one of its sub-vectors is among the
+    /// first `multivector_candidates` results of a query vector.
+    pub fn vbase_multi<'a>(
+        &'a self,
+        vectors: &[Borrowed<'a, O>],
+        opts: &'a SearchOptions,
+    ) -> Result<Vec<(Distance, Pointer)>, VbaseError> {
+        if vectors.is_empty() {
+            return Err(VbaseError::InvalidVector);
+        }
+        let n = vectors.len();
+        let candidates = opts.multivector_candidates as usize;
+        let mut bounds = Vec::with_capacity(n);
+        let mut rows = HashMap::<Pointer, Vec<Option<Distance>>>::new();
+        for (i, &vector) in vectors.iter().enumerate() {
+            let mut bound = Distance::NEG_INFINITY;
+            for (distance, pointer) in self.vbase(vector, opts)?.take(candidates) {
+                // sub-vectors share the pointer of their row, and the first one is the nearest
+                let row = rows.entry(pointer).or_insert_with(|| vec![None; n]);
+                row[i].get_or_insert(distance);
+                bound = distance;
+            }
+            bounds.push(bound);
+        }
+        let mut result = rows
+            .into_iter()
+            .map(|(pointer, row)| {
+                let sum = std::iter::zip(row, &bounds)
+                    .map(|(distance, &bound)| f32::from(distance.unwrap_or(bound)))
+                    .sum::<f32>();
+                (Distance::from(sum), pointer)
+            })
+            .collect::<Vec<_>>();
+        result.sort_unstable();
+        Ok(result)
+    }
+    // Vectors are only looked up in segments if `fetch` is set.
+    fn vbase_generic<'a>(
+        &'a self,
+        vector: Borrowed<'a, O>,
+        opts: &'a SearchOptions,
+        fetch: bool,
+    ) -> Result<impl Iterator<Item = (Distance, Pointer, Option<O::Vector>)> + 'a, VbaseError> {
         if self.options.vector.dims != vector.dims() {
             return Err(VbaseError::InvalidVector);
         }
@@ -399,23 +534,57 @@ impl<O: Op> IndexView<O> {
         }
 
         let n = self.sealed_segments.len() + self.read_segments.len() + 1;
-        let mut iterators = Vec::with_capacity(n);
+        let mut tasks: Vec<Task<'a, Option<O::Vector>>> = Vec::with_capacity(n);
+        let partitions = self.partitions.as_deref();
         for (_, sealed) in self.sealed_segments.iter() {
-            let stage2 = sealed.vbase(vector, opts);
-            iterators.push(stage2);
+            tasks.push(Box::new(move || {
+                let stage2 = match partitions {
+                    Some(keys) => sealed.vbase_partitioned(vector, opts, keys),
+                    None => sealed.vbase(vector, opts),
+                };
+                if fetch {
+                    Box::new(
+                        stage2.map(move |x| (x, AlwaysEqual(Some(sealed.vector(x.key.0).own())))),
+                    )
+                } else {
+                    Box::new(stage2.map(|x| (x, AlwaysEqual(None))))
+                }
+            }));
         }
         for (_, read) in self.read_segments.iter() {
-            let stage2 = read.vbase(vector, opts);
-            iterators.push(stage2);
+            tasks.push(Box::new(move || {
+                let stage2 = read.vbase(vector, opts, partitions);
+                if fetch {
+                    Box::new(
+                        stage2.map(move |x| (x, AlwaysEqual(Some(read.vector(x.key.0).own())))),
+                    )
+                } else {
+                    Box::new(stage2.map(|x| (x, AlwaysEqual(None))))
+                }
+            }));
         }
         if let Some((_, write)) = &self.write_segment {
-            let stage2 = write.vbase(vector, opts);
-            iterators.push(stage2);
+            tasks.push(Box::new(move || {
+                let stage2 = write.vbase(vector, opts, partitions);
+                if fetch {
+                    Box::new(
+                        stage2.map(move |x| (x, AlwaysEqual(Some(write.vector(x.key.0).own())))),
+                    )
+                } else {
+                    Box::new(stage2.map(|x| (x, AlwaysEqual(None))))
+                }
+            }));
         }
+        let threads = self.alterable_options.segment.search_threads as usize;
+        let iterators = if threads > 1 && tasks.len() > 1 {
+            search_parallel(tasks, threads)
+        } else {
+            tasks.into_iter().map(|task| task().peekable()).collect()
+        };
         let loser = LoserTree::new(iterators);
-        Ok(loser.filter_map(|x| {
+        Ok(loser.filter_map(|(x, AlwaysEqual(v))| {
             if self.delete.check(x.payload.0) {
-                Some((x.distance, x.payload.0.pointer()))
+                Some((x.distance, x.payload.0.pointer(), v))
             } else {
                 None
             }
@@ -446,6 +615,7 @@ impl<O: Op> IndexView<O> {
         &self,
         vector: O::Vector,
         pointer: Pointer,
+        partition: Option<u64>,
     ) -> Result<Result<(), OutdatedError>, InsertError> {
         if self.options.vector.dims != vector.as_borrowed().dims() {
             return Err(InsertError::InvalidVector);
@@ -454,7 +624,7 @@ impl<O: Op> IndexView<O> {
         let payload = Payload::new(pointer, self.delete.version(pointer));
         if let Some((_, segment)) = self.write_segment.as_ref() {
             use crate::segment::growing::GrowingSegmentInsertError;
-            if let Err(GrowingSegmentInsertError) = segment.insert(vector, payload) {
+            if let Err(GrowingSegmentInsertError) = segment.insert(vector, payload, partition) {
                 return Ok(Err(OutdatedError));
             }
             Ok(Ok(()))
@@ -504,6 +674,7 @@ impl<O: Op> IndexProtect<O> {
             sealed_segments: self.sealed_segments.clone(),
             read_segments: self.read_segments.clone(),
             write_segment: self.write_segment.clone(),
+            partitions: None,
         });
         let read_segment_ids = self.read_segments.keys().copied();
         let write_segment_id = self.write_segment.as_ref().map(|(id, _)| *id);
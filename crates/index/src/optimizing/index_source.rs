@@ -32,6 +32,22 @@ impl<O: Op> IndexSource<O::Vector, O> {
             _phantom: PhantomData,
         }
     }
+
+    pub fn partition(&self, mut index: u32) -> Option<u64> {
+        for x in self.sealed.iter() {
+            if index < x.len() {
+                return x.partition(index);
+            }
+            index -= x.len();
+        }
+        for x in self.growing.iter() {
+            if index < x.len() {
+                return x.partition(index);
+            }
+            index -= x.len();
+        }
+        panic!("Out of bound.")
+    }
 }
 
 impl<O: Op> Vectors<O::Vector> for IndexSource<O::Vector, O> {
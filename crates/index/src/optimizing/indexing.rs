@@ -89,6 +89,7 @@ pub fn scan<O: Op>(
 pub fn make<O: Op>(index: Arc<Index<O>>, source: IndexSource<O::Vector, O>) {
     let _ = index.create_sealed_segment(
         &source,
+        |i| source.partition(i),
         &source.sealed.iter().map(|x| x.id()).collect::<Vec<_>>(),
         &source.growing.iter().map(|x| x.id()).collect::<Vec<_>>(),
     );
@@ -74,7 +74,15 @@ impl<O: Op> GrowingSegment<O> {
         let mut wal = FileWal::open(&path);
         let mut vec = Vec::new();
         while let Some(log) = wal.read() {
-            let log = bincode::deserialize::<Log<O>>(&log).unwrap();
+            let log = bincode::deserialize::<Log<O>>(&log).unwrap_or_else(|_| {
+                // written before partitions are logged
+                let (vector, payload) = bincode::deserialize(&log).unwrap();
+                Log {
+                    vector,
+                    payload,
+                    partition: None,
+                }
+            });
             vec.push(MaybeUninit::new(UnsafeCell::new(log)));
         }
         wal.truncate();
@@ -133,8 +141,13 @@ impl<O: Op> GrowingSegment<O> {
         &self,
         vector: O::Vector,
         payload: Payload,
+        partition: Option<u64>,
     ) -> Result<(), GrowingSegmentInsertError> {
-        let log = Log { vector, payload };
+        let log = Log {
+            vector,
+            payload,
+            partition,
+        };
         let i;
         {
             let mut pro = self.pro.lock();
@@ -199,19 +212,35 @@ impl<O: Op> GrowingSegment<O> {
         log.payload
     }
 
+    pub fn partition(&self, i: u32) -> Option<u64> {
+        let i = i as usize;
+        if i >= self.len.load(Ordering::Acquire) {
+            panic!("Out of bound.");
+        }
+        let log = unsafe { &*self.vec[i].assume_init_ref().get().cast_const() };
+        log.partition
+    }
+
     pub fn vbase<'a>(
         &'a self,
         vector: Borrowed<'a, O>,
         _opts: &SearchOptions,
-    ) -> Box<dyn Iterator<Item = Element> + 'a> {
+        partitions: Option<&[u64]>,
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
         let n = self.len.load(Ordering::Acquire);
         let mut result = Vec::new();
         for i in 0..n {
             let log = unsafe { &*self.vec[i].assume_init_ref().get().cast_const() };
+            if let Some(keys) = partitions {
+                if !log.partition.is_some_and(|key| keys.contains(&key)) {
+                    continue;
+                }
+            }
             let distance = O::distance(vector, log.vector.as_borrowed());
             result.push(Element {
                 distance,
                 payload: AlwaysEqual(log.payload),
+                key: AlwaysEqual(i as u32),
             });
         }
         result.sort_unstable();
@@ -237,6 +266,7 @@ impl<O: Op> Drop for GrowingSegment<O> {
 struct Log<O: Op> {
     vector: O::Vector,
     payload: Payload,
+    partition: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -1,15 +1,19 @@
 use crate::utils::dir_ops::dir_size;
 use crate::IndexTracker;
 use crate::Op;
+use base::always_equal::AlwaysEqual;
 use base::index::*;
 use base::operator::*;
 use base::search::*;
+use common::json::Json;
+use common::mmap_array::MmapArray;
 use crossbeam::atomic::AtomicCell;
 use indexing::SealedIndexing;
 use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::num::NonZeroU128;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -18,6 +22,7 @@ pub struct SealedSegment<O: Op> {
     id: NonZeroU128,
     path: PathBuf,
     indexing: SealedIndexing<O>,
+    partitions: Option<SegmentPartitions>,
     deletes: AtomicCell<(Instant, u32)>,
     _sealed_segment_tracker: SealedSegmentTracker,
     _index_tracker: Arc<IndexTracker>,
@@ -38,12 +43,26 @@ impl<O: Op> SealedSegment<O> {
         id: NonZeroU128,
         options: IndexOptions,
         source: &(impl Vectors<O::Vector> + Collection + Source + Sync),
+        partition: impl Fn(u32) -> Option<u64>,
     ) -> Arc<Self> {
+        let partition_options = options.partition.clone();
         let indexing = SealedIndexing::create(&path, options, source);
+        let partitions = partition_options.map(|options| {
+            // the index may reorder vectors, so partitions are found by payloads
+            let lookup = (0..source.len())
+                .map(|i| (source.payload(i), partition(i)))
+                .collect::<HashMap<_, _>>();
+            SegmentPartitions::create(
+                &path,
+                options,
+                (0..indexing.len()).map(|u| lookup[&indexing.payload(u)]),
+            )
+        });
         Arc::new(Self {
             id,
             path: path.clone(),
             indexing,
+            partitions,
             deletes: AtomicCell::new((Instant::now(), 0)),
             _sealed_segment_tracker: SealedSegmentTracker { path },
             _index_tracker: index_tracker,
@@ -56,11 +75,14 @@ impl<O: Op> SealedSegment<O> {
         id: NonZeroU128,
         options: IndexOptions,
     ) -> Arc<Self> {
+        let partition_options = options.partition.clone();
         let indexing = SealedIndexing::open(&path, options);
+        let partitions = partition_options.map(|options| SegmentPartitions::open(&path, options));
         Arc::new(Self {
             id,
             path: path.clone(),
             indexing,
+            partitions,
             deletes: AtomicCell::new((Instant::now(), 0)),
             _sealed_segment_tracker: SealedSegmentTracker { path },
             _index_tracker: index_tracker,
@@ -71,6 +93,19 @@ impl<O: Op> SealedSegment<O> {
         self.id
     }
 
+    pub fn partition(&self, i: u32) -> Option<u64> {
+        let partitions = self.partitions.as_ref()?;
+        partitions.keys[partitions.rows[i as usize] as usize]
+    }
+
+    /// Whether the segment has vectors of any of the partitions.
+    pub fn has_partitions(&self, keys: &[u64]) -> bool {
+        match &self.partitions {
+            Some(partitions) => !partitions.find(keys).is_empty(),
+            None => true,
+        }
+    }
+
     pub fn stat_sealed(&self) -> SegmentStat {
         SegmentStat {
             id: self.id,
@@ -84,10 +119,45 @@ impl<O: Op> SealedSegment<O> {
         &'a self,
         vector: Borrowed<'a, O>,
         opts: &'a SearchOptions,
-    ) -> Box<dyn Iterator<Item = Element> + 'a> {
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
         self.indexing.vbase(vector, opts)
     }
 
+    /// Searches among vectors of the given partitions. If they have fewer
+    /// vectors than `flat_threshold`, they are scanned exactly, and otherwise
+    /// the index skips vectors of other partitions.
+    pub fn vbase_partitioned<'a>(
+        &'a self,
+        vector: Borrowed<'a, O>,
+        opts: &'a SearchOptions,
+        keys: &[u64],
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
+        let Some(partitions) = &self.partitions else {
+            return self.vbase(vector, opts);
+        };
+        let found = partitions.find(keys);
+        let n = found
+            .iter()
+            .map(|&j| partitions.members[j as usize].len())
+            .sum::<usize>();
+        if n < partitions.flat_threshold as usize {
+            let mut result = found
+                .iter()
+                .flat_map(|&j| partitions.members[j as usize].iter().copied())
+                .map(|u| Element {
+                    distance: O::distance(vector, self.vector(u)),
+                    payload: AlwaysEqual(self.payload(u)),
+                    key: AlwaysEqual(u),
+                })
+                .collect::<Vec<_>>();
+            result.sort_unstable();
+            return Box::new(result.into_iter());
+        }
+        let rows = &partitions.rows;
+        self.indexing
+            .vbase_filtered(vector, opts, move |u| found.contains(&rows[u as usize]))
+    }
+
     pub fn len(&self) -> u32 {
         self.indexing.len()
     }
@@ -121,6 +191,69 @@ impl<O: Op> SealedSegment<O> {
     }
 }
 
+/// Partitions of vectors in a segment, which share the index of the segment.
+struct SegmentPartitions {
+    // partitions in the segment, in order
+    keys: Json<Vec<Option<u64>>>,
+    // the partition of every vector, as its position in `keys`
+    rows: MmapArray<u32>,
+    // vectors of every partition, in the order of `keys`
+    members: Vec<Vec<u32>>,
+    flat_threshold: u32,
+}
+
+impl SegmentPartitions {
+    fn create(
+        path: &Path,
+        options: PartitionOptions,
+        partitions: impl Iterator<Item = Option<u64>>,
+    ) -> Self {
+        let partitions = partitions.collect::<Vec<_>>();
+        let mut keys = partitions.clone();
+        keys.sort_unstable();
+        keys.dedup();
+        let keys = Json::create(path.join("partition_keys"), keys);
+        let rows = MmapArray::create(
+            path.join("partition_rows"),
+            partitions
+                .iter()
+                .map(|key| keys.binary_search(key).unwrap() as u32),
+        );
+        Self::new(keys, rows, options)
+    }
+
+    fn open(path: &Path, options: PartitionOptions) -> Self {
+        let keys = Json::open(path.join("partition_keys"));
+        let rows = MmapArray::open(path.join("partition_rows"));
+        Self::new(keys, rows, options)
+    }
+
+    fn new(keys: Json<Vec<Option<u64>>>, rows: MmapArray<u32>, options: PartitionOptions) -> Self {
+        let mut members = vec![Vec::new(); keys.len()];
+        for (u, &j) in rows.iter().enumerate() {
+            members[j as usize].push(u as u32);
+        }
+        Self {
+            keys,
+            rows,
+            members,
+            flat_threshold: options.flat_threshold,
+        }
+    }
+
+    /// Positions of the given partitions in `keys`, if they are in the segment.
+    fn find(&self, keys: &[u64]) -> Vec<u32> {
+        let mut found = keys
+            .iter()
+            .filter_map(|&key| self.keys.binary_search(&Some(key)).ok())
+            .map(|j| j as u32)
+            .collect::<Vec<_>>();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SealedSegmentTracker {
     path: PathBuf,
@@ -0,0 +1,237 @@
+use crate::IndexView;
+use crate::Op;
+use base::index::*;
+use base::operator::*;
+use base::search::*;
+use base::vector::*;
+use rand::Rng;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::collections::BinaryHeap;
+use std::collections::HashSet;
+use std::time::Instant;
+use validator::Validate;
+
+const EF_SEARCH: [u32; 7] = [10, 20, 40, 80, 160, 320, 640];
+const NPROBE: [u32; 9] = [1, 2, 4, 8, 16, 32, 64, 128, 256];
+const RERANK_SIZE: [u32; 4] = [0, 16, 64, 256];
+const TRUNCATION_RERANK_SIZE: [u32; 4] = [16, 64, 256, 1024];
+
+type Row<'a, O> = Box<dyn Fn(u32) -> (Borrowed<'a, O>, Payload) + Sync + 'a>;
+
+impl<O: Op> IndexView<O> {
+    /// Measures recall and latency of searches at several search options, and
+    /// yields a trial for each of them.
+    ///
+    /// Queries are sampled from indexed vectors, and their nearest `k` vectors
+    /// other than themselves are found by a flat scan on `optimizing_threads`
+    /// threads. Search options differ from `opts`, with per-index defaults,
+    /// only in the options that matter to the index.
+    pub fn tune<'a>(
+        &'a self,
+        opts: &PartialSearchOptions,
+        tune: &TuneOptions,
+    ) -> Result<impl Iterator<Item = Result<TuneTrial, TuneError>> + 'a, TuneError> {
+        let opts = self.search_options(opts);
+        if let Err(err) = opts.validate() {
+            return Err(TuneError::InvalidSearchOptions {
+                reason: err.to_string(),
+            });
+        }
+        if let Err(err) = tune.validate() {
+            return Err(TuneError::InvalidTuneOptions {
+                reason: err.to_string(),
+            });
+        }
+        let rows = self.rows();
+        let live = || {
+            rows.iter()
+                .flat_map(|(len, row)| (0..*len).map(row))
+                .filter(|&(_, payload)| self.delete.check(payload))
+        };
+        let mut rng = base::rand::rng(self.options.seed);
+        let mut queries = Vec::new();
+        let mut seen = 0_usize;
+        for (vector, payload) in live() {
+            if queries.len() < tune.queries as usize {
+                queries.push((vector.own(), payload.pointer()));
+            } else {
+                let j = rng.gen_range(0..=seen);
+                if j < queries.len() {
+                    queries[j] = (vector.own(), payload.pointer());
+                }
+            }
+            seen += 1;
+        }
+        // a query is an indexed vector, so it is excluded from its neighbors
+        let k = std::cmp::min(tune.k as usize, seen.saturating_sub(1));
+        if k == 0 {
+            queries.clear();
+        }
+        let truths = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.alterable_options.optimizing.optimizing_threads as usize)
+            .build()
+            .expect("failed to build a tuning thread pool")
+            .install(|| {
+                queries
+                    .par_iter()
+                    .map(|(query, itself)| {
+                        let mut nearest = BinaryHeap::with_capacity(k + 1);
+                        for (vector, payload) in live() {
+                            if payload.pointer() == *itself {
+                                continue;
+                            }
+                            nearest.push((
+                                O::distance(query.as_borrowed(), vector),
+                                payload.pointer(),
+                            ));
+                            if nearest.len() > k {
+                                nearest.pop();
+                            }
+                        }
+                        nearest
+                            .into_iter()
+                            .map(|(_, pointer)| pointer)
+                            .collect::<HashSet<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            });
+        let candidates = if queries.is_empty() {
+            Vec::new()
+        } else {
+            candidates(&self.options, &opts)
+        };
+        for options in candidates.iter() {
+            if let Err(err) = options.validate() {
+                return Err(TuneError::InvalidSearchOptions {
+                    reason: err.to_string(),
+                });
+            }
+        }
+        // warm up, so that the first trial is not charged for cold caches
+        for (query, _) in queries.iter() {
+            self.vbase(query.as_borrowed(), &opts)?
+                .take(k + 1)
+                .for_each(drop);
+        }
+        let n = queries.len();
+        Ok(candidates.into_iter().map(move |options| {
+            let mut hits = 0;
+            let start = Instant::now();
+            for ((query, itself), truth) in queries.iter().zip(truths.iter()) {
+                hits += self
+                    .vbase(query.as_borrowed(), &options)?
+                    .filter(|(_, pointer)| pointer != itself)
+                    .take(k)
+                    .filter(|(_, pointer)| truth.contains(pointer))
+                    .count();
+            }
+            Ok(TuneTrial {
+                recall: hits as f32 / (k * n) as f32,
+                latency: start.elapsed() / n as u32,
+                options,
+            })
+        }))
+    }
+
+    /// Lengths of all segments, with readers of their rows.
+    fn rows(&self) -> Vec<(u32, Row<'_, O>)> {
+        let mut rows = Vec::<(u32, Row<'_, O>)>::new();
+        for sealed in self.sealed_segments.values() {
+            rows.push((
+                sealed.len(),
+                Box::new(|i| (sealed.vector(i), sealed.payload(i))),
+            ));
+        }
+        for read in self.read_segments.values() {
+            rows.push((read.len(), Box::new(|i| (read.vector(i), read.payload(i)))));
+        }
+        if let Some((_, write)) = &self.write_segment {
+            rows.push((
+                write.len(),
+                Box::new(|i| (write.vector(i), write.payload(i))),
+            ));
+        }
+        rows
+    }
+}
+
+/// Search options to be measured, as the cartesian product of values of the
+/// options that matter to the index.
+fn candidates(options: &IndexOptions, opts: &SearchOptions) -> Vec<SearchOptions> {
+    let (result, quantization) = match &options.indexing {
+        IndexingOptions::Flat(x) => (vec![opts.clone()], x.quantization.as_ref()),
+        IndexingOptions::Ivf(x) => (
+            NPROBE
+                .into_iter()
+                .filter(|&nprobe| nprobe < x.nlist)
+                .chain(std::iter::once(x.nlist))
+                .map(|ivf_nprobe| SearchOptions {
+                    ivf_nprobe,
+                    ..opts.clone()
+                })
+                .collect(),
+            x.quantization.as_ref(),
+        ),
+        IndexingOptions::Hnsw(x) => (
+            EF_SEARCH
+                .into_iter()
+                .map(|hnsw_ef_search| SearchOptions {
+                    hnsw_ef_search,
+                    ..opts.clone()
+                })
+                .collect(),
+            x.quantization.as_ref(),
+        ),
+        IndexingOptions::SparseInvertedIndex(_) => (vec![opts.clone()], None),
+    };
+    let result = match quantization {
+        Some(QuantizationOptions::Scalar(_)) => {
+            product(result, RERANK_SIZE, |x, sq_rerank_size| SearchOptions {
+                sq_rerank_size,
+                ..x.clone()
+            })
+        }
+        Some(QuantizationOptions::Product(_)) => {
+            product(result, RERANK_SIZE, |x, pq_rerank_size| SearchOptions {
+                pq_rerank_size,
+                ..x.clone()
+            })
+        }
+        Some(QuantizationOptions::Rabitq(_)) => {
+            product(result, RERANK_SIZE, |x, rq_rerank_size| SearchOptions {
+                rq_rerank_size,
+                ..x.clone()
+            })
+        }
+        Some(QuantizationOptions::Binary(_)) => {
+            product(result, RERANK_SIZE, |x, bq_rerank_size| SearchOptions {
+                bq_rerank_size,
+                ..x.clone()
+            })
+        }
+        None => result,
+    };
+    if options.truncation.is_some() {
+        product(
+            result,
+            TRUNCATION_RERANK_SIZE,
+            |x, truncation_rerank_size| SearchOptions {
+                truncation_rerank_size,
+                ..x.clone()
+            },
+        )
+    } else {
+        result
+    }
+}
+
+fn product<const N: usize>(
+    result: Vec<SearchOptions>,
+    values: [u32; N],
+    f: impl Fn(&SearchOptions, u32) -> SearchOptions,
+) -> Vec<SearchOptions> {
+    result
+        .iter()
+        .flat_map(|x| values.map(|value| f(x, value)))
+        .collect()
+}
@@ -1,13 +1,18 @@
+#![allow(clippy::len_without_is_empty)]
+
 pub mod sealed;
+pub mod truncated;
 
 use quantization::rabitq::OperatorRabitqQuantization;
 pub use sealed::SealedIndexing;
 
 use base::operator::Operator;
 use ivf::operator::OperatorIvf;
+use quantization::binary::OperatorBinaryQuantization;
 use quantization::product::OperatorProductQuantization;
 use quantization::scalar::OperatorScalarQuantization;
 use sparse_inverted_index::operator::OperatorSparseInvertedIndex;
+use truncated::OperatorTruncation;
 
 pub trait OperatorIndexing
 where
@@ -17,6 +22,8 @@ where
     Self: OperatorScalarQuantization,
     Self: OperatorProductQuantization,
     Self: OperatorRabitqQuantization,
+    Self: OperatorBinaryQuantization,
+    Self: OperatorTruncation,
 {
 }
 
@@ -28,5 +35,7 @@ where
     Self: OperatorScalarQuantization,
     Self: OperatorProductQuantization,
     Self: OperatorRabitqQuantization,
+    Self: OperatorBinaryQuantization,
+    Self: OperatorTruncation,
 {
 }
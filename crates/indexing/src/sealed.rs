@@ -1,3 +1,4 @@
+use crate::truncated::Truncated;
 use crate::OperatorIndexing;
 use base::index::*;
 use base::operator::*;
@@ -5,6 +6,7 @@ use base::search::*;
 use flat::Flat;
 use hnsw::Hnsw;
 use ivf::Ivf;
+use quantization::binary::BinaryQuantizer;
 use quantization::product::ProductQuantizer;
 use quantization::rabitq::RabitqQuantizer;
 use quantization::scalar::ScalarQuantizer;
@@ -18,15 +20,19 @@ pub enum SealedIndexing<O: OperatorIndexing> {
     FlatSq(Flat<O, ScalarQuantizer<O>>),
     FlatPq(Flat<O, ProductQuantizer<O>>),
     FlatRq(Flat<O, RabitqQuantizer<O>>),
+    FlatBq(Flat<O, BinaryQuantizer<O>>),
     Ivf(Ivf<O, TrivialQuantizer<O>>),
     IvfSq(Ivf<O, ScalarQuantizer<O>>),
     IvfPq(Ivf<O, ProductQuantizer<O>>),
     IvfRq(Ivf<O, RabitqQuantizer<O>>),
+    IvfBq(Ivf<O, BinaryQuantizer<O>>),
     Hnsw(Hnsw<O, TrivialQuantizer<O>>),
     HnswSq(Hnsw<O, ScalarQuantizer<O>>),
     HnswPq(Hnsw<O, ProductQuantizer<O>>),
     HnswRq(Hnsw<O, RabitqQuantizer<O>>),
+    HnswBq(Hnsw<O, BinaryQuantizer<O>>),
     SparseInvertedIndex(SparseInvertedIndex<O>),
+    Truncated(Box<Truncated<O>>),
 }
 
 impl<O: OperatorIndexing> SealedIndexing<O> {
@@ -35,6 +41,9 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
         options: IndexOptions,
         source: &(impl Vectors<O::Vector> + Collection + Source + Sync),
     ) -> Self {
+        if options.truncation.is_some() {
+            return Self::Truncated(Box::new(Truncated::create(path, options, source)));
+        }
         match options.indexing {
             IndexingOptions::Flat(FlatIndexingOptions {
                 ref quantization, ..
@@ -49,6 +58,9 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
                 Some(QuantizationOptions::Rabitq(_)) => {
                     Self::FlatRq(Flat::create(path, options, source))
                 }
+                Some(QuantizationOptions::Binary(_)) => {
+                    Self::FlatBq(Flat::create(path, options, source))
+                }
             },
             IndexingOptions::Ivf(IvfIndexingOptions {
                 ref quantization, ..
@@ -63,6 +75,9 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
                 Some(QuantizationOptions::Rabitq(_)) => {
                     Self::IvfRq(Ivf::create(path, options, source))
                 }
+                Some(QuantizationOptions::Binary(_)) => {
+                    Self::IvfBq(Ivf::create(path, options, source))
+                }
             },
             IndexingOptions::Hnsw(HnswIndexingOptions {
                 ref quantization, ..
@@ -77,6 +92,9 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
                 Some(QuantizationOptions::Rabitq(_)) => {
                     Self::HnswRq(Hnsw::create(path, options, source))
                 }
+                Some(QuantizationOptions::Binary(_)) => {
+                    Self::HnswBq(Hnsw::create(path, options, source))
+                }
             },
             IndexingOptions::SparseInvertedIndex(_) => {
                 Self::SparseInvertedIndex(SparseInvertedIndex::create(path, options, source))
@@ -85,6 +103,9 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
     }
 
     pub fn open(path: impl AsRef<Path>, options: IndexOptions) -> Self {
+        if options.truncation.is_some() {
+            return Self::Truncated(Box::new(Truncated::open(path, options)));
+        }
         match options.indexing {
             IndexingOptions::Flat(FlatIndexingOptions {
                 ref quantization, ..
@@ -93,6 +114,7 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
                 Some(QuantizationOptions::Scalar(_)) => Self::FlatSq(Flat::open(path)),
                 Some(QuantizationOptions::Product(_)) => Self::FlatPq(Flat::open(path)),
                 Some(QuantizationOptions::Rabitq(_)) => Self::FlatRq(Flat::open(path)),
+                Some(QuantizationOptions::Binary(_)) => Self::FlatBq(Flat::open(path)),
             },
             IndexingOptions::Ivf(IvfIndexingOptions {
                 ref quantization, ..
@@ -101,6 +123,7 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
                 Some(QuantizationOptions::Scalar(_)) => Self::IvfSq(Ivf::open(path)),
                 Some(QuantizationOptions::Product(_)) => Self::IvfPq(Ivf::open(path)),
                 Some(QuantizationOptions::Rabitq(_)) => Self::IvfRq(Ivf::open(path)),
+                Some(QuantizationOptions::Binary(_)) => Self::IvfBq(Ivf::open(path)),
             },
             IndexingOptions::Hnsw(HnswIndexingOptions {
                 ref quantization, ..
@@ -109,6 +132,7 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
                 Some(QuantizationOptions::Scalar(_)) => Self::HnswSq(Hnsw::open(path)),
                 Some(QuantizationOptions::Product(_)) => Self::HnswPq(Hnsw::open(path)),
                 Some(QuantizationOptions::Rabitq(_)) => Self::HnswRq(Hnsw::open(path)),
+                Some(QuantizationOptions::Binary(_)) => Self::HnswBq(Hnsw::open(path)),
             },
             IndexingOptions::SparseInvertedIndex(_) => {
                 Self::SparseInvertedIndex(SparseInvertedIndex::open(path))
@@ -116,25 +140,49 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
         }
     }
 
+    /// The search owns a copy of `vector`, so the query may be dropped
+    /// before results are read.
     pub fn vbase<'a>(
         &'a self,
-        vector: Borrowed<'a, O>,
+        vector: Borrowed<'_, O>,
         opts: &'a SearchOptions,
-    ) -> Box<dyn Iterator<Item = Element> + 'a> {
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
         match self {
             SealedIndexing::Flat(x) => x.vbase(vector, opts),
             SealedIndexing::FlatPq(x) => x.vbase(vector, opts),
             SealedIndexing::FlatSq(x) => x.vbase(vector, opts),
             SealedIndexing::FlatRq(x) => x.vbase(vector, opts),
+            SealedIndexing::FlatBq(x) => x.vbase(vector, opts),
             SealedIndexing::Ivf(x) => x.vbase(vector, opts),
             SealedIndexing::IvfPq(x) => x.vbase(vector, opts),
             SealedIndexing::IvfSq(x) => x.vbase(vector, opts),
             SealedIndexing::IvfRq(x) => x.vbase(vector, opts),
+            SealedIndexing::IvfBq(x) => x.vbase(vector, opts),
             SealedIndexing::Hnsw(x) => x.vbase(vector, opts),
             SealedIndexing::HnswPq(x) => x.vbase(vector, opts),
             SealedIndexing::HnswSq(x) => x.vbase(vector, opts),
             SealedIndexing::HnswRq(x) => x.vbase(vector, opts),
+            SealedIndexing::HnswBq(x) => x.vbase(vector, opts),
             SealedIndexing::SparseInvertedIndex(x) => x.vbase(vector, opts),
+            SealedIndexing::Truncated(x) => x.vbase(vector, opts),
+        }
+    }
+
+    /// Searches among vectors whose keys pass `filter`. Graph indexes skip
+    /// vectors failing it while searching, and others drop them from results.
+    pub fn vbase_filtered<'a>(
+        &'a self,
+        vector: Borrowed<'_, O>,
+        opts: &'a SearchOptions,
+        filter: impl Fn(u32) -> bool + Send + 'a,
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
+        match self {
+            SealedIndexing::Hnsw(x) => x.vbase_filtered(vector, opts, filter),
+            SealedIndexing::HnswPq(x) => x.vbase_filtered(vector, opts, filter),
+            SealedIndexing::HnswSq(x) => x.vbase_filtered(vector, opts, filter),
+            SealedIndexing::HnswRq(x) => x.vbase_filtered(vector, opts, filter),
+            SealedIndexing::HnswBq(x) => x.vbase_filtered(vector, opts, filter),
+            _ => Box::new(self.vbase(vector, opts).filter(move |x| filter(x.key.0))),
         }
     }
 
@@ -144,15 +192,19 @@ impl<O: OperatorIndexing> SealedIndexing<O> {
             SealedIndexing::FlatPq(x) => x,
             SealedIndexing::FlatSq(x) => x,
             SealedIndexing::FlatRq(x) => x,
+            SealedIndexing::FlatBq(x) => x,
             SealedIndexing::Ivf(x) => x,
             SealedIndexing::IvfPq(x) => x,
             SealedIndexing::IvfSq(x) => x,
             SealedIndexing::IvfRq(x) => x,
+            SealedIndexing::IvfBq(x) => x,
             SealedIndexing::Hnsw(x) => x,
             SealedIndexing::HnswPq(x) => x,
             SealedIndexing::HnswSq(x) => x,
             SealedIndexing::HnswRq(x) => x,
+            SealedIndexing::HnswBq(x) => x,
             SealedIndexing::SparseInvertedIndex(x) => x,
+            SealedIndexing::Truncated(x) => x.as_ref(),
         }
     }
 }
@@ -164,15 +216,19 @@ impl<O: OperatorIndexing> Vectors<O::Vector> for SealedIndexing<O> {
             SealedIndexing::FlatSq(x) => x.dims(),
             SealedIndexing::FlatPq(x) => x.dims(),
             SealedIndexing::FlatRq(x) => x.dims(),
+            SealedIndexing::FlatBq(x) => x.dims(),
             SealedIndexing::Ivf(x) => x.dims(),
             SealedIndexing::IvfSq(x) => x.dims(),
             SealedIndexing::IvfPq(x) => x.dims(),
             SealedIndexing::IvfRq(x) => x.dims(),
+            SealedIndexing::IvfBq(x) => x.dims(),
             SealedIndexing::Hnsw(x) => x.dims(),
             SealedIndexing::HnswPq(x) => x.dims(),
             SealedIndexing::HnswSq(x) => x.dims(),
             SealedIndexing::HnswRq(x) => x.dims(),
+            SealedIndexing::HnswBq(x) => x.dims(),
             SealedIndexing::SparseInvertedIndex(x) => x.dims(),
+            SealedIndexing::Truncated(x) => x.dims(),
         }
     }
 
@@ -182,15 +238,19 @@ impl<O: OperatorIndexing> Vectors<O::Vector> for SealedIndexing<O> {
             SealedIndexing::FlatPq(x) => x.len(),
             SealedIndexing::FlatSq(x) => x.len(),
             SealedIndexing::FlatRq(x) => x.len(),
+            SealedIndexing::FlatBq(x) => x.len(),
             SealedIndexing::Ivf(x) => x.len(),
             SealedIndexing::IvfPq(x) => x.len(),
             SealedIndexing::IvfSq(x) => x.len(),
             SealedIndexing::IvfRq(x) => x.len(),
+            SealedIndexing::IvfBq(x) => x.len(),
             SealedIndexing::Hnsw(x) => x.len(),
             SealedIndexing::HnswPq(x) => x.len(),
             SealedIndexing::HnswSq(x) => x.len(),
             SealedIndexing::HnswRq(x) => x.len(),
+            SealedIndexing::HnswBq(x) => x.len(),
             SealedIndexing::SparseInvertedIndex(x) => x.len(),
+            SealedIndexing::Truncated(x) => x.len(),
         }
     }
 
@@ -200,15 +260,19 @@ impl<O: OperatorIndexing> Vectors<O::Vector> for SealedIndexing<O> {
             SealedIndexing::FlatPq(x) => x.vector(i),
             SealedIndexing::FlatSq(x) => x.vector(i),
             SealedIndexing::FlatRq(x) => x.vector(i),
+            SealedIndexing::FlatBq(x) => x.vector(i),
             SealedIndexing::Ivf(x) => x.vector(i),
             SealedIndexing::IvfSq(x) => x.vector(i),
             SealedIndexing::IvfPq(x) => x.vector(i),
             SealedIndexing::IvfRq(x) => x.vector(i),
+            SealedIndexing::IvfBq(x) => x.vector(i),
             SealedIndexing::Hnsw(x) => x.vector(i),
             SealedIndexing::HnswSq(x) => x.vector(i),
             SealedIndexing::HnswPq(x) => x.vector(i),
             SealedIndexing::HnswRq(x) => x.vector(i),
+            SealedIndexing::HnswBq(x) => x.vector(i),
             SealedIndexing::SparseInvertedIndex(x) => x.vector(i),
+            SealedIndexing::Truncated(x) => x.vector(i),
         }
     }
 }
@@ -220,15 +284,19 @@ impl<O: OperatorIndexing> Collection for SealedIndexing<O> {
             SealedIndexing::FlatPq(x) => x.payload(i),
             SealedIndexing::FlatSq(x) => x.payload(i),
             SealedIndexing::FlatRq(x) => x.payload(i),
+            SealedIndexing::FlatBq(x) => x.payload(i),
             SealedIndexing::Ivf(x) => x.payload(i),
             SealedIndexing::IvfPq(x) => x.payload(i),
             SealedIndexing::IvfSq(x) => x.payload(i),
             SealedIndexing::IvfRq(x) => x.payload(i),
+            SealedIndexing::IvfBq(x) => x.payload(i),
             SealedIndexing::Hnsw(x) => x.payload(i),
             SealedIndexing::HnswPq(x) => x.payload(i),
             SealedIndexing::HnswSq(x) => x.payload(i),
             SealedIndexing::HnswRq(x) => x.payload(i),
+            SealedIndexing::HnswBq(x) => x.payload(i),
             SealedIndexing::SparseInvertedIndex(x) => x.payload(i),
+            SealedIndexing::Truncated(x) => x.payload(i),
         }
     }
 }
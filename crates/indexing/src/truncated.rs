@@ -0,0 +1,223 @@
+use crate::OperatorIndexing;
+use crate::SealedIndexing;
+use base::index::*;
+use base::operator::*;
+use base::scalar::ScalarLike;
+use base::search::*;
+use base::vector::*;
+use common::remap::RemappedCollection;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::create_dir;
+use std::path::Path;
+use storage::Storage;
+
+/// An index built on the first dimensions of vectors, reranked on full vectors.
+pub struct Truncated<O: OperatorIndexing> {
+    indexing: SealedIndexing<O>,
+    storage: O::Storage,
+    truncation: TruncationOptions,
+}
+
+impl<O: OperatorIndexing> Truncated<O> {
+    pub fn create(
+        path: impl AsRef<Path>,
+        options: IndexOptions,
+        source: &(impl Vectors<O::Vector> + Collection + Source + Sync),
+    ) -> Self {
+        create_dir(path.as_ref()).unwrap();
+        let truncation = options.truncation.clone().expect("truncation is not set");
+        let remapped = RemappedCollection::from_source(source);
+        let collection = TruncatedCollection::<O> {
+            normalized: truncation.renormalize.then(|| {
+                (0..remapped.len())
+                    .map(|i| truncate::<O>(remapped.vector(i), &truncation))
+                    .collect()
+            }),
+            source: &remapped,
+            dims: truncation.dims,
+        };
+        let indexing = SealedIndexing::create(
+            path.as_ref().join("indexing"),
+            inner_options(options),
+            &collection,
+        );
+        let storage = O::Storage::create(path.as_ref().join("storage"), &remapped);
+        Self {
+            indexing,
+            storage,
+            truncation,
+        }
+    }
+
+    pub fn open(path: impl AsRef<Path>, options: IndexOptions) -> Self {
+        let truncation = options.truncation.clone().expect("truncation is not set");
+        let indexing = SealedIndexing::open(path.as_ref().join("indexing"), inner_options(options));
+        let storage = O::Storage::open(path.as_ref().join("storage"));
+        Self {
+            indexing,
+            storage,
+            truncation,
+        }
+    }
+
+    pub fn vbase<'a>(
+        &'a self,
+        vector: Borrowed<'_, O>,
+        opts: &'a SearchOptions,
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
+        let query = truncate::<O>(vector, &self.truncation);
+        let mut inner = Some(self.indexing.vbase(query.as_borrowed(), opts));
+        let vector = vector.own();
+        let size = opts.truncation_rerank_size as usize;
+        let mut heap = BinaryHeap::<Reverse<Element>>::new();
+        Box::new(std::iter::from_fn(move || {
+            while heap.len() < size {
+                let Some(x) = inner.as_mut().and_then(|inner| inner.next()) else {
+                    inner = None;
+                    break;
+                };
+                let distance = O::distance(self.storage.vector(x.key.0), vector.as_borrowed());
+                heap.push(Reverse(Element { distance, ..x }));
+            }
+            heap.pop().map(|Reverse(x)| x)
+        }))
+    }
+
+    pub fn dims(&self) -> u32 {
+        self.storage.dims()
+    }
+
+    pub fn len(&self) -> u32 {
+        self.storage.len()
+    }
+
+    pub fn vector(&self, i: u32) -> Borrowed<'_, O> {
+        self.storage.vector(i)
+    }
+
+    pub fn payload(&self, i: u32) -> Payload {
+        self.indexing.payload(i)
+    }
+}
+
+fn inner_options(options: IndexOptions) -> IndexOptions {
+    let truncation = options.truncation.expect("truncation is not set");
+    IndexOptions {
+        vector: VectorOptions {
+            dims: truncation.dims,
+            ..options.vector
+        },
+        indexing: options.indexing,
+        truncation: None,
+        seed: options.seed,
+        partition: None,
+    }
+}
+
+fn truncate<O: OperatorIndexing>(
+    vector: Borrowed<'_, O>,
+    truncation: &TruncationOptions,
+) -> O::Vector {
+    let truncated = O::prefix(vector, truncation.dims);
+    if truncation.renormalize {
+        truncated.function_normalize()
+    } else {
+        truncated.own()
+    }
+}
+
+pub trait OperatorTruncation: Operator {
+    /// The first `dims` dimensions of a vector, borrowed from it.
+    fn prefix(vector: Borrowed<'_, Self>, dims: u32) -> Borrowed<'_, Self>;
+}
+
+impl<S: ScalarLike> OperatorTruncation for VectDot<S> {
+    fn prefix(vector: Borrowed<'_, Self>, dims: u32) -> Borrowed<'_, Self> {
+        VectBorrowed::new(&vector.slice()[..dims as usize])
+    }
+}
+
+impl<S: ScalarLike> OperatorTruncation for VectL2<S> {
+    fn prefix(vector: Borrowed<'_, Self>, dims: u32) -> Borrowed<'_, Self> {
+        VectBorrowed::new(&vector.slice()[..dims as usize])
+    }
+}
+
+impl<S: ScalarLike> OperatorTruncation for SVectDot<S> {
+    fn prefix(_: Borrowed<'_, Self>, _: u32) -> Borrowed<'_, Self> {
+        unimplemented!()
+    }
+}
+
+impl<S: ScalarLike> OperatorTruncation for SVectL2<S> {
+    fn prefix(_: Borrowed<'_, Self>, _: u32) -> Borrowed<'_, Self> {
+        unimplemented!()
+    }
+}
+
+macro_rules! unimpl_operator_truncation {
+    ($t:ty) => {
+        impl OperatorTruncation for $t {
+            fn prefix(_: Borrowed<'_, Self>, _: u32) -> Borrowed<'_, Self> {
+                unimplemented!()
+            }
+        }
+    };
+}
+
+unimpl_operator_truncation!(BVectorDot);
+unimpl_operator_truncation!(BVectorJaccard);
+unimpl_operator_truncation!(BVectorHamming);
+
+trait TruncationSource<V: VectorOwned>: Vectors<V> + Collection + Sync {}
+
+impl<V: VectorOwned, T: Vectors<V> + Collection + Sync> TruncationSource<V> for T {}
+
+// Vectors are read in remapped order, so the inner index shares indexes with
+// the full vectors and never builds on a previous segment. Prefixes are
+// borrowed from the source, but normalized prefixes are new vectors, so they
+// are computed before building. The source is a trait object, so that the
+// inner index isn't instantiated for every type of sources.
+struct TruncatedCollection<'a, O: Operator> {
+    source: &'a dyn TruncationSource<O::Vector>,
+    dims: u32,
+    normalized: Option<Vec<O::Vector>>,
+}
+
+impl<O: OperatorIndexing> Vectors<O::Vector> for TruncatedCollection<'_, O> {
+    fn dims(&self) -> u32 {
+        self.dims
+    }
+
+    fn len(&self) -> u32 {
+        self.source.len()
+    }
+
+    fn vector(&self, i: u32) -> Borrowed<'_, O> {
+        match &self.normalized {
+            Some(normalized) => normalized[i as usize].as_borrowed(),
+            None => O::prefix(self.source.vector(i), self.dims),
+        }
+    }
+}
+
+impl<O: Operator> Collection for TruncatedCollection<'_, O> {
+    fn payload(&self, i: u32) -> Payload {
+        self.source.payload(i)
+    }
+}
+
+impl<O: Operator> Source for TruncatedCollection<'_, O> {
+    fn get_main<T: std::any::Any>(&self) -> Option<&T> {
+        None
+    }
+
+    fn get_main_len(&self) -> u32 {
+        0
+    }
+
+    fn check_existing(&self, _: u32) -> bool {
+        true
+    }
+}
@@ -0,0 +1,198 @@
+use crate::select;
+use base::index::KMeansAlgorithm;
+use base::scalar::ScalarLike;
+use base::search::*;
+use base::vector::*;
+use common::vec2::Vec2;
+use k_means::k_means;
+use k_means::k_means_lookup;
+use k_means::k_means_lookup_many;
+use k_means::mini_batch_k_means;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+use serde::Deserialize;
+use serde::Serialize;
+use std::cmp::Reverse;
+use stoppable_rayon as rayon;
+
+const MINI_BATCH_SIZE: usize = 1024;
+
+/// Coarse centroids over lists, so that a probe scores lists of a few groups
+/// instead of every list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hierarchy<S> {
+    pub centroids: Vec2<S>,
+    // lists of the `j`-th group are `offsets[j]..offsets[j + 1]`
+    pub offsets: Vec<u32>,
+}
+
+pub fn train<S: ScalarLike>(
+    c: usize,
+    samples: Vec2<S>,
+    is_spherical: bool,
+    kmeans: KMeansAlgorithm,
+    seed: Option<u64>,
+) -> Vec2<S> {
+    match kmeans {
+        KMeansAlgorithm::Lloyd => k_means(c, samples, is_spherical, 10, false, seed),
+        KMeansAlgorithm::MiniBatch => {
+            // two passes over samples, while Lloyd's algorithm takes up to ten
+            let iterations = 2 * samples.shape_0().div_ceil(MINI_BATCH_SIZE);
+            mini_batch_k_means(c, samples, is_spherical, iterations, MINI_BATCH_SIZE, seed)
+        }
+    }
+}
+
+/// Trains `sqrt(c)` coarse centroids, and then lists of every group on its own
+/// samples. Lists are shared among groups in proportion to their samples.
+pub fn train_hierarchical<S: ScalarLike>(
+    c: usize,
+    samples: Vec2<S>,
+    is_spherical: bool,
+    kmeans: KMeansAlgorithm,
+    seed: Option<u64>,
+) -> (Hierarchy<S>, Vec2<S>) {
+    let n = samples.shape_0();
+    let dims = samples.shape_1();
+    let k = std::cmp::min(c, (c as f64).sqrt().ceil() as usize);
+    let coarse = train(k, samples.clone(), is_spherical, kmeans, seed);
+    rayon::check();
+    let assign = (0..n)
+        .into_par_iter()
+        .map(|i| k_means_lookup(&samples[(i,)], &coarse))
+        .collect::<Vec<_>>();
+    let mut members = vec![Vec::new(); k];
+    for i in 0..n {
+        members[assign[i]].push(i);
+    }
+    let mut sizes = vec![1_usize; k];
+    let rest = c - k;
+    let mut given = 0;
+    for j in 0..k {
+        let x = rest * members[j].len() / n.max(1);
+        sizes[j] += x;
+        given += x;
+    }
+    let mut order = (0..k).collect::<Vec<_>>();
+    order.sort_by_key(|&j| Reverse(members[j].len()));
+    for &j in order.iter().cycle().take(rest - given) {
+        sizes[j] += 1;
+    }
+    let lists = (0..k)
+        .into_par_iter()
+        .map(|j| {
+            if members[j].is_empty() {
+                let mut lists = Vec2::zeros((sizes[j], dims));
+                for t in 0..sizes[j] {
+                    lists[(t,)].copy_from_slice(&coarse[(j,)]);
+                }
+                return lists;
+            }
+            let mut subsamples = Vec2::zeros((members[j].len(), dims));
+            for (t, &i) in members[j].iter().enumerate() {
+                subsamples[(t,)].copy_from_slice(&samples[(i,)]);
+            }
+            let seed = seed.map(|seed| seed.wrapping_add(1 + j as u64));
+            train(sizes[j], subsamples, is_spherical, kmeans, seed)
+        })
+        .collect::<Vec<_>>();
+    let mut centroids = Vec2::zeros((c, dims));
+    let mut offsets = vec![0_u32; k + 1];
+    for j in 0..k {
+        offsets[j + 1] = offsets[j] + sizes[j] as u32;
+        for t in 0..sizes[j] {
+            centroids[(offsets[j] as usize + t,)].copy_from_slice(&lists[j][(t,)]);
+        }
+    }
+    (
+        Hierarchy {
+            centroids: coarse,
+            offsets,
+        },
+        centroids,
+    )
+}
+
+/// The nearest list of a vector.
+pub fn lookup<S: ScalarLike>(
+    hierarchy: Option<&Hierarchy<S>>,
+    centroids: &Vec2<S>,
+    vector: &[S],
+) -> usize {
+    let Some(hierarchy) = hierarchy else {
+        return k_means_lookup(vector, centroids);
+    };
+    let j = k_means_lookup(vector, &hierarchy.centroids);
+    let mut result = (f32::INFINITY, 0);
+    for i in hierarchy.offsets[j]..hierarchy.offsets[j + 1] {
+        let dis = S::reduce_sum_of_d2(vector, &centroids[(i as usize,)]);
+        if dis <= result.0 {
+            result = (dis, i as usize);
+        }
+    }
+    result.1
+}
+
+/// Lists to be probed for a vector, with their distances.
+pub fn lookup_many<S: ScalarLike>(
+    hierarchy: Option<&Hierarchy<S>>,
+    centroids: &Vec2<S>,
+    vector: &[S],
+    coarse_nprobe: u32,
+) -> Vec<(f32, usize)> {
+    let Some(hierarchy) = hierarchy else {
+        return k_means_lookup_many(vector, centroids);
+    };
+    let groups = select(
+        k_means_lookup_many(vector, &hierarchy.centroids),
+        coarse_nprobe as usize,
+    );
+    let mut result = Vec::new();
+    for (_, j) in groups {
+        for i in hierarchy.offsets[j]..hierarchy.offsets[j + 1] {
+            let dis = S::reduce_sum_of_d2(vector, &centroids[(i as usize,)]);
+            result.push((dis, i as usize));
+        }
+    }
+    result
+}
+
+/// Centroids as a collection, so that a HNSW graph can be built over them. The
+/// pointer of the `i`-th centroid is `i`.
+pub struct CentroidCollection<'a, S> {
+    pub centroids: &'a Vec2<S>,
+}
+
+impl<S: ScalarLike> Vectors<VectOwned<S>> for CentroidCollection<'_, S> {
+    fn dims(&self) -> u32 {
+        self.centroids.shape_1() as u32
+    }
+
+    fn len(&self) -> u32 {
+        self.centroids.shape_0() as u32
+    }
+
+    fn vector(&self, i: u32) -> VectBorrowed<'_, S> {
+        VectBorrowed::new(&self.centroids[(i as usize,)])
+    }
+}
+
+impl<S> Collection for CentroidCollection<'_, S> {
+    fn payload(&self, i: u32) -> Payload {
+        Payload::new(Pointer::new(i as u64), 0)
+    }
+}
+
+impl<S> Source for CentroidCollection<'_, S> {
+    fn get_main<T: std::any::Any>(&self) -> Option<&T> {
+        None
+    }
+
+    fn get_main_len(&self) -> u32 {
+        0
+    }
+
+    fn check_existing(&self, _: u32) -> bool {
+        true
+    }
+}
@@ -1,26 +1,33 @@
 #![allow(clippy::len_without_is_empty)]
 #![allow(clippy::needless_range_loop)]
 
+pub mod centroids;
 pub mod operator;
 
 use base::always_equal::AlwaysEqual;
+use base::distance::Distance;
+use base::distance::DistanceKind;
 use base::index::*;
 use base::operator::*;
 use base::search::*;
+use base::vector::VectBorrowed;
 use base::vector::VectorBorrowed;
 use base::vector::VectorOwned;
+use centroids::CentroidCollection;
+use centroids::Hierarchy;
 use common::json::Json;
 use common::mmap_array::MmapArray;
 use common::remap::RemappedCollection;
 use common::vec2::Vec2;
-use k_means::k_means;
-use k_means::k_means_lookup;
-use k_means::k_means_lookup_many;
+use hnsw::Hnsw;
 use operator::OperatorIvf as Op;
 use quantization::quantizer::Quantizer;
+use quantization::trivial::TrivialQuantizer;
 use quantization::Quantization;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::fs::create_dir;
 use std::path::Path;
 use stoppable_rayon as rayon;
@@ -32,9 +39,13 @@ pub struct Ivf<O: Op, Q: Quantizer<O>> {
     payloads: MmapArray<Payload>,
     offsets: Json<Vec<u32>>,
     projected_centroids: Json<Vec2<<O as Op>::Scalar>>,
+    projected_hierarchy: Option<Json<Hierarchy<<O as Op>::Scalar>>>,
+    projected_graph: Option<CentroidGraph<<O as Op>::Scalar>>,
     is_residual: Json<bool>,
 }
 
+type CentroidGraph<S> = Hnsw<VectL2<S>, TrivialQuantizer<VectL2<S>>>;
+
 impl<O: Op, Q: Quantizer<O>> Ivf<O, Q> {
     pub fn create(
         path: impl AsRef<Path>,
@@ -67,18 +78,36 @@ impl<O: Op, Q: Quantizer<O>> Ivf<O, Q> {
 
     pub fn vbase<'a>(
         &'a self,
-        vector: Borrowed<'a, O>,
+        vector: Borrowed<'_, O>,
         opts: &'a SearchOptions,
-    ) -> Box<dyn Iterator<Item = Element> + 'a> {
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
         let projected_vector = self.quantization.project(vector);
-        let lists = select(
-            k_means_lookup_many(
-                O::interpret(projected_vector.as_borrowed()),
-                &self.projected_centroids,
-            ),
-            opts.ivf_nprobe as usize,
-        );
+        let lists = if let Some(graph) = self.projected_graph.as_ref() {
+            // the graph is searched with `hnsw_ef_search`
+            graph
+                .vbase(
+                    VectBorrowed::new(O::interpret(projected_vector.as_borrowed())),
+                    opts,
+                )
+                .take(opts.ivf_nprobe as usize)
+                .map(|e| (e.distance.to_f32(), e.payload.0.pointer().as_u64() as usize))
+                .collect()
+        } else {
+            select(
+                centroids::lookup_many(
+                    self.projected_hierarchy.as_deref(),
+                    &self.projected_centroids,
+                    O::interpret(projected_vector.as_borrowed()),
+                    opts.ivf_coarse_nprobe,
+                ),
+                opts.ivf_nprobe as usize,
+            )
+        };
         let mut heap = Q::flat_rerank_start();
+        let mut nearest = BinaryHeap::new();
+        let mut stale = 0_u32;
+        // exact distances computed for early termination, reused by the reranker
+        let mut exact = HashMap::<u32, Distance>::new();
         let lut = if *self.is_residual {
             None
         } else {
@@ -102,18 +131,54 @@ impl<O: Op, Q: Quantizer<O>> Ivf<O, Q> {
             };
             let start = self.offsets[i];
             let end = self.offsets[i + 1];
+            let pushed = Q::flat_rerank_estimates(&heap).map_or(0, |x| x.len());
             self.quantization
                 .flat_rerank_continue(lut, start..end, &mut heap);
+            if opts.ivf_adaptive_patience != 0 {
+                // without estimated distances, the bound is of exact distances
+                let improved = match Q::flat_rerank_estimates(&heap) {
+                    Some(estimates) => improve(
+                        &mut nearest,
+                        opts.adaptive_k as usize,
+                        estimates[pushed..].iter().map(|&(Reverse(dis_u), _)| dis_u),
+                    ),
+                    None => improve(
+                        &mut nearest,
+                        opts.adaptive_k as usize,
+                        (start..end).map(|u| {
+                            let dis_u = O::distance(vector, self.storage.vector(u));
+                            exact.insert(u, dis_u);
+                            dis_u
+                        }),
+                    ),
+                };
+                if improved {
+                    stale = 0;
+                } else {
+                    stale += 1;
+                    if stale >= opts.ivf_adaptive_patience {
+                        break;
+                    }
+                }
+            }
         }
+        let vector = vector.own();
         let mut reranker = self.quantization.flat_rerank_break(
             heap,
-            move |u| (O::distance(vector, self.storage.vector(u)), ()),
+            move |u| {
+                let dis_u = match exact.get(&u) {
+                    Some(&dis_u) => dis_u,
+                    None => O::distance(vector.as_borrowed(), self.storage.vector(u)),
+                };
+                (dis_u, ())
+            },
             opts,
         );
         Box::new(std::iter::from_fn(move || {
             reranker.pop().map(|(dis_u, u, ())| Element {
                 distance: dis_u,
                 payload: AlwaysEqual(self.payload(u)),
+                key: AlwaysEqual(u),
             })
         }))
     }
@@ -129,15 +194,42 @@ fn from_nothing<O: Op, Q: Quantizer<O>>(
         nlist,
         spherical_centroids,
         residual_quantization,
+        kmeans,
+        hierarchical_centroids,
+        hnsw_centroids,
         quantization: quantization_options,
     } = options.indexing.clone().unwrap_ivf();
-    let samples = O::sample(collection, nlist);
+    let samples = O::sample(collection, nlist, options.seed);
     rayon::check();
-    let centroids = k_means(nlist as usize, samples, spherical_centroids, 10, false);
+    let (hierarchy, centroids) = if hierarchical_centroids {
+        let (hierarchy, centroids) = centroids::train_hierarchical(
+            nlist as usize,
+            samples,
+            spherical_centroids,
+            kmeans,
+            options.seed,
+        );
+        (Some(hierarchy), centroids)
+    } else {
+        let centroids = centroids::train(
+            nlist as usize,
+            samples,
+            spherical_centroids,
+            kmeans,
+            options.seed,
+        );
+        (None, centroids)
+    };
     rayon::check();
     let fa = (0..collection.len())
         .into_par_iter()
-        .map(|i| k_means_lookup(O::interpret(collection.vector(i)), &centroids))
+        .map(|i| {
+            centroids::lookup(
+                hierarchy.as_ref(),
+                &centroids,
+                O::interpret(collection.vector(i)),
+            )
+        })
         .collect::<Vec<_>>();
     let ls = (0..collection.len())
         .into_par_iter()
@@ -174,12 +266,14 @@ fn from_nothing<O: Op, Q: Quantizer<O>>(
     let storage = O::Storage::create(path.as_ref().join("storage"), &collection);
     let quantization = Quantization::<O, Q>::create(
         path.as_ref().join("quantization"),
-        options.vector,
+        options.vector.clone(),
         quantization_options,
+        options.seed,
         &collection,
         |vector| {
             if is_residual {
-                let target = k_means_lookup(O::interpret(vector), &centroids);
+                let target =
+                    centroids::lookup(hierarchy.as_ref(), &centroids, O::interpret(vector));
                 O::residual(vector, &centroids[(target,)])
             } else {
                 vector.own()
@@ -199,6 +293,39 @@ fn from_nothing<O: Op, Q: Quantizer<O>>(
         }
         projected_centroids
     });
+    let projected_hierarchy = hierarchy.map(|hierarchy| {
+        let mut projected_centroids = Vec2::zeros(hierarchy.centroids.shape());
+        for j in 0..hierarchy.centroids.shape_0() {
+            projected_centroids[(j,)].copy_from_slice(&O::project(
+                quantization.quantizer(),
+                &hierarchy.centroids[(j,)],
+            ));
+        }
+        Json::create(
+            path.as_ref().join("projected_hierarchy"),
+            Hierarchy {
+                centroids: projected_centroids,
+                offsets: hierarchy.offsets,
+            },
+        )
+    });
+    let projected_graph = hnsw_centroids.map(|hnsw_centroids| {
+        let collection = CentroidCollection {
+            centroids: &projected_centroids,
+        };
+        let options = IndexOptions {
+            vector: VectorOptions {
+                dims: collection.dims(),
+                d: DistanceKind::L2,
+                ..options.vector
+            },
+            indexing: IndexingOptions::Hnsw(hnsw_centroids),
+            truncation: None,
+            seed: options.seed,
+            partition: None,
+        };
+        Hnsw::create(path.as_ref().join("projected_graph"), options, &collection)
+    });
     let is_residual = Json::create(path.as_ref().join("is_residual"), is_residual);
     Ivf {
         storage,
@@ -206,6 +333,8 @@ fn from_nothing<O: Op, Q: Quantizer<O>>(
         payloads,
         offsets,
         projected_centroids,
+        projected_hierarchy,
+        projected_graph,
         is_residual,
     }
 }
@@ -216,6 +345,16 @@ fn open<O: Op, Q: Quantizer<O>>(path: impl AsRef<Path>) -> Ivf<O, Q> {
     let payloads = MmapArray::open(path.as_ref().join("payloads"));
     let offsets = Json::open(path.as_ref().join("offsets"));
     let projected_centroids = Json::open(path.as_ref().join("projected_centroids"));
+    let projected_hierarchy = path
+        .as_ref()
+        .join("projected_hierarchy")
+        .exists()
+        .then(|| Json::open(path.as_ref().join("projected_hierarchy")));
+    let projected_graph = path
+        .as_ref()
+        .join("projected_graph")
+        .exists()
+        .then(|| Hnsw::open(path.as_ref().join("projected_graph")));
     let is_residual = Json::open(path.as_ref().join("is_residual"));
     Ivf {
         storage,
@@ -223,10 +362,32 @@ fn open<O: Op, Q: Quantizer<O>>(path: impl AsRef<Path>) -> Ivf<O, Q> {
         payloads,
         offsets,
         projected_centroids,
+        projected_hierarchy,
+        projected_graph,
         is_residual,
     }
 }
 
+/// Pushes distances into the nearest `k` ones, and returns whether any of them
+/// is kept.
+fn improve(
+    nearest: &mut BinaryHeap<Distance>,
+    k: usize,
+    distances: impl Iterator<Item = Distance>,
+) -> bool {
+    let mut improved = false;
+    for dis_u in distances {
+        if nearest.len() < k || Some(&dis_u) < nearest.peek() {
+            nearest.push(dis_u);
+            if nearest.len() > k {
+                nearest.pop();
+            }
+            improved = true;
+        }
+    }
+    improved
+}
+
 fn select(mut lists: Vec<(f32, usize)>, n: usize) -> Vec<(f32, usize)> {
     if lists.is_empty() || n == 0 {
         return Vec::new();
@@ -9,7 +9,11 @@ use storage::OperatorStorage;
 
 pub trait OperatorIvf: OperatorStorage {
     type Scalar: ScalarLike;
-    fn sample(vectors: &impl Vectors<Self::Vector>, nlist: u32) -> Vec2<Self::Scalar>;
+    fn sample(
+        vectors: &impl Vectors<Self::Vector>,
+        nlist: u32,
+        seed: Option<u64>,
+    ) -> Vec2<Self::Scalar>;
     fn interpret(vector: Borrowed<'_, Self>) -> &[Self::Scalar];
     fn project<Q: Quantizer<Self>>(quantizer: &Q, slice: &[Self::Scalar]) -> Vec<Self::Scalar>;
     const SUPPORT_RESIDUAL: bool;
@@ -18,7 +22,7 @@ pub trait OperatorIvf: OperatorStorage {
 
 impl OperatorIvf for BVectorDot {
     type Scalar = Impossible;
-    fn sample(_: &impl Vectors<Self::Vector>, _: u32) -> Vec2<Self::Scalar> {
+    fn sample(_: &impl Vectors<Self::Vector>, _: u32, _: Option<u64>) -> Vec2<Self::Scalar> {
         unimplemented!()
     }
     fn interpret(_: Borrowed<'_, Self>) -> &[Self::Scalar] {
@@ -35,7 +39,7 @@ impl OperatorIvf for BVectorDot {
 
 impl OperatorIvf for BVectorJaccard {
     type Scalar = Impossible;
-    fn sample(_: &impl Vectors<Self::Vector>, _: u32) -> Vec2<Self::Scalar> {
+    fn sample(_: &impl Vectors<Self::Vector>, _: u32, _: Option<u64>) -> Vec2<Self::Scalar> {
         unimplemented!()
     }
     fn interpret(_: Borrowed<'_, Self>) -> &[Self::Scalar] {
@@ -52,7 +56,7 @@ impl OperatorIvf for BVectorJaccard {
 
 impl OperatorIvf for BVectorHamming {
     type Scalar = Impossible;
-    fn sample(_: &impl Vectors<Self::Vector>, _: u32) -> Vec2<Self::Scalar> {
+    fn sample(_: &impl Vectors<Self::Vector>, _: u32, _: Option<u64>) -> Vec2<Self::Scalar> {
         unimplemented!()
     }
     fn interpret(_: Borrowed<'_, Self>) -> &[Self::Scalar] {
@@ -67,9 +71,9 @@ impl OperatorIvf for BVectorHamming {
     }
 }
 
-impl OperatorIvf for SVectDot<f32> {
+impl<S: ScalarLike> OperatorIvf for SVectDot<S> {
     type Scalar = Impossible;
-    fn sample(_: &impl Vectors<Self::Vector>, _: u32) -> Vec2<Self::Scalar> {
+    fn sample(_: &impl Vectors<Self::Vector>, _: u32, _: Option<u64>) -> Vec2<Self::Scalar> {
         unimplemented!()
     }
     fn interpret(_: Borrowed<'_, Self>) -> &[Self::Scalar] {
@@ -84,9 +88,9 @@ impl OperatorIvf for SVectDot<f32> {
     }
 }
 
-impl OperatorIvf for SVectL2<f32> {
+impl<S: ScalarLike> OperatorIvf for SVectL2<S> {
     type Scalar = Impossible;
-    fn sample(_: &impl Vectors<Self::Vector>, _: u32) -> Vec2<Self::Scalar> {
+    fn sample(_: &impl Vectors<Self::Vector>, _: u32, _: Option<u64>) -> Vec2<Self::Scalar> {
         unimplemented!()
     }
     fn interpret(_: Borrowed<'_, Self>) -> &[Self::Scalar] {
@@ -103,11 +107,16 @@ impl OperatorIvf for SVectL2<f32> {
 
 impl<S: ScalarLike> OperatorIvf for VectDot<S> {
     type Scalar = S;
-    fn sample(vectors: &impl Vectors<Self::Vector>, nlist: u32) -> Vec2<Self::Scalar> {
+    fn sample(
+        vectors: &impl Vectors<Self::Vector>,
+        nlist: u32,
+        seed: Option<u64>,
+    ) -> Vec2<Self::Scalar> {
         common::sample::sample(
             vectors.len(),
             nlist.saturating_mul(256).min(1 << 20),
             vectors.dims(),
+            seed,
             |i| vectors.vector(i).slice(),
         )
     }
@@ -125,11 +134,16 @@ impl<S: ScalarLike> OperatorIvf for VectDot<S> {
 
 impl<S: ScalarLike> OperatorIvf for VectL2<S> {
     type Scalar = S;
-    fn sample(vectors: &impl Vectors<Self::Vector>, nlist: u32) -> Vec2<Self::Scalar> {
+    fn sample(
+        vectors: &impl Vectors<Self::Vector>,
+        nlist: u32,
+        seed: Option<u64>,
+    ) -> Vec2<Self::Scalar> {
         common::sample::sample(
             vectors.len(),
             nlist.saturating_mul(256).min(1 << 20),
             vectors.dims(),
+            seed,
             |i| vectors.vector(i).slice(),
         )
     }
@@ -2,12 +2,14 @@
 
 pub mod kmeans1d;
 pub mod lloyd;
+pub mod mini_batch;
 pub mod quick_centers;
 
 use base::scalar::*;
 use common::vec2::Vec2;
 use kmeans1d::kmeans1d;
 use lloyd::LloydKMeans;
+use mini_batch::MiniBatchKMeans;
 use stoppable_rayon as rayon;
 
 pub fn k_means<S: ScalarLike>(
@@ -16,27 +18,24 @@ pub fn k_means<S: ScalarLike>(
     is_spherical: bool,
     iterations: usize,
     prefer_kmeanspp: bool,
+    seed: Option<u64>,
 ) -> Vec2<S> {
     assert!(c > 0);
     let n = samples.shape_0();
     let dims = samples.shape_1();
     assert!(dims > 0);
     if is_spherical {
-        for i in 0..n {
-            let sample = &mut samples[(i,)];
-            let l = S::reduce_sum_of_x2(sample).sqrt();
-            S::vector_mul_scalar_inplace(sample, 1.0 / l);
-        }
+        normalize(&mut samples);
     }
     if n <= c {
-        return quick_centers::quick_centers(c, samples);
+        return quick_centers::quick_centers(c, samples, seed);
     }
     if dims == 1 {
         let samples = S::vector_to_f32(samples.as_slice());
         let centroids = S::vector_from_f32(&kmeans1d(c, samples.as_slice()));
         return Vec2::from_vec((c, 1), centroids);
     }
-    let mut lloyd_k_means = LloydKMeans::new(c, samples, is_spherical, prefer_kmeanspp);
+    let mut lloyd_k_means = LloydKMeans::new(c, samples, is_spherical, prefer_kmeanspp, seed);
     for _ in 0..iterations {
         rayon::check();
         if lloyd_k_means.iterate() {
@@ -46,6 +45,40 @@ pub fn k_means<S: ScalarLike>(
     lloyd_k_means.finish()
 }
 
+pub fn mini_batch_k_means<S: ScalarLike>(
+    c: usize,
+    mut samples: Vec2<S>,
+    is_spherical: bool,
+    iterations: usize,
+    batch_size: usize,
+    seed: Option<u64>,
+) -> Vec2<S> {
+    assert!(c > 0);
+    let n = samples.shape_0();
+    let dims = samples.shape_1();
+    assert!(dims > 0);
+    if is_spherical {
+        normalize(&mut samples);
+    }
+    if n <= c {
+        return quick_centers::quick_centers(c, samples, seed);
+    }
+    let mut mini_batch_k_means = MiniBatchKMeans::new(c, samples, is_spherical, seed);
+    for _ in 0..iterations {
+        rayon::check();
+        mini_batch_k_means.iterate(batch_size);
+    }
+    mini_batch_k_means.finish()
+}
+
+fn normalize<S: ScalarLike>(samples: &mut Vec2<S>) {
+    for i in 0..samples.shape_0() {
+        let sample = &mut samples[(i,)];
+        let l = S::reduce_sum_of_x2(sample).sqrt();
+        S::vector_mul_scalar_inplace(sample, 1.0 / l);
+    }
+}
+
 pub fn k_means_lookup<S: ScalarLike>(vector: &[S], centroids: &Vec2<S>) -> usize {
     assert_ne!(centroids.shape_0(), 0);
     let mut result = (f32::INFINITY, 0);
@@ -67,3 +100,49 @@ pub fn k_means_lookup_many<S: ScalarLike>(vector: &[S], centroids: &Vec2<S>) ->
     }
     seq
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seeded() {
+        let samples = Vec2::from_vec(
+            (1000, 4),
+            (0..4000)
+                .map(|i| (i as f32 * 0.7).sin())
+                .collect::<Vec<f32>>(),
+        );
+        let train = || {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(4)
+                .build_scoped(|pool| {
+                    pool.install(|| k_means(16, samples.clone(), false, 10, true, Some(42)))
+                })
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(train().as_slice(), train().as_slice());
+    }
+
+    #[test]
+    fn mini_batch() {
+        // two clusters far apart, so every centroid settles in one of them
+        let samples = Vec2::from_vec(
+            (1000, 2),
+            (0..2000)
+                .map(|i| (i as f32 * 0.7).sin() + if i % 4 < 2 { 100.0 } else { -100.0 })
+                .collect::<Vec<f32>>(),
+        );
+        let centroids = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build_scoped(|pool| {
+                pool.install(|| mini_batch_k_means(2, samples, false, 50, 64, Some(42)))
+            })
+            .unwrap()
+            .unwrap();
+        let mut x = [centroids[(0, 0)], centroids[(1, 0)]];
+        x.sort_by(f32::total_cmp);
+        assert!((x[0] + 100.0).abs() < 1.0 && (x[1] - 100.0).abs() < 1.0);
+    }
+}
@@ -2,7 +2,7 @@ use base::scalar::*;
 use common::vec2::Vec2;
 use half::f16;
 use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
+use rand::Rng;
 use rayon::iter::IntoParallelRefMutIterator;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use stoppable_rayon as rayon;
@@ -19,12 +19,20 @@ pub struct LloydKMeans<S> {
 
 const DELTA: f32 = f16::EPSILON.to_f32_const();
 
+const CHUNKS: usize = 32;
+
 impl<S: ScalarLike> LloydKMeans<S> {
-    pub fn new(c: usize, samples: Vec2<S>, is_spherical: bool, prefer_kmeanspp: bool) -> Self {
+    pub fn new(
+        c: usize,
+        samples: Vec2<S>,
+        is_spherical: bool,
+        prefer_kmeanspp: bool,
+        seed: Option<u64>,
+    ) -> Self {
         let n = samples.shape_0();
         let dims = samples.shape_1();
 
-        let mut rng = StdRng::from_entropy();
+        let mut rng = base::rand::rng(seed);
         let mut centroids = Vec::with_capacity(c);
 
         if prefer_kmeanspp {
@@ -91,18 +99,24 @@ impl<S: ScalarLike> LloydKMeans<S> {
         let samples = &self.samples;
         let n = samples.shape_0();
 
-        let (sum, mut count) = (0..n)
+        // sums are taken over fixed chunks and added in order, so that the result
+        // does not depend on how the work is scheduled
+        let chunk = n.div_ceil(CHUNKS);
+        let (sum, mut count) = (0..CHUNKS)
             .into_par_iter()
-            .fold(
-                || (vec![vec![S::zero(); dims]; c], vec![0.0f32; c]),
-                |(mut sum, mut count), i| {
+            .map(|k| {
+                let mut sum = vec![vec![S::zero(); dims]; c];
+                let mut count = vec![0.0f32; c];
+                for i in std::cmp::min(n, k * chunk)..std::cmp::min(n, (k + 1) * chunk) {
                     S::vector_add_inplace(&mut sum[self.assign[i]], &samples[(i,)]);
                     count[self.assign[i]] += 1.0;
-                    (sum, count)
-                },
-            )
-            .reduce(
-                || (vec![vec![S::zero(); dims]; c], vec![0.0f32; c]),
+                }
+                (sum, count)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .fold(
+                (vec![vec![S::zero(); dims]; c], vec![0.0f32; c]),
                 |(mut sum, mut count), (sum_1, count_1)| {
                     for i in 0..c {
                         S::vector_add_inplace(&mut sum[i], &sum_1[i]);
@@ -0,0 +1,76 @@
+use crate::k_means_lookup;
+use base::scalar::*;
+use common::vec2::Vec2;
+use rand::Rng;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use stoppable_rayon as rayon;
+
+/// Mini-batch k-means. Every iteration assigns a random batch of samples and
+/// moves their centroids towards them, with a learning rate of the inverse of
+/// the number of samples a centroid has seen so far.
+pub struct MiniBatchKMeans<S> {
+    c: usize,
+    is_spherical: bool,
+    centroids: Vec2<S>,
+    count: Vec<f32>,
+    rng: rand::rngs::StdRng,
+    samples: Vec2<S>,
+}
+
+impl<S: ScalarLike> MiniBatchKMeans<S> {
+    pub fn new(c: usize, samples: Vec2<S>, is_spherical: bool, seed: Option<u64>) -> Self {
+        let n = samples.shape_0();
+        let dims = samples.shape_1();
+
+        let mut rng = base::rand::rng(seed);
+        let mut centroids = Vec2::zeros((c, dims));
+        for (i, index) in rand::seq::index::sample(&mut rng, n, c)
+            .into_iter()
+            .enumerate()
+        {
+            centroids[(i,)].copy_from_slice(&samples[(index,)]);
+        }
+
+        Self {
+            c,
+            is_spherical,
+            centroids,
+            count: vec![0.0f32; c],
+            rng,
+            samples,
+        }
+    }
+
+    pub fn iterate(&mut self, batch_size: usize) {
+        let n = self.samples.shape_0();
+        let batch = (0..batch_size)
+            .map(|_| self.rng.gen_range(0..n))
+            .collect::<Vec<_>>();
+        let assign = (0..batch_size)
+            .into_par_iter()
+            .map(|k| k_means_lookup(&self.samples[(batch[k],)], &self.centroids))
+            .collect::<Vec<_>>();
+        let mut touched = vec![false; self.c];
+        for k in 0..batch_size {
+            let (i, j) = (batch[k], assign[k]);
+            self.count[j] += 1.0;
+            let eta = 1.0 / self.count[j];
+            let delta = S::vector_mul_scalar(&self.samples[(i,)], eta);
+            let centroid = &mut self.centroids[(j,)];
+            S::vector_mul_scalar_inplace(centroid, 1.0 - eta);
+            S::vector_add_inplace(centroid, &delta);
+            touched[j] = true;
+        }
+        if self.is_spherical {
+            for j in (0..self.c).filter(|&j| touched[j]) {
+                let centroid = &mut self.centroids[(j,)];
+                let l = S::reduce_sum_of_x2(centroid).sqrt();
+                S::vector_mul_scalar_inplace(centroid, 1.0 / l);
+            }
+        }
+    }
+
+    pub fn finish(self) -> Vec2<S> {
+        self.centroids
+    }
+}
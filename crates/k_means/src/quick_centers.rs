@@ -2,11 +2,11 @@ use base::scalar::ScalarLike;
 use common::vec2::Vec2;
 use rand::Rng;
 
-pub fn quick_centers<S: ScalarLike>(c: usize, samples: Vec2<S>) -> Vec2<S> {
+pub fn quick_centers<S: ScalarLike>(c: usize, samples: Vec2<S>, seed: Option<u64>) -> Vec2<S> {
     let n = samples.shape_0();
     let dims = samples.shape_1();
     assert!(c >= n);
-    let mut rng = rand::thread_rng();
+    let mut rng = base::rand::rng(seed);
     let mut centroids = Vec2::zeros((c, dims));
     centroids
         .as_mut_slice()
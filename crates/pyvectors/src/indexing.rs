@@ -1,7 +1,7 @@
 use base::distance::{Distance, DistanceKind};
 use base::index::{IndexOptions, SearchOptions};
 use base::operator::*;
-use base::search::{Collection, Element, Pointer, Source, Vectors};
+use base::search::{Collection, Element, Filter, Pointer, Source, Vectors};
 use base::vector::*;
 use half::f16;
 use std::path::Path;
@@ -91,20 +91,44 @@ impl Indexing {
         &'a self,
         vector: BorrowedVector<'a>,
         opts: &'a SearchOptions,
+        filter: Option<&'a dyn Filter>,
     ) -> impl Iterator<Item = (Distance, Pointer)> + 'a {
+        fn vbase<'a, O: indexing::OperatorIndexing>(
+            x: &'a indexing::SealedIndexing<O>,
+            vector: Borrowed<'a, O>,
+            opts: &'a SearchOptions,
+            filter: Option<&'a dyn Filter>,
+        ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
+            match filter {
+                Some(filter) => x.vbase_filtered(vector, opts, move |u| filter.check(x.payload(u))),
+                None => x.vbase(vector, opts),
+            }
+        }
         match (self, vector) {
-            (Self::Vecf32L2(x), BorrowedVector::Vecf32(vector)) => x.vbase(vector, opts),
-            (Self::Vecf32Dot(x), BorrowedVector::Vecf32(vector)) => x.vbase(vector, opts),
-            (Self::Vecf16Dot(x), BorrowedVector::Vecf16(vector)) => x.vbase(vector, opts),
-            (Self::Vecf16L2(x), BorrowedVector::Vecf16(vector)) => x.vbase(vector, opts),
-            (Self::BVectorDot(x), BorrowedVector::BVector(vector)) => x.vbase(vector, opts),
-            (Self::BVectorHamming(x), BorrowedVector::BVector(vector)) => x.vbase(vector, opts),
-            (Self::BVectorJaccard(x), BorrowedVector::BVector(vector)) => x.vbase(vector, opts),
-            (Self::SVecf32Dot(x), BorrowedVector::SVecf32(vector)) => x.vbase(vector, opts),
-            (Self::SVecf32L2(x), BorrowedVector::SVecf32(vector)) => x.vbase(vector, opts),
+            (Self::Vecf32L2(x), BorrowedVector::Vecf32(vector)) => vbase(x, vector, opts, filter),
+            (Self::Vecf32Dot(x), BorrowedVector::Vecf32(vector)) => vbase(x, vector, opts, filter),
+            (Self::Vecf16Dot(x), BorrowedVector::Vecf16(vector)) => vbase(x, vector, opts, filter),
+            (Self::Vecf16L2(x), BorrowedVector::Vecf16(vector)) => vbase(x, vector, opts, filter),
+            (Self::BVectorDot(x), BorrowedVector::BVector(vector)) => {
+                vbase(x, vector, opts, filter)
+            }
+            (Self::BVectorHamming(x), BorrowedVector::BVector(vector)) => {
+                vbase(x, vector, opts, filter)
+            }
+            (Self::BVectorJaccard(x), BorrowedVector::BVector(vector)) => {
+                vbase(x, vector, opts, filter)
+            }
+            (Self::SVecf32Dot(x), BorrowedVector::SVecf32(vector)) => {
+                vbase(x, vector, opts, filter)
+            }
+            (Self::SVecf32L2(x), BorrowedVector::SVecf32(vector)) => vbase(x, vector, opts, filter),
             _ => panic!("invalid vector type"),
         }
-        .map(|Element { distance, payload }| (distance, payload.0.pointer()))
+        .map(
+            |Element {
+                 distance, payload, ..
+             }| (distance, payload.0.pointer()),
+        )
     }
     pub fn dims(&self) -> u32 {
         match self {
@@ -4,13 +4,14 @@ mod with_labels;
 
 use base::distance::DistanceKind;
 use base::index::*;
-use base::search::Vectors;
+use base::search::{Filter, Payload, Vectors};
 use base::vector::{BorrowedVector, VectorKind};
 use dataset::Dataset;
 use ndarray::{Array1, Array2};
 use numpy::{IntoPyArray, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+use std::collections::HashSet;
 use std::path::PathBuf;
 use validator::Validate;
 use with_labels::WithLabels;
@@ -69,6 +70,9 @@ impl Indexing {
         let index_options = IndexOptions {
             vector: vector_options,
             indexing: indexing_options,
+            truncation: None,
+            seed: None,
+            partition: None,
         };
         index_options.validate().expect("not valid index options");
         // build
@@ -82,12 +86,13 @@ impl Indexing {
     pub fn open(path: &str) -> Self {
         Self(indexing::Indexing::open(path))
     }
-    #[pyo3(signature = (dataset, k, **search_options))]
+    #[pyo3(signature = (dataset, k, filter = None, **search_options))]
     pub fn search<'py>(
         &self,
         py: Python<'py>,
         dataset: PyReadonlyArray2<'py, f32>,
         k: u32,
+        filter: Option<PyReadonlyArray1<'py, i64>>,
         search_options: Option<Bound<'py, PyDict>>,
     ) -> (Bound<'py, PyArray2<f32>>, Bound<'py, PyArray2<i64>>) {
         // dataset
@@ -98,13 +103,25 @@ impl Indexing {
         let search_options: SearchOptions = search_options
             .map(|obj| pythonize::depythonize_bound(obj.into_any()).expect("failed to deserialize"))
             .unwrap_or_default();
+        // filter, as labels of vectors to be searched
+        let filter = filter.map(|labels| {
+            let labels = labels.as_slice().expect("memory is non continuous");
+            labels.iter().map(|&x| x as u64).collect::<HashSet<_>>()
+        });
+        let filter = filter
+            .as_ref()
+            .map(|labels| move |payload: Payload| labels.contains(&payload.pointer().as_u64()));
         // results
         let mut d = Array2::zeros((0, k as usize));
         let mut l = Array2::zeros((0, k as usize));
         for i in 0..dataset.len() {
             let (distances, labels) = self
                 .0
-                .vbase(BorrowedVector::Vecf32(dataset.vector(i)), &search_options)
+                .vbase(
+                    BorrowedVector::Vecf32(dataset.vector(i)),
+                    &search_options,
+                    filter.as_ref().map(|f| f as &dyn Filter),
+                )
                 .map(|(distance, label)| (f32::from(distance), label.as_u64() as i64))
                 .chain(std::iter::repeat((f32::INFINITY, i64::MAX)))
                 .take(k as usize)
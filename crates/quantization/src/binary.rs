@@ -0,0 +1,377 @@
+use crate::quantizer::Quantizer;
+use crate::rabitq::asymmetric_binary_dot_product;
+use crate::rabitq::binarize;
+use crate::reranker::flat::WindowFlatReranker;
+use crate::reranker::graph_2::Graph2Reranker;
+use base::always_equal::AlwaysEqual;
+use base::distance::Distance;
+use base::index::*;
+use base::operator::*;
+use base::scalar::impossible::Impossible;
+use base::scalar::ScalarLike;
+use base::search::RerankerPop;
+use base::search::RerankerPush;
+use base::search::Vectors;
+use base::vector::*;
+use half::f16;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+use serde::Deserialize;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::marker::PhantomData;
+use std::ops::Range;
+use stoppable_rayon as rayon;
+
+/// Codes every dimension by its sign around the mean.
+///
+/// With more than 1 bit, the remaining bits split each side into equal bins,
+/// so the highest bit is always the sign. Bits of codes are stored in planes,
+/// so distances are estimated by popcounts, like RaBitQ. Codes only rank
+/// candidates, which are reranked against the stored vectors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BinaryQuantizer<O: OperatorBinaryQuantization> {
+    dims: u32,
+    bits: u32,
+    mean: Vec<f32>,
+    scale: Vec<f32>,
+    _phantom: PhantomData<fn(O) -> O>,
+}
+
+impl<O: OperatorBinaryQuantization> Quantizer<O> for BinaryQuantizer<O> {
+    fn train(
+        vector_options: VectorOptions,
+        options: Option<QuantizationOptions>,
+        _: Option<u64>,
+        vectors: &(impl Vectors<O::Vector> + Sync),
+        transform: impl Fn(Borrowed<'_, O>) -> O::Vector + Copy + Sync,
+    ) -> Self {
+        let options = if let Some(QuantizationOptions::Binary(x)) = options {
+            x
+        } else {
+            panic!("inconsistent parameters: options and generics")
+        };
+        let dims = vector_options.dims;
+        let bits = options.bits;
+        let n = vectors.len();
+        // sums are taken over fixed chunks and added in order, so that the result
+        // does not depend on how the work is scheduled
+        const CHUNKS: u32 = 32;
+        let chunk = n.div_ceil(CHUNKS);
+        let sum = (0..CHUNKS)
+            .into_par_iter()
+            .map(|k| {
+                let mut sum = vec![0.0f64; dims as usize];
+                for i in std::cmp::min(n, k * chunk)..std::cmp::min(n, (k + 1) * chunk) {
+                    let vector = transform(vectors.vector(i));
+                    for j in 0..dims {
+                        sum[j as usize] += O::get(vector.as_borrowed(), j).to_f32() as f64;
+                    }
+                }
+                sum
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .fold(vec![0.0f64; dims as usize], |mut sum, rsum| {
+                for j in 0..dims {
+                    sum[j as usize] += rsum[j as usize];
+                }
+                sum
+            });
+        let mean = sum
+            .into_iter()
+            .map(|x| if n != 0 { (x / n as f64) as f32 } else { 0.0 })
+            .collect::<Vec<_>>();
+        let scale = (0..n)
+            .into_par_iter()
+            .fold(
+                || vec![0.0f32; dims as usize],
+                |mut scale, i| {
+                    let vector = transform(vectors.vector(i));
+                    for j in 0..dims {
+                        let x = O::get(vector.as_borrowed(), j).to_f32() - mean[j as usize];
+                        scale[j as usize] = scale[j as usize].max(x.abs());
+                    }
+                    scale
+                },
+            )
+            .reduce(
+                || vec![0.0f32; dims as usize],
+                |mut scale, rscale| {
+                    for j in 0..dims {
+                        scale[j as usize] = scale[j as usize].max(rscale[j as usize]);
+                    }
+                    scale
+                },
+            );
+        Self {
+            dims,
+            bits,
+            mean,
+            scale,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn encode(&self, vector: Borrowed<'_, O>) -> Vec<u8> {
+        let dims = self.dims;
+        let levels = 1_u32 << self.bits;
+        let mut code = Vec::with_capacity(dims as usize);
+        let mut norm = 0.0f32;
+        for i in 0..dims {
+            let x = O::get(vector, i).to_f32() - self.mean[i as usize];
+            let scale = self.scale[i as usize];
+            let t = if scale > 0.0 { x / scale } else { 0.0 };
+            // bins split [-1, 1] equally, and `t = 0` goes to the positive side
+            let j = ((t + 1.0) * 0.5 * levels as f32).floor() as i32;
+            let j = j.clamp(0, levels as i32 - 1) as u32;
+            let y = scale * center(levels, j);
+            norm += y * y;
+            code.push(j);
+        }
+        let sum = code.iter().sum::<u32>();
+        let mut result = Vec::with_capacity(self.code_size() as usize);
+        result.extend(norm.to_ne_bytes());
+        result.extend((sum as f32).to_ne_bytes());
+        for k in 0..self.bits {
+            let mut plane = vec![0_u64; dims.div_ceil(64) as usize];
+            for (i, &j) in code.iter().enumerate() {
+                plane[i / 64] |= (((j >> k) & 1) as u64) << (i % 64);
+            }
+            for x in plane {
+                result.extend(x.to_ne_bytes());
+            }
+        }
+        result
+    }
+
+    fn fscan_encode(&self, _: [O::Vector; 32]) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn code_size(&self) -> u32 {
+        size_of::<f32>() as u32 * 2 + size_of::<u64>() as u32 * self.bits * self.dims.div_ceil(64)
+    }
+
+    fn fcode_size(&self) -> u32 {
+        0
+    }
+
+    fn project(&self, vector: Borrowed<'_, O>) -> O::Vector {
+        vector.own()
+    }
+
+    type Lut = (f32, f32, f32, f32, (Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>));
+
+    fn preprocess(&self, vector: Borrowed<'_, O>) -> Self::Lut {
+        use crate::quantize;
+        let (base, weights) = O::preprocess(&self.mean, &self.scale, vector);
+        let (k, b, qvector) = quantize::quantize::<15>(&weights);
+        let qvector_sum = if weights.len() <= 4369 {
+            quantize::reduce_sum_of_x_as_u16(&qvector) as f32
+        } else {
+            quantize::reduce_sum_of_x_as_u32(&qvector) as f32
+        };
+        (base, k, b, qvector_sum, binarize(&qvector))
+    }
+
+    fn process(&self, lut: &Self::Lut, code: &[u8], _: Borrowed<'_, O>) -> Distance {
+        self.estimate(lut, code)
+    }
+
+    type FLut = std::convert::Infallible;
+
+    fn fscan_preprocess(&self, _: Borrowed<'_, O>) -> Self::FLut {
+        unimplemented!()
+    }
+
+    fn fscan_process(&self, flut: &Self::FLut, _: &[u8]) -> [Distance; 32] {
+        match *flut {}
+    }
+
+    type FlatRerankVec = Vec<(Reverse<Distance>, AlwaysEqual<u32>)>;
+
+    fn flat_rerank_start() -> Self::FlatRerankVec {
+        Vec::new()
+    }
+
+    fn flat_rerank_estimates(
+        heap: &Self::FlatRerankVec,
+    ) -> Option<&[(Reverse<Distance>, AlwaysEqual<u32>)]> {
+        Some(heap)
+    }
+
+    fn flat_rerank_preprocess(
+        &self,
+        vector: Borrowed<'_, O>,
+        _: &SearchOptions,
+    ) -> Result<Self::FLut, Self::Lut> {
+        Err(self.preprocess(vector))
+    }
+
+    fn flat_rerank_continue<C>(
+        &self,
+        locate_0: impl Fn(u32) -> C,
+        _: impl Fn(u32) -> C,
+        frlut: &Result<Self::FLut, Self::Lut>,
+        range: Range<u32>,
+        heap: &mut Self::FlatRerankVec,
+    ) where
+        C: AsRef<[u8]>,
+    {
+        match frlut {
+            Ok(flut) => match *flut {},
+            Err(lut) => {
+                for j in range {
+                    let r = self.estimate(lut, locate_0(j).as_ref());
+                    heap.push((Reverse(r), AlwaysEqual(j)));
+                }
+            }
+        }
+    }
+
+    fn flat_rerank_break<'a, T: Send + 'a, R>(
+        &'a self,
+        heap: Self::FlatRerankVec,
+        rerank: R,
+        opts: &SearchOptions,
+    ) -> impl RerankerPop<T> + Send + 'a
+    where
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
+    {
+        WindowFlatReranker::new(heap, rerank, opts.bq_rerank_size)
+    }
+
+    fn graph_rerank<'a, T, R, C>(
+        &'a self,
+        lut: Self::Lut,
+        locate: impl Fn(u32) -> C + Send + 'a,
+        rerank: R,
+    ) -> impl RerankerPush + RerankerPop<T> + Send + 'a
+    where
+        T: Send + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
+        C: AsRef<[u8]>,
+    {
+        Graph2Reranker::new(move |u| self.estimate(&lut, locate(u).as_ref()), rerank)
+    }
+}
+
+impl<O: OperatorBinaryQuantization> BinaryQuantizer<O> {
+    /// Estimates the distance with popcounts of the bit planes of the code
+    /// and of the query, whose weights are quantized into 4 bits.
+    ///
+    /// With `t(j) = (2j + 1 - L) / L` as the center of the `j`-th bin of `L`
+    /// bins and `w ~ b + k * q` as the quantized weights, `sum(w * t(j))` is
+    /// `(2b * sum(j) + b(1 - L) * n + 2k * sum(q * j) + k(1 - L) * sum(q)) / L`.
+    fn estimate(&self, lut: &<Self as Quantizer<O>>::Lut, code: &[u8]) -> Distance {
+        let &(base, k, b, qvector_sum, ref s) = lut;
+        let (norm, sum, planes) = parse_code(code);
+        let words = self.dims.div_ceil(64) as usize;
+        let mut value = 0_u32;
+        for (i, plane) in planes.chunks_exact(words).enumerate() {
+            value += asymmetric_binary_dot_product(plane, s) << i;
+        }
+        let levels = (1_u32 << self.bits) as f32;
+        let n = self.dims as f32;
+        let dot = (2.0 * b * sum
+            + b * (1.0 - levels) * n
+            + 2.0 * k * value as f32
+            + k * (1.0 - levels) * qvector_sum)
+            / levels;
+        O::process(base, norm, dot)
+    }
+}
+
+/// The center of the `j`-th bin of `levels` bins of `[-1, 1]`.
+fn center(levels: u32, j: u32) -> f32 {
+    (2 * j + 1) as f32 / levels as f32 - 1.0
+}
+
+fn parse_code(code: &[u8]) -> (f32, f32, &[u64]) {
+    assert!(code.len() % size_of::<u64>() == 0, "length is incorrect");
+    assert!(code.as_ptr() as usize % 8 == 0, "pointer is not aligned");
+    unsafe {
+        let a = code.as_ptr().add(0).cast::<f32>().read();
+        let b = code.as_ptr().add(4).cast::<f32>().read();
+        let c = std::slice::from_raw_parts(code[8..].as_ptr().cast(), code[8..].len() / 8);
+        (a, b, c)
+    }
+}
+
+pub trait OperatorBinaryQuantization: Operator {
+    type Scalar: ScalarLike;
+    fn get(vector: Borrowed<'_, Self>, i: u32) -> Self::Scalar;
+
+    /// Returns a constant of the query, and the weight of every dimension, so
+    /// that the distance is a function of them and `sum(weight * t)`, where `t`
+    /// is the coded value of a dimension, in the unit of its scale.
+    fn preprocess(mean: &[f32], scale: &[f32], vector: Borrowed<'_, Self>) -> (f32, Vec<f32>);
+    /// Gets the distance from the constant of the query, the squared norm of
+    /// the coded vector around the mean, and `sum(weight * t)`.
+    fn process(base: f32, norm: f32, dot: f32) -> Distance;
+}
+
+impl<S: ScalarLike> OperatorBinaryQuantization for VectDot<S> {
+    type Scalar = S;
+    fn get(vector: Borrowed<'_, Self>, i: u32) -> Self::Scalar {
+        vector.slice()[i as usize]
+    }
+
+    fn preprocess(mean: &[f32], scale: &[f32], vector: Borrowed<'_, Self>) -> (f32, Vec<f32>) {
+        let vector = S::vector_to_f32(vector.slice());
+        let base = f32::reduce_sum_of_xy(&vector, mean);
+        let weights = vector.iter().zip(scale).map(|(x, s)| x * s).collect();
+        (base, weights)
+    }
+    fn process(base: f32, _: f32, dot: f32) -> Distance {
+        Distance::from_f32(-(base + dot))
+    }
+}
+
+impl<S: ScalarLike> OperatorBinaryQuantization for VectL2<S> {
+    type Scalar = S;
+    fn get(vector: Borrowed<'_, Self>, i: u32) -> Self::Scalar {
+        vector.slice()[i as usize]
+    }
+
+    fn preprocess(mean: &[f32], scale: &[f32], vector: Borrowed<'_, Self>) -> (f32, Vec<f32>) {
+        let vector = S::vector_to_f32(vector.slice());
+        let base = f32::reduce_sum_of_d2(&vector, mean);
+        let weights = (vector.iter().zip(mean).zip(scale))
+            .map(|((x, m), s)| (x - m) * s)
+            .collect();
+        (base, weights)
+    }
+    fn process(base: f32, norm: f32, dot: f32) -> Distance {
+        Distance::from_f32(base - 2.0 * dot + norm)
+    }
+}
+
+macro_rules! unimpl_operator_binary_quantization {
+    ($t:ty) => {
+        impl OperatorBinaryQuantization for $t {
+            type Scalar = Impossible;
+            fn get(_: Borrowed<'_, Self>, _: u32) -> Self::Scalar {
+                unimplemented!()
+            }
+
+            fn preprocess(_: &[f32], _: &[f32], _: Borrowed<'_, Self>) -> (f32, Vec<f32>) {
+                unimplemented!()
+            }
+            fn process(_: f32, _: f32, _: f32) -> Distance {
+                unimplemented!()
+            }
+        }
+    };
+}
+
+unimpl_operator_binary_quantization!(BVectorDot);
+unimpl_operator_binary_quantization!(BVectorHamming);
+unimpl_operator_binary_quantization!(BVectorJaccard);
+
+unimpl_operator_binary_quantization!(SVectDot<f32>);
+unimpl_operator_binary_quantization!(SVectL2<f32>);
+unimpl_operator_binary_quantization!(SVectDot<f16>);
+unimpl_operator_binary_quantization!(SVectL2<f16>);
@@ -5,6 +5,7 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::too_many_arguments)]
 
+pub mod binary;
 pub mod fast_scan;
 pub mod product;
 pub mod quantize;
@@ -42,13 +43,20 @@ impl<O: Operator, Q: Quantizer<O>> Quantization<O, Q> {
         path: impl AsRef<Path>,
         vector_options: VectorOptions,
         quantization_options: Option<QuantizationOptions>,
+        seed: Option<u64>,
         vectors: &(impl Vectors<O::Vector> + Sync),
         transform: impl Fn(Borrowed<'_, O>) -> O::Vector + Copy + Send + Sync,
     ) -> Self {
         std::fs::create_dir(path.as_ref()).unwrap();
         let quantizer = Json::create(
             path.as_ref().join("quantizer"),
-            Q::train(vector_options, quantization_options, vectors, transform),
+            Q::train(
+                vector_options,
+                quantization_options,
+                seed,
+                vectors,
+                transform,
+            ),
         );
         let codes = MmapArray::create(path.as_ref().join("codes"), {
             (0..vectors.len())
@@ -160,23 +168,23 @@ impl<O: Operator, Q: Quantizer<O>> Quantization<O, Q> {
         )
     }
 
-    pub fn flat_rerank_break<'a, 'b, T: 'a, R>(
+    pub fn flat_rerank_break<'a, 'b, T: Send + 'a, R>(
         &'a self,
         heap: Q::FlatRerankVec,
         rerank: R,
         opts: &'b SearchOptions,
-    ) -> impl RerankerPop<T> + 'a + use<'a, 'b, T, O, Q, R>
+    ) -> impl RerankerPop<T> + Send + 'a + use<'a, 'b, T, O, Q, R>
     where
-        R: Fn(u32) -> (Distance, T) + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
     {
         Q::flat_rerank_break(&self.quantizer, heap, rerank, opts)
     }
 
-    pub fn graph_rerank<'a, T: 'a, R: Fn(u32) -> (Distance, T) + 'a>(
+    pub fn graph_rerank<'a, T: Send + 'a, R: Fn(u32) -> (Distance, T) + Send + 'a>(
         &'a self,
         lut: Q::Lut,
         rerank: R,
-    ) -> impl RerankerPush + RerankerPop<T> + 'a {
+    ) -> impl RerankerPush + RerankerPop<T> + Send + 'a {
         Q::graph_rerank(
             &self.quantizer,
             lut,
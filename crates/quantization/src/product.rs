@@ -15,11 +15,14 @@ use base::operator::*;
 use base::scalar::impossible::Impossible;
 use base::scalar::ScalarLike;
 use base::search::*;
+use base::vector::VectOwned;
 use base::vector::VectorBorrowed;
 use base::vector::VectorOwned;
 use common::sample::sample;
 use common::vec2::Vec2;
+use half::f16;
 use k_means::k_means;
+use k_means::k_means_lookup;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::Deserialize;
 use serde::Serialize;
@@ -35,12 +38,16 @@ pub struct ProductQuantizer<O: OperatorProductQuantization> {
     bits: u32,
     centroids: Vec2<O::Scalar>,
     tcentroids: Vec2<O::Scalar>,
+    /// Rows of the rotation learned by OPQ, applied before vectors are split.
+    #[serde(default)]
+    rotation: Option<Vec<Vec<O::Scalar>>>,
 }
 
 impl<O: OperatorProductQuantization> Quantizer<O> for ProductQuantizer<O> {
     fn train(
         vector_options: VectorOptions,
         options: Option<QuantizationOptions>,
+        seed: Option<u64>,
         vectors: &(impl Vectors<O::Vector> + Sync),
         transform: impl Fn(Borrowed<'_, O>) -> O::Vector + Copy + Sync,
     ) -> Self {
@@ -52,21 +59,25 @@ impl<O: OperatorProductQuantization> Quantizer<O> for ProductQuantizer<O> {
         };
         let ratio = options.ratio;
         let bits = options.bits;
+        let rotation = options
+            .opq
+            .then(|| train_rotation::<O>(dims, ratio, bits, seed, vectors, transform));
+        let rotate = |vector: Borrowed<'_, O>| match &rotation {
+            Some(rotation) => O::rotate(rotation, transform(vector).as_borrowed()),
+            None => transform(vector),
+        };
         let points = (0..dims.div_ceil(ratio))
             .into_par_iter()
             .map(|p| {
                 let subdims = std::cmp::min(ratio, dims - ratio * p);
                 let start = p * ratio;
                 let end = start + subdims;
-                let subsamples = sample(vectors.len(), 65536, end - start, |i| {
-                    O::subslice(
-                        transform(vectors.vector(i)).as_borrowed(),
-                        start,
-                        end - start,
-                    )
-                    .to_vec()
+                let seed = seed.map(|seed| seed.wrapping_add(p as u64));
+                let subsamples = sample(vectors.len(), 65536, end - start, seed, |i| {
+                    O::subslice(rotate(vectors.vector(i)).as_borrowed(), start, end - start)
+                        .to_vec()
                 });
-                k_means(1 << bits, subsamples, false, 25, true)
+                k_means(1 << bits, subsamples, false, 25, true, seed)
             })
             .collect::<Vec<_>>();
         let mut centroids = Vec2::zeros((1 << bits, dims as usize));
@@ -89,6 +100,7 @@ impl<O: OperatorProductQuantization> Quantizer<O> for ProductQuantizer<O> {
             bits,
             centroids,
             tcentroids,
+            rotation,
         }
     }
 
@@ -142,7 +154,10 @@ impl<O: OperatorProductQuantization> Quantizer<O> for ProductQuantizer<O> {
     }
 
     fn project(&self, vector: Borrowed<'_, O>) -> O::Vector {
-        vector.own()
+        match &self.rotation {
+            Some(rotation) => O::rotate(rotation, vector),
+            None => vector.own(),
+        }
     }
 
     type Lut = Vec<f32>;
@@ -176,6 +191,12 @@ impl<O: OperatorProductQuantization> Quantizer<O> for ProductQuantizer<O> {
         Vec::new()
     }
 
+    fn flat_rerank_estimates(
+        heap: &Self::FlatRerankVec,
+    ) -> Option<&[(Reverse<Distance>, AlwaysEqual<u32>)]> {
+        Some(heap)
+    }
+
     fn flat_rerank_preprocess(
         &self,
         vector: Borrowed<'_, O>,
@@ -252,14 +273,14 @@ impl<O: OperatorProductQuantization> Quantizer<O> for ProductQuantizer<O> {
         }
     }
 
-    fn flat_rerank_break<'a, T: 'a, R>(
+    fn flat_rerank_break<'a, T: Send + 'a, R>(
         &'a self,
         heap: Vec<(Reverse<Distance>, AlwaysEqual<u32>)>,
         rerank: R,
         opts: &SearchOptions,
-    ) -> impl RerankerPop<T> + 'a
+    ) -> impl RerankerPop<T> + Send + 'a
     where
-        R: Fn(u32) -> (Distance, T) + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
     {
         WindowFlatReranker::new(heap, rerank, opts.pq_rerank_size)
     }
@@ -267,12 +288,12 @@ impl<O: OperatorProductQuantization> Quantizer<O> for ProductQuantizer<O> {
     fn graph_rerank<'a, T, R, C>(
         &'a self,
         lut: Self::Lut,
-        locate: impl Fn(u32) -> C + 'a,
+        locate: impl Fn(u32) -> C + Send + 'a,
         rerank: R,
-    ) -> impl RerankerPush + RerankerPop<T> + 'a
+    ) -> impl RerankerPush + RerankerPop<T> + Send + 'a
     where
-        T: 'a,
-        R: Fn(u32) -> (Distance, T) + 'a,
+        T: Send + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
         C: AsRef<[u8]>,
     {
         Graph2Reranker::new(
@@ -282,9 +303,73 @@ impl<O: OperatorProductQuantization> Quantizer<O> for ProductQuantizer<O> {
     }
 }
 
+/// Learns a rotation by alternating between training sub-quantizers on rotated
+/// samples and solving the orthogonal Procrustes problem from samples to their
+/// reconstructions, as the non-parametric variant of OPQ does.
+fn train_rotation<O: OperatorProductQuantization>(
+    dims: u32,
+    ratio: u32,
+    bits: u32,
+    seed: Option<u64>,
+    vectors: &(impl Vectors<O::Vector> + Sync),
+    transform: impl Fn(Borrowed<'_, O>) -> O::Vector + Copy + Sync,
+) -> Vec<Vec<O::Scalar>> {
+    use nalgebra::DMatrix;
+    const SAMPLES: u32 = 8192;
+    const ITERATIONS: usize = 4;
+    let samples = sample(vectors.len(), SAMPLES, dims, seed, |i| {
+        O::Scalar::vector_to_f32(O::subslice(
+            transform(vectors.vector(i)).as_borrowed(),
+            0,
+            dims,
+        ))
+    });
+    let (n, d) = (samples.shape_0(), dims as usize);
+    let x = DMatrix::from_row_slice(n, d, samples.as_slice());
+    let mut rotation = DMatrix::<f32>::identity(d, d);
+    for iteration in 0..ITERATIONS {
+        rayon::check();
+        let y = &x * rotation.transpose();
+        let reconstructions = (0..dims.div_ceil(ratio))
+            .into_par_iter()
+            .map(|p| {
+                let start = (p * ratio) as usize;
+                let subdims = std::cmp::min(ratio, dims - ratio * p) as usize;
+                let mut subsamples = Vec2::zeros((n, subdims));
+                for i in 0..n {
+                    for k in 0..subdims {
+                        subsamples[(i, k)] = y[(i, start + k)];
+                    }
+                }
+                let seed = seed.map(|seed| seed.wrapping_add((iteration as u64) << 32 | p as u64));
+                let centroids = k_means(1 << bits, subsamples.clone(), false, 10, true, seed);
+                (0..n)
+                    .map(|i| centroids[(k_means_lookup(&subsamples[(i,)], &centroids),)].to_vec())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut r = DMatrix::<f32>::zeros(n, d);
+        for (p, reconstruction) in reconstructions.into_iter().enumerate() {
+            let start = p * ratio as usize;
+            for (i, subvector) in reconstruction.into_iter().enumerate() {
+                for (k, value) in subvector.into_iter().enumerate() {
+                    r[(i, start + k)] = value;
+                }
+            }
+        }
+        let svd = (r.transpose() * &x).svd(true, true);
+        rotation = svd.u.unwrap() * svd.v_t.unwrap();
+    }
+    rotation
+        .row_iter()
+        .map(|row| O::Scalar::vector_from_f32(&row.iter().copied().collect::<Vec<_>>()))
+        .collect()
+}
+
 pub trait OperatorProductQuantization: Operator {
     type Scalar: ScalarLike;
     fn subslice(vector: Borrowed<'_, Self>, start: u32, len: u32) -> &[Self::Scalar];
+    fn rotate(rotation: &[Vec<Self::Scalar>], vector: Borrowed<'_, Self>) -> Self::Vector;
     fn code(
         dims: u32,
         ratio: u32,
@@ -316,6 +401,12 @@ impl<S: ScalarLike> OperatorProductQuantization for VectDot<S> {
     fn subslice(vector: Borrowed<'_, Self>, start: u32, len: u32) -> &[Self::Scalar] {
         &vector.slice()[start as usize..][..len as usize]
     }
+    fn rotate(rotation: &[Vec<Self::Scalar>], vector: Borrowed<'_, Self>) -> Self::Vector {
+        let slice = (0..rotation.len())
+            .map(|i| S::from_f32(S::reduce_sum_of_xy(&rotation[i], vector.slice())))
+            .collect();
+        VectOwned::new(slice)
+    }
     fn code(
         dims: u32,
         ratio: u32,
@@ -516,6 +607,12 @@ impl<S: ScalarLike> OperatorProductQuantization for VectL2<S> {
     fn subslice(vector: Borrowed<'_, Self>, start: u32, len: u32) -> &[Self::Scalar] {
         &vector.slice()[start as usize..][..len as usize]
     }
+    fn rotate(rotation: &[Vec<Self::Scalar>], vector: Borrowed<'_, Self>) -> Self::Vector {
+        let slice = (0..rotation.len())
+            .map(|i| S::from_f32(S::reduce_sum_of_xy(&rotation[i], vector.slice())))
+            .collect();
+        VectOwned::new(slice)
+    }
     fn code(
         dims: u32,
         ratio: u32,
@@ -718,6 +815,9 @@ macro_rules! unimpl_operator_product_quantization {
             fn subslice(_: Borrowed<'_, Self>, _: u32, _: u32) -> &[Self::Scalar] {
                 unimplemented!()
             }
+            fn rotate(_: &[Vec<Self::Scalar>], _: Borrowed<'_, Self>) -> Self::Vector {
+                unimplemented!()
+            }
             fn code(
                 _: u32,
                 _: u32,
@@ -763,3 +863,5 @@ unimpl_operator_product_quantization!(BVectorJaccard);
 
 unimpl_operator_product_quantization!(SVectDot<f32>);
 unimpl_operator_product_quantization!(SVectL2<f32>);
+unimpl_operator_product_quantization!(SVectDot<f16>);
+unimpl_operator_product_quantization!(SVectL2<f16>);
@@ -1,9 +1,11 @@
+use base::always_equal::AlwaysEqual;
 use base::distance::Distance;
 use base::index::{QuantizationOptions, SearchOptions, VectorOptions};
 use base::operator::Borrowed;
 use base::operator::Operator;
 use base::search::{RerankerPop, RerankerPush, Vectors};
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use std::ops::Range;
 
 pub trait Quantizer<O: Operator>:
@@ -12,6 +14,7 @@ pub trait Quantizer<O: Operator>:
     fn train(
         vector_options: VectorOptions,
         options: Option<QuantizationOptions>,
+        seed: Option<u64>,
         vectors: &(impl Vectors<O::Vector> + Sync),
         transform: impl Fn(Borrowed<'_, O>) -> O::Vector + Copy + Sync,
     ) -> Self;
@@ -23,7 +26,7 @@ pub trait Quantizer<O: Operator>:
 
     fn project(&self, vector: Borrowed<'_, O>) -> O::Vector;
 
-    type Lut;
+    type Lut: Send;
     fn preprocess(&self, vector: Borrowed<'_, O>) -> Self::Lut;
     fn process(&self, lut: &Self::Lut, code: &[u8], vector: Borrowed<'_, O>) -> Distance;
 
@@ -31,7 +34,7 @@ pub trait Quantizer<O: Operator>:
     fn fscan_preprocess(&self, vector: Borrowed<'_, O>) -> Self::FLut;
     fn fscan_process(&self, flut: &Self::FLut, code: &[u8]) -> [Distance; 32];
 
-    type FlatRerankVec;
+    type FlatRerankVec: Send;
 
     fn flat_rerank_start() -> Self::FlatRerankVec;
 
@@ -51,23 +54,29 @@ pub trait Quantizer<O: Operator>:
     ) where
         C: AsRef<[u8]>;
 
-    fn flat_rerank_break<'a, T: 'a, R>(
+    /// Estimated distances of candidates in `heap`, in the order that they are
+    /// pushed, or `None` if distances are only known after reranking.
+    fn flat_rerank_estimates(
+        heap: &Self::FlatRerankVec,
+    ) -> Option<&[(Reverse<Distance>, AlwaysEqual<u32>)]>;
+
+    fn flat_rerank_break<'a, T: Send + 'a, R>(
         &'a self,
         heap: Self::FlatRerankVec,
         rerank: R,
         opts: &SearchOptions,
-    ) -> impl RerankerPop<T> + 'a
+    ) -> impl RerankerPop<T> + Send + 'a
     where
-        R: Fn(u32) -> (Distance, T) + 'a;
+        R: Fn(u32) -> (Distance, T) + Send + 'a;
 
     fn graph_rerank<'a, T, R, C>(
         &'a self,
         lut: Self::Lut,
-        locate: impl Fn(u32) -> C + 'a,
+        locate: impl Fn(u32) -> C + Send + 'a,
         rerank: R,
-    ) -> impl RerankerPush + RerankerPop<T> + 'a
+    ) -> impl RerankerPush + RerankerPop<T> + Send + 'a
     where
-        T: 'a,
-        R: Fn(u32) -> (Distance, T) + 'a,
+        T: Send + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
         C: AsRef<[u8]>;
 }
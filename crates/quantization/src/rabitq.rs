@@ -2,7 +2,9 @@ use crate::fast_scan::b4::fast_scan_b4;
 use crate::fast_scan::b4::pack;
 use crate::quantizer::Quantizer;
 use crate::reranker::error::ErrorFlatReranker;
+use crate::reranker::flat::WindowFlatReranker;
 use crate::reranker::graph_2::Graph2Reranker;
+use crate::utils::random_orthogonal_matrix;
 use crate::utils::InfiniteByteChunks;
 use base::always_equal::AlwaysEqual;
 use base::distance::Distance;
@@ -14,47 +16,63 @@ use base::search::*;
 use base::vector::VectOwned;
 use base::vector::VectorBorrowed;
 use base::vector::VectorOwned;
+use half::f16;
 use serde::{Deserialize, Serialize};
 use std::cmp::Reverse;
 use std::marker::PhantomData;
 use std::ops::Range;
 
+/// With more than 1 bit, every code is followed by an extended code, as in
+/// extended RaBitQ. It gives a finer estimation than signs, but no error bound.
+///
+/// Each projected dimension falls into one of `2^bits` equal bins, whose
+/// highest bit is the sign in the code, so the extended code only keeps the
+/// other `bits - 1` bits. Bits of extended codes are stored in planes, from
+/// the lowest bit, so they are scanned with popcounts like signs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(bound = "")]
 pub struct RabitqQuantizer<O: OperatorRabitqQuantization> {
     dims: u32,
+    #[serde(default = "default_bits")]
+    bits: u32,
     projection: Vec<Vec<O::Scalar>>,
     _maker: PhantomData<O>,
 }
 
+fn default_bits() -> u32 {
+    1
+}
+
 impl<O: OperatorRabitqQuantization> Quantizer<O> for RabitqQuantizer<O> {
     fn train(
         vector_options: VectorOptions,
-        _: Option<QuantizationOptions>,
+        options: Option<QuantizationOptions>,
+        seed: Option<u64>,
         _: &(impl Vectors<O::Vector> + Sync),
         _: impl Fn(Borrowed<'_, O>) -> O::Vector + Copy + Sync,
     ) -> Self {
+        let options = if let Some(QuantizationOptions::Rabitq(x)) = options {
+            x
+        } else {
+            panic!("inconsistent parameters: options and generics")
+        };
         let dims = vector_options.dims;
         let projection = {
-            use nalgebra::{DMatrix, QR};
-            use rand::{Rng, SeedableRng};
+            use rand::SeedableRng;
             use rand_chacha::ChaCha12Rng;
-            use rand_distr::StandardNormal;
-            let mut rng = ChaCha12Rng::from_seed([7; 32]);
-            let matrix = DMatrix::from_fn(dims as usize, dims as usize, |_, _| {
-                rng.sample(StandardNormal)
-            });
-            let qr = QR::new(matrix);
-            let q = qr.q();
-            let mut projection = Vec::new();
-            for v in q.row_iter() {
-                let vector = v.iter().copied().collect::<Vec<_>>();
-                projection.push(O::Scalar::vector_from_f32(&vector));
-            }
-            projection
+            // the seed of the quantization wins over the seed of the index
+            let mut rng = match options.seed.or(seed) {
+                Some(seed) => ChaCha12Rng::seed_from_u64(seed),
+                None => ChaCha12Rng::from_seed([7; 32]),
+            };
+            random_orthogonal_matrix(dims, &mut rng)
+                .iter()
+                .map(|row| O::Scalar::vector_from_f32(row))
+                .collect()
         };
         Self {
             dims,
+            bits: options.bits,
             projection,
             _maker: PhantomData,
         }
@@ -63,7 +81,7 @@ impl<O: OperatorRabitqQuantization> Quantizer<O> for RabitqQuantizer<O> {
     fn encode(&self, vector: Borrowed<'_, O>) -> Vec<u8> {
         let dims = self.dims;
         let (a, b, c, d, e) = O::code(vector);
-        let mut result = Vec::with_capacity(size_of::<f32>() * 4);
+        let mut result = Vec::with_capacity(self.code_size() as usize);
         result.extend(a.to_ne_bytes());
         result.extend(b.to_ne_bytes());
         result.extend(c.to_ne_bytes());
@@ -75,6 +93,20 @@ impl<O: OperatorRabitqQuantization> Quantizer<O> for RabitqQuantizer<O> {
             }
             result.extend(r.to_ne_bytes().into_iter());
         }
+        if self.bits > 1 {
+            let (k, b, codes) = ex_code(self.bits, &O::vector_to_f32(vector));
+            result.extend(k.to_ne_bytes());
+            result.extend(b.to_ne_bytes());
+            for i in 0..self.bits - 1 {
+                let mut plane = vec![0_u64; dims.div_ceil(64) as usize];
+                for (j, &code) in codes.iter().enumerate() {
+                    plane[j / 64] |= (((code >> i) & 1) as u64) << (j % 64);
+                }
+                for x in plane {
+                    result.extend(x.to_ne_bytes());
+                }
+            }
+        }
         result
     }
 
@@ -105,7 +137,13 @@ impl<O: OperatorRabitqQuantization> Quantizer<O> for RabitqQuantizer<O> {
     }
 
     fn code_size(&self) -> u32 {
-        size_of::<f32>() as u32 * 4 + size_of::<u64>() as u32 * self.dims.div_ceil(64)
+        if self.bits > 1 {
+            self.sign_code_size()
+                + size_of::<f32>() as u32 * 2
+                + size_of::<u64>() as u32 * (self.bits - 1) * self.dims.div_ceil(64)
+        } else {
+            self.sign_code_size()
+        }
     }
 
     fn fcode_size(&self) -> u32 {
@@ -119,8 +157,7 @@ impl<O: OperatorRabitqQuantization> Quantizer<O> for RabitqQuantizer<O> {
     }
 
     fn process(&self, lut: &Self::Lut, code: &[u8], _: Borrowed<'_, O>) -> Distance {
-        let c = parse_code(code);
-        O::process(lut, c)
+        self.estimate(lut, code)
     }
 
     fn project(&self, vector: Borrowed<'_, O>) -> O::Vector {
@@ -144,12 +181,18 @@ impl<O: OperatorRabitqQuantization> Quantizer<O> for RabitqQuantizer<O> {
         Vec::new()
     }
 
+    fn flat_rerank_estimates(
+        heap: &Self::FlatRerankVec,
+    ) -> Option<&[(Reverse<Distance>, AlwaysEqual<u32>)]> {
+        Some(heap)
+    }
+
     fn flat_rerank_preprocess(
         &self,
         vector: Borrowed<'_, O>,
         opts: &SearchOptions,
     ) -> Result<Self::FLut, Self::Lut> {
-        if opts.rq_fast_scan {
+        if self.bits == 1 && opts.rq_fast_scan {
             Ok(self.fscan_preprocess(vector))
         } else {
             Err(self.preprocess(vector))
@@ -217,10 +260,16 @@ impl<O: OperatorRabitqQuantization> Quantizer<O> for RabitqQuantizer<O> {
                     }
                 }
             }
+            Err(lut) if self.bits > 1 => {
+                for j in range {
+                    let r = self.estimate(lut, locate_0(j).as_ref());
+                    heap.push((Reverse(r), AlwaysEqual(j)));
+                }
+            }
             Err(lut) => {
                 for j in range {
                     let c = locate_0(j);
-                    let c = parse_code(c.as_ref());
+                    let c = parse_code(self.sign_code(c.as_ref()));
                     let r = O::process_lowerbound(lut, c, 1.9);
                     heap.push((Reverse(r), AlwaysEqual(j)));
                 }
@@ -228,33 +277,133 @@ impl<O: OperatorRabitqQuantization> Quantizer<O> for RabitqQuantizer<O> {
         }
     }
 
-    fn flat_rerank_break<'a, T: 'a, R>(
+    fn flat_rerank_break<'a, T: Send + 'a, R>(
         &'a self,
         heap: Self::FlatRerankVec,
         rerank: R,
-        _: &SearchOptions,
-    ) -> impl RerankerPop<T> + 'a
+        opts: &SearchOptions,
+    ) -> impl RerankerPop<T> + Send + 'a
     where
-        R: Fn(u32) -> (Distance, T) + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
     {
-        ErrorFlatReranker::new(heap, rerank)
+        // estimations of extended codes are not lower bounds, so they are
+        // always reranked in a window
+        match (self.bits > 1, opts.rq_rerank_size) {
+            (false, 0) => Reranker::Error(ErrorFlatReranker::new(heap, rerank)),
+            (true, 0) => Reranker::Window(WindowFlatReranker::new(heap, rerank, EX_RERANK_SIZE)),
+            (_, size) => Reranker::Window(WindowFlatReranker::new(heap, rerank, size)),
+        }
     }
 
     fn graph_rerank<'a, T, R, C>(
         &'a self,
         lut: Self::Lut,
-        locate: impl Fn(u32) -> C + 'a,
+        locate: impl Fn(u32) -> C + Send + 'a,
         rerank: R,
-    ) -> impl RerankerPush + RerankerPop<T> + 'a
+    ) -> impl RerankerPush + RerankerPop<T> + Send + 'a
     where
-        T: 'a,
-        R: Fn(u32) -> (Distance, T) + 'a,
+        T: Send + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
         C: AsRef<[u8]>,
     {
-        Graph2Reranker::new(
-            move |u| O::process(&lut, parse_code(locate(u).as_ref())),
-            rerank,
-        )
+        Graph2Reranker::new(move |u| self.estimate(&lut, locate(u).as_ref()), rerank)
+    }
+}
+
+impl<O: OperatorRabitqQuantization> RabitqQuantizer<O> {
+    fn sign_code_size(&self) -> u32 {
+        size_of::<f32>() as u32 * 4 + size_of::<u64>() as u32 * self.dims.div_ceil(64)
+    }
+
+    fn sign_code<'a>(&self, code: &'a [u8]) -> &'a [u8] {
+        &code[..self.sign_code_size() as usize]
+    }
+
+    fn estimate(&self, lut: &O::Lut, code: &[u8]) -> Distance {
+        let c = parse_code(self.sign_code(code));
+        if self.bits > 1 {
+            let (k, b, ex) = parse_ex_code(&code[self.sign_code_size() as usize..]);
+            // the sign is the highest bit of bins
+            let planes = ex
+                .chunks_exact(self.dims.div_ceil(64) as usize)
+                .chain(std::iter::once(c.4));
+            O::process_ex(lut, self.dims, c.0, (k, b), planes)
+        } else {
+            O::process(lut, c)
+        }
+    }
+}
+
+/// Quantizes every dimension into `2^bits` equal bins of `[-m, m]`, where `m`
+/// is the maximum absolute value. Bins are split by the sign first, so the
+/// highest bit of a bin is the sign in the code. It returns `k` and `b`, so
+/// `k * <code, q> + b * sum(q)` estimates `<x, q>` after the bin centers are
+/// rescaled to make the estimation of `<x, x>` exact.
+fn ex_code(bits: u32, vector: &[f32]) -> (f32, f32, Vec<u8>) {
+    let half = 1_u32 << (bits - 1);
+    let m = vector.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+    if m == 0.0 {
+        let codes = vector
+            .iter()
+            .map(|x| if x.is_sign_positive() { half } else { half - 1 } as u8)
+            .collect();
+        return (0.0, 0.0, codes);
+    }
+    let step = m / half as f32;
+    let codes = vector
+        .iter()
+        .map(|x| {
+            let magnitude = ((x.abs() / step).floor() as u32).min(half - 1);
+            if x.is_sign_positive() {
+                (half + magnitude) as u8
+            } else {
+                (half - 1 - magnitude) as u8
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut dot = 0.0f32;
+    let mut sum_of_x2 = 0.0f32;
+    for (&x, &c) in vector.iter().zip(codes.iter()) {
+        dot += x * (step * (c as f32 + 0.5) - m);
+        sum_of_x2 += x * x;
+    }
+    let r = if dot > 0.0 { sum_of_x2 / dot } else { 0.0 };
+    (r * step, r * (0.5 * step - m), codes)
+}
+
+/// Gets `<code, q>` and `sum(q)` of a query quantized as `b + k * q'`, from
+/// bit planes of codes, from the lowest bit.
+fn ex_dot<'a>(
+    dims: u32,
+    (b, k, qvector_sum, s): (f32, f32, f32, &(Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>)),
+    planes: impl Iterator<Item = &'a [u64]>,
+) -> (f32, f32) {
+    let mut value = 0_u32;
+    let mut sum = 0_u32;
+    for (i, plane) in planes.enumerate() {
+        value += asymmetric_binary_dot_product(plane, s) << i;
+        sum += plane.iter().map(|x| x.count_ones()).sum::<u32>() << i;
+    }
+    (
+        b * sum as f32 + k * value as f32,
+        b * dims as f32 + k * qvector_sum,
+    )
+}
+
+/// The reranker window of extended codes if `rq_rerank_size` is 0.
+const EX_RERANK_SIZE: u32 = 100;
+
+enum Reranker<A, B> {
+    Error(A),
+    Window(B),
+}
+
+impl<T, A: RerankerPop<T>, B: RerankerPop<T>> RerankerPop<T> for Reranker<A, B> {
+    fn pop(&mut self) -> Option<(Distance, u32, T)> {
+        match self {
+            Reranker::Error(x) => x.pop(),
+            Reranker::Window(x) => x.pop(),
+        }
     }
 }
 
@@ -265,9 +414,20 @@ pub trait OperatorRabitqQuantization: Operator {
 
     fn project(projection: &[Vec<Self::Scalar>], vector: Borrowed<'_, Self>) -> Self::Vector;
 
-    type Lut;
+    fn vector_to_f32(vector: Borrowed<'_, Self>) -> Vec<f32>;
+
+    type Lut: Send;
     fn preprocess(vector: Borrowed<'_, Self>) -> Self::Lut;
     fn process(lut: &Self::Lut, code: (f32, f32, f32, f32, &[u64])) -> Distance;
+    /// Gets the distance from the squared norm of the vector, and `k`, `b` and
+    /// bit planes of its extended code, with the sign as the highest bit.
+    fn process_ex<'a>(
+        lut: &Self::Lut,
+        dims: u32,
+        dis_u_2: f32,
+        ex: (f32, f32),
+        planes: impl Iterator<Item = &'a [u64]>,
+    ) -> Distance;
     fn process_lowerbound(
         lut: &Self::Lut,
         code: (f32, f32, f32, f32, &[u64]),
@@ -327,6 +487,10 @@ impl<S: ScalarLike> OperatorRabitqQuantization for VectL2<S> {
         VectOwned::new(slice)
     }
 
+    fn vector_to_f32(vector: Borrowed<'_, Self>) -> Vec<f32> {
+        S::vector_to_f32(vector.slice())
+    }
+
     type Lut = (f32, f32, f32, f32, (Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>));
 
     fn preprocess(vector: Borrowed<'_, Self>) -> Self::Lut {
@@ -356,6 +520,19 @@ impl<S: ScalarLike> OperatorRabitqQuantization for VectL2<S> {
         Distance::from_f32(rough)
     }
 
+    fn process_ex<'a>(
+        lut: &Self::Lut,
+        dims: u32,
+        dis_u_2: f32,
+        (k, b): (f32, f32),
+        planes: impl Iterator<Item = &'a [u64]>,
+    ) -> Distance {
+        let &(dis_v_2, qb, qk, qvector_sum, ref s) = lut;
+        let (dot, sum) = ex_dot(dims, (qb, qk, qvector_sum, s), planes);
+        let ip = k * dot + b * sum;
+        Distance::from_f32(dis_u_2 + dis_v_2 - 2.0 * ip)
+    }
+
     fn process_lowerbound(
         lut: &Self::Lut,
         (dis_u_2, factor_ppc, factor_ip, factor_err, t): (f32, f32, f32, f32, &[u64]),
@@ -472,6 +649,10 @@ impl<S: ScalarLike> OperatorRabitqQuantization for VectDot<S> {
         VectOwned::new(slice)
     }
 
+    fn vector_to_f32(vector: Borrowed<'_, Self>) -> Vec<f32> {
+        S::vector_to_f32(vector.slice())
+    }
+
     type Lut = (f32, f32, f32, f32, (Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>));
 
     fn preprocess(vector: Borrowed<'_, Self>) -> Self::Lut {
@@ -499,6 +680,19 @@ impl<S: ScalarLike> OperatorRabitqQuantization for VectDot<S> {
         Distance::from_f32(rough)
     }
 
+    fn process_ex<'a>(
+        lut: &Self::Lut,
+        dims: u32,
+        _: f32,
+        (k, b): (f32, f32),
+        planes: impl Iterator<Item = &'a [u64]>,
+    ) -> Distance {
+        let &(_, qb, qk, qvector_sum, ref s) = lut;
+        let (dot, sum) = ex_dot(dims, (qb, qk, qvector_sum, s), planes);
+        let ip = k * dot + b * sum;
+        Distance::from_f32(-ip)
+    }
+
     fn process_lowerbound(
         lut: &Self::Lut,
         (_, factor_ppc, factor_ip, factor_err, t): (f32, f32, f32, f32, &[u64]),
@@ -578,6 +772,10 @@ macro_rules! unimpl_operator_rabitq_quantization {
                 unimplemented!()
             }
 
+            fn vector_to_f32(_: Borrowed<'_, Self>) -> Vec<f32> {
+                unimplemented!()
+            }
+
             type Lut = std::convert::Infallible;
             fn preprocess(_: Borrowed<'_, Self>) -> Self::Lut {
                 unimplemented!()
@@ -585,6 +783,15 @@ macro_rules! unimpl_operator_rabitq_quantization {
             fn process(_: &Self::Lut, _: (f32, f32, f32, f32, &[u64])) -> Distance {
                 unimplemented!()
             }
+            fn process_ex<'a>(
+                _: &Self::Lut,
+                _: u32,
+                _: f32,
+                _: (f32, f32),
+                _: impl Iterator<Item = &'a [u64]>,
+            ) -> Distance {
+                unimplemented!()
+            }
             fn process_lowerbound(
                 _: &Self::Lut,
                 _: (f32, f32, f32, f32, &[u64]),
@@ -622,6 +829,8 @@ unimpl_operator_rabitq_quantization!(BVectorJaccard);
 
 unimpl_operator_rabitq_quantization!(SVectDot<f32>);
 unimpl_operator_rabitq_quantization!(SVectL2<f32>);
+unimpl_operator_rabitq_quantization!(SVectDot<f16>);
+unimpl_operator_rabitq_quantization!(SVectL2<f16>);
 
 fn parse_code(code: &[u8]) -> (f32, f32, f32, f32, &[u64]) {
     assert!(code.len() > size_of::<f32>() * 4, "length is incorrect");
@@ -637,6 +846,17 @@ fn parse_code(code: &[u8]) -> (f32, f32, f32, f32, &[u64]) {
     }
 }
 
+fn parse_ex_code(code: &[u8]) -> (f32, f32, &[u64]) {
+    assert!(code.len() % size_of::<u64>() == 0, "length is incorrect");
+    assert!(code.as_ptr() as usize % 8 == 0, "pointer is not aligned");
+    unsafe {
+        let a = code.as_ptr().add(0).cast::<f32>().read();
+        let b = code.as_ptr().add(4).cast::<f32>().read();
+        let c = std::slice::from_raw_parts(code[8..].as_ptr().cast(), code[8..].len() / 8);
+        (a, b, c)
+    }
+}
+
 fn parses_codes(code: &[u8]) -> (&[f32; 32], &[f32; 32], &[f32; 32], &[f32; 32], &[u8]) {
     assert!(code.len() > size_of::<f32>() * 128, "length is incorrect");
     assert!(code.as_ptr() as usize % 4 == 0, "pointer is not aligned");
@@ -685,7 +905,7 @@ fn gen(mut qvector: Vec<u8>) -> Vec<u8> {
     lut
 }
 
-fn binarize(vector: &[u8]) -> (Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>) {
+pub fn binarize(vector: &[u8]) -> (Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>) {
     let n = vector.len();
     let mut t0 = vec![0u64; n.div_ceil(64)];
     let mut t1 = vec![0u64; n.div_ceil(64)];
@@ -701,7 +921,10 @@ fn binarize(vector: &[u8]) -> (Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>) {
 }
 
 #[detect::multiversion(v2, fallback)]
-fn asymmetric_binary_dot_product(x: &[u64], y: &(Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>)) -> u32 {
+pub fn asymmetric_binary_dot_product(
+    x: &[u64],
+    y: &(Vec<u64>, Vec<u64>, Vec<u64>, Vec<u64>),
+) -> u32 {
     assert_eq!(x.len(), y.0.len());
     assert_eq!(x.len(), y.1.len());
     assert_eq!(x.len(), y.2.len());
@@ -722,3 +945,22 @@ fn asymmetric_binary_dot_product(x: &[u64], y: &(Vec<u64>, Vec<u64>, Vec<u64>, V
     }
     (t0 << 0) + (t1 << 1) + (t2 << 2) + (t3 << 3)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ex_code_sign() {
+        let vector = (0..37)
+            .map(|i| ((i * 7 + 3) % 11) as f32 - 5.0)
+            .collect::<Vec<_>>();
+        for bits in 2..=8 {
+            let (_, _, codes) = ex_code(bits, &vector);
+            for (&x, &code) in vector.iter().zip(codes.iter()) {
+                assert!((code as u32) < 1 << bits);
+                assert_eq!(code >> (bits - 1) == 1, x.is_sign_positive());
+            }
+        }
+    }
+}
@@ -18,6 +18,10 @@ use base::search::RerankerPop;
 use base::search::RerankerPush;
 use base::search::Vectors;
 use base::vector::*;
+use common::sample::sample;
+use common::vec2::Vec2;
+use half::f16;
+use k_means::kmeans1d::kmeans1d;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use serde::Deserialize;
@@ -34,6 +38,9 @@ pub struct ScalarQuantizer<O: OperatorScalarQuantization> {
     bits: u32,
     min: Vec<f32>,
     max: Vec<f32>,
+    // `2^bits` sorted levels of every dimension, if bins are not equal
+    #[serde(default)]
+    levels: Option<Vec<f32>>,
     _phantom: PhantomData<fn(O) -> O>,
 }
 
@@ -41,6 +48,7 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
     fn train(
         vector_options: VectorOptions,
         options: Option<QuantizationOptions>,
+        seed: Option<u64>,
         vectors: &(impl Vectors<O::Vector> + Sync),
         transform: impl Fn(Borrowed<'_, O>) -> O::Vector + Copy + Sync,
     ) -> Self {
@@ -52,6 +60,60 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
         let dims = vector_options.dims;
         let bits = options.bits;
         let n = vectors.len();
+        match options.codebook {
+            ScalarQuantizationCodebook::Uniform => (),
+            // percentiles of no samples are undefined, so bounds are taken as for uniform bins
+            ScalarQuantizationCodebook::Percentile if n == 0 => (),
+            ScalarQuantizationCodebook::Percentile => {
+                let samples = sample(n, 65536, dims, seed, |i| {
+                    O::Scalar::vector_to_f32(O::slice(transform(vectors.vector(i)).as_borrowed()))
+                });
+                let (min, max) = (0..dims)
+                    .into_par_iter()
+                    .map(|j| {
+                        let x = column(&samples, j as usize);
+                        let p = |q: f64| x[((x.len() - 1) as f64 * q).round() as usize];
+                        (p(0.01), p(0.99))
+                    })
+                    .unzip();
+                return Self {
+                    dims,
+                    bits,
+                    min,
+                    max,
+                    levels: None,
+                    _phantom: PhantomData,
+                };
+            }
+            ScalarQuantizationCodebook::Kmeans => {
+                // the dynamic programming of 1-D k-means takes `O(n * 2^bits)` space
+                let samples = sample(n, 4096, dims, seed, |i| {
+                    O::Scalar::vector_to_f32(O::slice(transform(vectors.vector(i)).as_borrowed()))
+                });
+                let levels = (0..dims)
+                    .into_par_iter()
+                    .map(|j| {
+                        let x = column(&samples, j as usize);
+                        let c = 1_usize << bits;
+                        let mut levels = if c < x.len() { kmeans1d(c, &x) } else { x };
+                        levels.sort_by(f32::total_cmp);
+                        let last = levels.last().copied().unwrap_or(0.0);
+                        levels.resize(c, last);
+                        levels
+                    })
+                    .collect::<Vec<_>>();
+                let min = levels.iter().map(|x| x[0]).collect();
+                let max = levels.iter().map(|x| x[x.len() - 1]).collect();
+                return Self {
+                    dims,
+                    bits,
+                    min,
+                    max,
+                    levels: Some(levels.into_iter().flatten().collect()),
+                    _phantom: PhantomData,
+                };
+            }
+        }
         let (min, max) = (0..n)
             .into_par_iter()
             .fold(
@@ -91,6 +153,7 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
             bits,
             min,
             max,
+            levels: None,
             _phantom: PhantomData,
         }
     }
@@ -98,17 +161,8 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
     fn encode(&self, vector: Borrowed<'_, O>) -> Vec<u8> {
         let dims = self.dims;
         let bits = self.bits;
-        let min = self.min.as_slice();
-        let max = self.max.as_slice();
         let code_size = (dims * bits).div_ceil(8);
-        let mut code = Vec::with_capacity(dims as usize);
-        for i in 0..dims {
-            let val = O::get(vector, i).to_f32();
-            let bas = min[i as usize];
-            let del = (max[i as usize] - min[i as usize]) / ((1 << bits) - 1) as f32;
-            let j = ((val - bas) / del).round_ties_even() as u32;
-            code.push(j.clamp(0, (1 << bits) - 1) as u8);
-        }
+        let code = self.code(vector);
         match bits {
             1 => InfiniteByteChunks::new(code.into_iter())
                 .map(merge_8)
@@ -130,20 +184,8 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
     fn fscan_encode(&self, vectors: [O::Vector; 32]) -> Vec<u8> {
         let dims = self.dims;
         let bits = self.bits;
-        let min = self.min.as_slice();
-        let max = self.max.as_slice();
         if bits == 4 {
-            let codes = vectors.map(|vector| {
-                let mut code = Vec::with_capacity(dims as usize);
-                for i in 0..dims {
-                    let val = O::get(vector.as_borrowed(), i).to_f32();
-                    let bas = min[i as usize];
-                    let del = (max[i as usize] - min[i as usize]) / ((1 << bits) - 1) as f32;
-                    let j = ((val - bas) / del).round_ties_even() as u32;
-                    code.push(j.clamp(0, (1 << bits) - 1) as u8);
-                }
-                code
-            });
+            let codes = vectors.map(|vector| self.code(vector.as_borrowed()));
             pack(dims, codes).collect()
         } else {
             Vec::new()
@@ -169,7 +211,10 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
     type Lut = Vec<f32>;
 
     fn preprocess(&self, vector: Borrowed<'_, O>) -> Self::Lut {
-        O::preprocess(self.dims, self.bits, &self.min, &self.max, vector)
+        match &self.levels {
+            Some(levels) => O::preprocess_levels(self.dims, self.bits, levels, vector),
+            None => O::preprocess(self.dims, self.bits, &self.min, &self.max, vector),
+        }
     }
 
     fn process(&self, lut: &Self::Lut, code: &[u8], _: Borrowed<'_, O>) -> Distance {
@@ -184,7 +229,13 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
     );
 
     fn fscan_preprocess(&self, vector: Borrowed<'_, O>) -> Self::FLut {
-        O::fscan_preprocess(self.dims, self.bits, &self.min, &self.max, vector)
+        match &self.levels {
+            Some(_) => {
+                let (k, b, t) = quantize::<255>(&self.preprocess(vector));
+                (self.dims, k, b, t)
+            }
+            None => O::fscan_preprocess(self.dims, self.bits, &self.min, &self.max, vector),
+        }
     }
 
     fn fscan_process(&self, flut: &Self::FLut, code: &[u8]) -> [Distance; 32] {
@@ -197,6 +248,12 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
         Vec::new()
     }
 
+    fn flat_rerank_estimates(
+        heap: &Self::FlatRerankVec,
+    ) -> Option<&[(Reverse<Distance>, AlwaysEqual<u32>)]> {
+        Some(heap)
+    }
+
     fn flat_rerank_preprocess(
         &self,
         vector: Borrowed<'_, O>,
@@ -209,14 +266,14 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
         }
     }
 
-    fn flat_rerank_break<'a, T: 'a, R>(
+    fn flat_rerank_break<'a, T: Send + 'a, R>(
         &'a self,
         heap: Vec<(Reverse<Distance>, AlwaysEqual<u32>)>,
         rerank: R,
         opts: &SearchOptions,
-    ) -> impl RerankerPop<T> + 'a
+    ) -> impl RerankerPop<T> + Send + 'a
     where
-        R: Fn(u32) -> (Distance, T) + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
     {
         WindowFlatReranker::new(heap, rerank, opts.sq_rerank_size)
     }
@@ -288,12 +345,12 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
     fn graph_rerank<'a, T, R, C>(
         &'a self,
         lut: Self::Lut,
-        locate: impl Fn(u32) -> C + 'a,
+        locate: impl Fn(u32) -> C + Send + 'a,
         rerank: R,
-    ) -> impl RerankerPush + RerankerPop<T> + 'a
+    ) -> impl RerankerPush + RerankerPop<T> + Send + 'a
     where
-        T: 'a,
-        R: Fn(u32) -> (Distance, T) + 'a,
+        T: Send + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
         C: AsRef<[u8]>,
     {
         Graph2Reranker::new(
@@ -303,9 +360,44 @@ impl<O: OperatorScalarQuantization> Quantizer<O> for ScalarQuantizer<O> {
     }
 }
 
+impl<O: OperatorScalarQuantization> ScalarQuantizer<O> {
+    fn code(&self, vector: Borrowed<'_, O>) -> Vec<u8> {
+        let dims = self.dims;
+        let bits = self.bits;
+        let mut code = Vec::with_capacity(dims as usize);
+        for i in 0..dims {
+            let val = O::get(vector, i).to_f32();
+            if let Some(levels) = &self.levels {
+                let levels = &levels[(i << bits) as usize..][..1 << bits];
+                let j = levels.partition_point(|&x| x < val);
+                if j == 0 || (j < levels.len() && levels[j] - val < val - levels[j - 1]) {
+                    code.push(j as u8);
+                } else {
+                    code.push((j - 1) as u8);
+                }
+            } else {
+                let bas = self.min[i as usize];
+                let del = (self.max[i as usize] - self.min[i as usize]) / ((1 << bits) - 1) as f32;
+                let j = ((val - bas) / del).round_ties_even() as u32;
+                code.push(j.clamp(0, (1 << bits) - 1) as u8);
+            }
+        }
+        code
+    }
+}
+
+fn column(samples: &Vec2<f32>, j: usize) -> Vec<f32> {
+    let mut x = (0..samples.shape_0())
+        .map(|i| samples[(i, j)])
+        .collect::<Vec<_>>();
+    x.sort_by(f32::total_cmp);
+    x
+}
+
 pub trait OperatorScalarQuantization: Operator {
     type Scalar: ScalarLike;
     fn get(vector: Borrowed<'_, Self>, i: u32) -> Self::Scalar;
+    fn slice(vector: Borrowed<'_, Self>) -> &[Self::Scalar];
 
     fn preprocess(
         dims: u32,
@@ -315,6 +407,12 @@ pub trait OperatorScalarQuantization: Operator {
         vector: Borrowed<'_, Self>,
     ) -> Vec<f32>;
     fn process(dims: u32, bits: u32, lut: &[f32], code: &[u8]) -> Distance;
+    fn preprocess_levels(
+        dims: u32,
+        bits: u32,
+        levels: &[f32],
+        vector: Borrowed<'_, Self>,
+    ) -> Vec<f32>;
 
     fn fscan_preprocess(
         dims: u32,
@@ -331,6 +429,9 @@ impl<S: ScalarLike> OperatorScalarQuantization for VectDot<S> {
     fn get(vector: Borrowed<'_, Self>, i: u32) -> Self::Scalar {
         vector.slice()[i as usize]
     }
+    fn slice(vector: Borrowed<'_, Self>) -> &[Self::Scalar] {
+        vector.slice()
+    }
 
     fn preprocess(
         dims: u32,
@@ -408,6 +509,21 @@ impl<S: ScalarLike> OperatorScalarQuantization for VectDot<S> {
         }
     }
 
+    fn preprocess_levels(
+        dims: u32,
+        bits: u32,
+        levels: &[f32],
+        vector: Borrowed<'_, Self>,
+    ) -> Vec<f32> {
+        assert!(levels.len() == (dims as usize) << bits);
+        let vector = vector.slice();
+        levels
+            .iter()
+            .enumerate()
+            .map(|(k, y)| vector[k >> bits].to_f32() * y)
+            .collect()
+    }
+
     fn fscan_preprocess(
         dims: u32,
         bits: u32,
@@ -430,6 +546,9 @@ impl<S: ScalarLike> OperatorScalarQuantization for VectL2<S> {
     fn get(vector: Borrowed<'_, Self>, i: u32) -> Self::Scalar {
         vector.slice()[i as usize]
     }
+    fn slice(vector: Borrowed<'_, Self>) -> &[Self::Scalar] {
+        vector.slice()
+    }
 
     fn preprocess(
         dims: u32,
@@ -507,6 +626,24 @@ impl<S: ScalarLike> OperatorScalarQuantization for VectL2<S> {
         }
     }
 
+    fn preprocess_levels(
+        dims: u32,
+        bits: u32,
+        levels: &[f32],
+        vector: Borrowed<'_, Self>,
+    ) -> Vec<f32> {
+        assert!(levels.len() == (dims as usize) << bits);
+        let vector = vector.slice();
+        levels
+            .iter()
+            .enumerate()
+            .map(|(k, y)| {
+                let x = vector[k >> bits].to_f32();
+                (x - y) * (x - y)
+            })
+            .collect()
+    }
+
     fn fscan_preprocess(
         dims: u32,
         bits: u32,
@@ -531,6 +668,9 @@ macro_rules! unimpl_operator_scalar_quantization {
             fn get(_: Borrowed<'_, Self>, _: u32) -> Self::Scalar {
                 unimplemented!()
             }
+            fn slice(_: Borrowed<'_, Self>) -> &[Self::Scalar] {
+                unimplemented!()
+            }
 
             fn preprocess(_: u32, _: u32, _: &[f32], _: &[f32], _: Borrowed<'_, Self>) -> Vec<f32> {
                 unimplemented!()
@@ -538,6 +678,9 @@ macro_rules! unimpl_operator_scalar_quantization {
             fn process(_: u32, _: u32, _: &[f32], _: &[u8]) -> Distance {
                 unimplemented!()
             }
+            fn preprocess_levels(_: u32, _: u32, _: &[f32], _: Borrowed<'_, Self>) -> Vec<f32> {
+                unimplemented!()
+            }
 
             fn fscan_preprocess(
                 _: u32,
@@ -561,3 +704,5 @@ unimpl_operator_scalar_quantization!(BVectorJaccard);
 
 unimpl_operator_scalar_quantization!(SVectDot<f32>);
 unimpl_operator_scalar_quantization!(SVectL2<f32>);
+unimpl_operator_scalar_quantization!(SVectDot<f16>);
+unimpl_operator_scalar_quantization!(SVectL2<f16>);
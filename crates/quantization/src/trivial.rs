@@ -24,6 +24,7 @@ impl<O: Operator> Quantizer<O> for TrivialQuantizer<O> {
     fn train(
         _: VectorOptions,
         _: Option<QuantizationOptions>,
+        _: Option<u64>,
         _: &impl Vectors<O::Vector>,
         _: impl Fn(Borrowed<'_, O>) -> O::Vector + Copy,
     ) -> Self {
@@ -78,6 +79,12 @@ impl<O: Operator> Quantizer<O> for TrivialQuantizer<O> {
         Vec::new()
     }
 
+    fn flat_rerank_estimates(
+        _: &Self::FlatRerankVec,
+    ) -> Option<&[(Reverse<Distance>, AlwaysEqual<u32>)]> {
+        None
+    }
+
     fn flat_rerank_preprocess(
         &self,
         vector: Borrowed<'_, O>,
@@ -99,14 +106,14 @@ impl<O: Operator> Quantizer<O> for TrivialQuantizer<O> {
         heap.extend(range);
     }
 
-    fn flat_rerank_break<'a, T: 'a, R>(
+    fn flat_rerank_break<'a, T: Send + 'a, R>(
         &'a self,
         heap: Vec<u32>,
         rerank: R,
         _: &SearchOptions,
-    ) -> impl RerankerPop<T> + 'a
+    ) -> impl RerankerPop<T> + Send + 'a
     where
-        R: Fn(u32) -> (Distance, T) + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
     {
         heap.into_iter()
             .map(|u| {
@@ -119,12 +126,12 @@ impl<O: Operator> Quantizer<O> for TrivialQuantizer<O> {
     fn graph_rerank<'a, T, R, C>(
         &'a self,
         _: Self::Lut,
-        _: impl Fn(u32) -> C + 'a,
+        _: impl Fn(u32) -> C + Send + 'a,
         rerank: R,
-    ) -> impl RerankerPush + RerankerPop<T> + 'a
+    ) -> impl RerankerPush + RerankerPop<T> + Send + 'a
     where
-        T: 'a,
-        R: Fn(u32) -> (Distance, T) + 'a,
+        T: Send + 'a,
+        R: Fn(u32) -> (Distance, T) + Send + 'a,
         C: AsRef<[u8]>,
     {
         GraphReranker::new(rerank)
@@ -30,3 +30,58 @@ pub fn merge_4([b0, b1, b2, b3]: [u8; 4]) -> u8 {
 pub fn merge_2([b0, b1]: [u8; 2]) -> u8 {
     b0 | (b1 << 4)
 }
+
+/// Rows of a random orthogonal matrix, taken from the QR decomposition of a
+/// Gaussian matrix.
+pub fn random_orthogonal_matrix(dims: u32, rng: &mut impl rand::Rng) -> Vec<Vec<f32>> {
+    use nalgebra::{DMatrix, QR};
+    use rand_distr::StandardNormal;
+    let matrix = DMatrix::from_fn(dims as usize, dims as usize, |_, _| {
+        rng.sample(StandardNormal)
+    });
+    let qr = QR::new(matrix);
+    let q = qr.q();
+    q.row_iter()
+        .map(|v| v.iter().copied().collect::<Vec<_>>())
+        .collect()
+}
+
+/// `rows` orthonormal rows of length `dims`, taken from the QR decomposition of
+/// a `dims` by `rows` Gaussian matrix.
+pub fn random_orthonormal_rows(dims: u32, rows: u32, rng: &mut impl rand::Rng) -> Vec<Vec<f32>> {
+    use nalgebra::{DMatrix, QR};
+    use rand_distr::StandardNormal;
+    assert!(rows <= dims);
+    let matrix = DMatrix::from_fn(dims as usize, rows as usize, |_, _| {
+        rng.sample(StandardNormal)
+    });
+    let qr = QR::new(matrix);
+    let q = qr.q();
+    q.column_iter()
+        .map(|v| v.iter().copied().collect::<Vec<_>>())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn orthonormal_rows() {
+        let mut rng = base::rand::rng(Some(7));
+        let rows = random_orthonormal_rows(64, 8, &mut rng);
+        assert_eq!(rows.len(), 8);
+        for i in 0..8 {
+            assert_eq!(rows[i].len(), 64);
+            for j in 0..8 {
+                let dot = rows[i]
+                    .iter()
+                    .zip(&rows[j])
+                    .map(|(x, y)| x * y)
+                    .sum::<f32>();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot - expected).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -4,7 +4,7 @@ use base::operator::*;
 use base::search::*;
 use base::vector::*;
 use base::worker::*;
-use half::f16;
+use half::{bf16, f16};
 use index::Index;
 use index::IndexTracker;
 use index::IndexView;
@@ -18,8 +18,12 @@ pub enum Instance {
     Vecf32L2(Arc<Index<VectL2<f32>>>),
     Vecf16Dot(Arc<Index<VectDot<f16>>>),
     Vecf16L2(Arc<Index<VectL2<f16>>>),
+    Vecbf16Dot(Arc<Index<VectDot<bf16>>>),
+    Vecbf16L2(Arc<Index<VectL2<bf16>>>),
     SVecf32Dot(Arc<Index<SVectDot<f32>>>),
     SVecf32L2(Arc<Index<SVectL2<f32>>>),
+    SVecf16Dot(Arc<Index<SVectDot<f16>>>),
+    SVecf16L2(Arc<Index<SVectL2<f16>>>),
     BVectorDot(Arc<Index<BVectorDot>>),
     BVectorHamming(Arc<Index<BVectorHamming>>),
     BVectorJaccard(Arc<Index<BVectorJaccard>>),
@@ -48,6 +52,14 @@ impl Instance {
                 let index = Index::create(path.clone(), options, alterable_options)?;
                 Ok(Self::Vecf16L2(index))
             }
+            (VectorKind::Vecbf16, DistanceKind::Dot) => {
+                let index = Index::create(path.clone(), options, alterable_options)?;
+                Ok(Self::Vecbf16Dot(index))
+            }
+            (VectorKind::Vecbf16, DistanceKind::L2) => {
+                let index = Index::create(path.clone(), options, alterable_options)?;
+                Ok(Self::Vecbf16L2(index))
+            }
             (VectorKind::SVecf32, DistanceKind::Dot) => {
                 let index = Index::create(path.clone(), options, alterable_options)?;
                 Ok(Self::SVecf32Dot(index))
@@ -56,6 +68,14 @@ impl Instance {
                 let index = Index::create(path.clone(), options, alterable_options)?;
                 Ok(Self::SVecf32L2(index))
             }
+            (VectorKind::SVecf16, DistanceKind::Dot) => {
+                let index = Index::create(path.clone(), options, alterable_options)?;
+                Ok(Self::SVecf16Dot(index))
+            }
+            (VectorKind::SVecf16, DistanceKind::L2) => {
+                let index = Index::create(path.clone(), options, alterable_options)?;
+                Ok(Self::SVecf16L2(index))
+            }
             (VectorKind::BVector, DistanceKind::Dot) => {
                 let index = Index::create(path.clone(), options, alterable_options)?;
                 Ok(Self::BVectorDot(index))
@@ -82,8 +102,12 @@ impl Instance {
             (VectorKind::Vecf32, DistanceKind::L2) => Self::Vecf32L2(Index::open(path)),
             (VectorKind::Vecf16, DistanceKind::Dot) => Self::Vecf16Dot(Index::open(path)),
             (VectorKind::Vecf16, DistanceKind::L2) => Self::Vecf16L2(Index::open(path)),
+            (VectorKind::Vecbf16, DistanceKind::Dot) => Self::Vecbf16Dot(Index::open(path)),
+            (VectorKind::Vecbf16, DistanceKind::L2) => Self::Vecbf16L2(Index::open(path)),
             (VectorKind::SVecf32, DistanceKind::Dot) => Self::SVecf32Dot(Index::open(path)),
             (VectorKind::SVecf32, DistanceKind::L2) => Self::SVecf32L2(Index::open(path)),
+            (VectorKind::SVecf16, DistanceKind::Dot) => Self::SVecf16Dot(Index::open(path)),
+            (VectorKind::SVecf16, DistanceKind::L2) => Self::SVecf16L2(Index::open(path)),
             (VectorKind::BVector, DistanceKind::Dot) => Self::BVectorDot(Index::open(path)),
             (VectorKind::BVector, DistanceKind::Hamming) => Self::BVectorHamming(Index::open(path)),
             (VectorKind::BVector, DistanceKind::Jaccard) => Self::BVectorJaccard(Index::open(path)),
@@ -96,8 +120,12 @@ impl Instance {
             Instance::Vecf32L2(x) => x.refresh(),
             Instance::Vecf16Dot(x) => x.refresh(),
             Instance::Vecf16L2(x) => x.refresh(),
+            Instance::Vecbf16Dot(x) => x.refresh(),
+            Instance::Vecbf16L2(x) => x.refresh(),
             Instance::SVecf32Dot(x) => x.refresh(),
             Instance::SVecf32L2(x) => x.refresh(),
+            Instance::SVecf16Dot(x) => x.refresh(),
+            Instance::SVecf16L2(x) => x.refresh(),
             Instance::BVectorDot(x) => x.refresh(),
             Instance::BVectorHamming(x) => x.refresh(),
             Instance::BVectorJaccard(x) => x.refresh(),
@@ -109,8 +137,12 @@ impl Instance {
             Instance::Vecf32L2(x) => InstanceView::Vecf32L2(x.view()),
             Instance::Vecf16Dot(x) => InstanceView::Vecf16Dot(x.view()),
             Instance::Vecf16L2(x) => InstanceView::Vecf16L2(x.view()),
+            Instance::Vecbf16Dot(x) => InstanceView::Vecbf16Dot(x.view()),
+            Instance::Vecbf16L2(x) => InstanceView::Vecbf16L2(x.view()),
             Instance::SVecf32Dot(x) => InstanceView::SVecf32Dot(x.view()),
             Instance::SVecf32L2(x) => InstanceView::SVecf32L2(x.view()),
+            Instance::SVecf16Dot(x) => InstanceView::SVecf16Dot(x.view()),
+            Instance::SVecf16L2(x) => InstanceView::SVecf16L2(x.view()),
             Instance::BVectorDot(x) => InstanceView::BVectorDot(x.view()),
             Instance::BVectorHamming(x) => InstanceView::BVectorHamming(x.view()),
             Instance::BVectorJaccard(x) => InstanceView::BVectorJaccard(x.view()),
@@ -122,8 +154,12 @@ impl Instance {
             Instance::Vecf32L2(x) => x.stat(),
             Instance::Vecf16Dot(x) => x.stat(),
             Instance::Vecf16L2(x) => x.stat(),
+            Instance::Vecbf16Dot(x) => x.stat(),
+            Instance::Vecbf16L2(x) => x.stat(),
             Instance::SVecf32Dot(x) => x.stat(),
             Instance::SVecf32L2(x) => x.stat(),
+            Instance::SVecf16Dot(x) => x.stat(),
+            Instance::SVecf16L2(x) => x.stat(),
             Instance::BVectorDot(x) => x.stat(),
             Instance::BVectorHamming(x) => x.stat(),
             Instance::BVectorJaccard(x) => x.stat(),
@@ -135,8 +171,12 @@ impl Instance {
             Instance::Vecf32L2(x) => x.alter(key, value),
             Instance::Vecf16Dot(x) => x.alter(key, value),
             Instance::Vecf16L2(x) => x.alter(key, value),
+            Instance::Vecbf16Dot(x) => x.alter(key, value),
+            Instance::Vecbf16L2(x) => x.alter(key, value),
             Instance::SVecf32Dot(x) => x.alter(key, value),
             Instance::SVecf32L2(x) => x.alter(key, value),
+            Instance::SVecf16Dot(x) => x.alter(key, value),
+            Instance::SVecf16L2(x) => x.alter(key, value),
             Instance::BVectorDot(x) => x.alter(key, value),
             Instance::BVectorHamming(x) => x.alter(key, value),
             Instance::BVectorJaccard(x) => x.alter(key, value),
@@ -148,8 +188,12 @@ impl Instance {
             Instance::Vecf32L2(x) => x.delete(pointer),
             Instance::Vecf16Dot(x) => x.delete(pointer),
             Instance::Vecf16L2(x) => x.delete(pointer),
+            Instance::Vecbf16Dot(x) => x.delete(pointer),
+            Instance::Vecbf16L2(x) => x.delete(pointer),
             Instance::SVecf32Dot(x) => x.delete(pointer),
             Instance::SVecf32L2(x) => x.delete(pointer),
+            Instance::SVecf16Dot(x) => x.delete(pointer),
+            Instance::SVecf16L2(x) => x.delete(pointer),
             Instance::BVectorDot(x) => x.delete(pointer),
             Instance::BVectorHamming(x) => x.delete(pointer),
             Instance::BVectorJaccard(x) => x.delete(pointer),
@@ -161,8 +205,12 @@ impl Instance {
             Instance::Vecf32L2(x) => x.start(),
             Instance::Vecf16Dot(x) => x.start(),
             Instance::Vecf16L2(x) => x.start(),
+            Instance::Vecbf16Dot(x) => x.start(),
+            Instance::Vecbf16L2(x) => x.start(),
             Instance::SVecf32Dot(x) => x.start(),
             Instance::SVecf32L2(x) => x.start(),
+            Instance::SVecf16Dot(x) => x.start(),
+            Instance::SVecf16L2(x) => x.start(),
             Instance::BVectorDot(x) => x.start(),
             Instance::BVectorHamming(x) => x.start(),
             Instance::BVectorJaccard(x) => x.start(),
@@ -174,8 +222,12 @@ impl Instance {
             Instance::Vecf32L2(x) => x.stop(),
             Instance::Vecf16Dot(x) => x.stop(),
             Instance::Vecf16L2(x) => x.stop(),
+            Instance::Vecbf16Dot(x) => x.stop(),
+            Instance::Vecbf16L2(x) => x.stop(),
             Instance::SVecf32Dot(x) => x.stop(),
             Instance::SVecf32L2(x) => x.stop(),
+            Instance::SVecf16Dot(x) => x.stop(),
+            Instance::SVecf16L2(x) => x.stop(),
             Instance::BVectorDot(x) => x.stop(),
             Instance::BVectorHamming(x) => x.stop(),
             Instance::BVectorJaccard(x) => x.stop(),
@@ -187,8 +239,12 @@ impl Instance {
             Instance::Vecf32L2(x) => x.wait(),
             Instance::Vecf16Dot(x) => x.wait(),
             Instance::Vecf16L2(x) => x.wait(),
+            Instance::Vecbf16Dot(x) => x.wait(),
+            Instance::Vecbf16L2(x) => x.wait(),
             Instance::SVecf32Dot(x) => x.wait(),
             Instance::SVecf32L2(x) => x.wait(),
+            Instance::SVecf16Dot(x) => x.wait(),
+            Instance::SVecf16L2(x) => x.wait(),
             Instance::BVectorDot(x) => x.wait(),
             Instance::BVectorHamming(x) => x.wait(),
             Instance::BVectorJaccard(x) => x.wait(),
@@ -201,14 +257,56 @@ pub enum InstanceView {
     Vecf32L2(Arc<IndexView<VectL2<f32>>>),
     Vecf16Dot(Arc<IndexView<VectDot<f16>>>),
     Vecf16L2(Arc<IndexView<VectL2<f16>>>),
+    Vecbf16Dot(Arc<IndexView<VectDot<bf16>>>),
+    Vecbf16L2(Arc<IndexView<VectL2<bf16>>>),
     SVecf32Dot(Arc<IndexView<SVectDot<f32>>>),
     SVecf32L2(Arc<IndexView<SVectL2<f32>>>),
+    SVecf16Dot(Arc<IndexView<SVectDot<f16>>>),
+    SVecf16L2(Arc<IndexView<SVectL2<f16>>>),
     BVectorDot(Arc<IndexView<BVectorDot>>),
     BVectorHamming(Arc<IndexView<BVectorHamming>>),
     BVectorJaccard(Arc<IndexView<BVectorJaccard>>),
 }
 
 impl ViewVbaseOperations for InstanceView {
+    fn partition(self, keys: &[u64]) -> Self {
+        match self {
+            InstanceView::Vecf32Dot(x) => InstanceView::Vecf32Dot(Arc::new(x.partition(keys))),
+            InstanceView::Vecf32L2(x) => InstanceView::Vecf32L2(Arc::new(x.partition(keys))),
+            InstanceView::Vecf16Dot(x) => InstanceView::Vecf16Dot(Arc::new(x.partition(keys))),
+            InstanceView::Vecf16L2(x) => InstanceView::Vecf16L2(Arc::new(x.partition(keys))),
+            InstanceView::Vecbf16Dot(x) => InstanceView::Vecbf16Dot(Arc::new(x.partition(keys))),
+            InstanceView::Vecbf16L2(x) => InstanceView::Vecbf16L2(Arc::new(x.partition(keys))),
+            InstanceView::SVecf32Dot(x) => InstanceView::SVecf32Dot(Arc::new(x.partition(keys))),
+            InstanceView::SVecf32L2(x) => InstanceView::SVecf32L2(Arc::new(x.partition(keys))),
+            InstanceView::SVecf16Dot(x) => InstanceView::SVecf16Dot(Arc::new(x.partition(keys))),
+            InstanceView::SVecf16L2(x) => InstanceView::SVecf16L2(Arc::new(x.partition(keys))),
+            InstanceView::BVectorDot(x) => InstanceView::BVectorDot(Arc::new(x.partition(keys))),
+            InstanceView::BVectorHamming(x) => {
+                InstanceView::BVectorHamming(Arc::new(x.partition(keys)))
+            }
+            InstanceView::BVectorJaccard(x) => {
+                InstanceView::BVectorJaccard(Arc::new(x.partition(keys)))
+            }
+        }
+    }
+    fn search_options(&self, opts: &PartialSearchOptions) -> SearchOptions {
+        match self {
+            InstanceView::Vecf32Dot(x) => x.search_options(opts),
+            InstanceView::Vecf32L2(x) => x.search_options(opts),
+            InstanceView::Vecf16Dot(x) => x.search_options(opts),
+            InstanceView::Vecf16L2(x) => x.search_options(opts),
+            InstanceView::Vecbf16Dot(x) => x.search_options(opts),
+            InstanceView::Vecbf16L2(x) => x.search_options(opts),
+            InstanceView::SVecf32Dot(x) => x.search_options(opts),
+            InstanceView::SVecf32L2(x) => x.search_options(opts),
+            InstanceView::SVecf16Dot(x) => x.search_options(opts),
+            InstanceView::SVecf16L2(x) => x.search_options(opts),
+            InstanceView::BVectorDot(x) => x.search_options(opts),
+            InstanceView::BVectorHamming(x) => x.search_options(opts),
+            InstanceView::BVectorJaccard(x) => x.search_options(opts),
+        }
+    }
     fn vbase<'a>(
         &'a self,
         vector: &'a OwnedVector,
@@ -227,12 +325,24 @@ impl ViewVbaseOperations for InstanceView {
             (InstanceView::Vecf16L2(x), OwnedVector::Vecf16(vector)) => {
                 Ok(Box::new(x.vbase(vector.as_borrowed(), opts)?))
             }
+            (InstanceView::Vecbf16Dot(x), OwnedVector::Vecbf16(vector)) => {
+                Ok(Box::new(x.vbase(vector.as_borrowed(), opts)?))
+            }
+            (InstanceView::Vecbf16L2(x), OwnedVector::Vecbf16(vector)) => {
+                Ok(Box::new(x.vbase(vector.as_borrowed(), opts)?))
+            }
             (InstanceView::SVecf32Dot(x), OwnedVector::SVecf32(vector)) => {
                 Ok(Box::new(x.vbase(vector.as_borrowed(), opts)?))
             }
             (InstanceView::SVecf32L2(x), OwnedVector::SVecf32(vector)) => {
                 Ok(Box::new(x.vbase(vector.as_borrowed(), opts)?))
             }
+            (InstanceView::SVecf16Dot(x), OwnedVector::SVecf16(vector)) => {
+                Ok(Box::new(x.vbase(vector.as_borrowed(), opts)?))
+            }
+            (InstanceView::SVecf16L2(x), OwnedVector::SVecf16(vector)) => {
+                Ok(Box::new(x.vbase(vector.as_borrowed(), opts)?))
+            }
             (InstanceView::BVectorDot(x), OwnedVector::BVector(vector)) => {
                 Ok(Box::new(x.vbase(vector.as_borrowed(), opts)?))
             }
@@ -245,6 +355,176 @@ impl ViewVbaseOperations for InstanceView {
             _ => Err(VbaseError::InvalidVector),
         }
     }
+    fn vbase_vector<'a>(
+        &'a self,
+        vector: &'a OwnedVector,
+        opts: &'a SearchOptions,
+    ) -> Result<Box<dyn Iterator<Item = (Distance, Pointer, OwnedVector)> + 'a>, VbaseError> {
+        match (self, vector) {
+            (InstanceView::Vecf32Dot(x), OwnedVector::Vecf32(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::Vecf32(v))),
+            )),
+            (InstanceView::Vecf32L2(x), OwnedVector::Vecf32(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::Vecf32(v))),
+            )),
+            (InstanceView::Vecf16Dot(x), OwnedVector::Vecf16(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::Vecf16(v))),
+            )),
+            (InstanceView::Vecf16L2(x), OwnedVector::Vecf16(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::Vecf16(v))),
+            )),
+            (InstanceView::Vecbf16Dot(x), OwnedVector::Vecbf16(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::Vecbf16(v))),
+            )),
+            (InstanceView::Vecbf16L2(x), OwnedVector::Vecbf16(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::Vecbf16(v))),
+            )),
+            (InstanceView::SVecf32Dot(x), OwnedVector::SVecf32(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::SVecf32(v))),
+            )),
+            (InstanceView::SVecf32L2(x), OwnedVector::SVecf32(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::SVecf32(v))),
+            )),
+            (InstanceView::SVecf16Dot(x), OwnedVector::SVecf16(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::SVecf16(v))),
+            )),
+            (InstanceView::SVecf16L2(x), OwnedVector::SVecf16(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::SVecf16(v))),
+            )),
+            (InstanceView::BVectorDot(x), OwnedVector::BVector(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::BVector(v))),
+            )),
+            (InstanceView::BVectorHamming(x), OwnedVector::BVector(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::BVector(v))),
+            )),
+            (InstanceView::BVectorJaccard(x), OwnedVector::BVector(vector)) => Ok(Box::new(
+                x.vbase_vector(vector.as_borrowed(), opts)?
+                    .map(|(d, p, v)| (d, p, OwnedVector::BVector(v))),
+            )),
+            _ => Err(VbaseError::InvalidVector),
+        }
+    }
+    fn vbase_multi<'a>(
+        &'a self,
+        vectors: &'a [OwnedVector],
+        opts: &'a SearchOptions,
+    ) -> Result<Box<dyn Iterator<Item = (Distance, Pointer)> + 'a>, VbaseError> {
+        match self {
+            InstanceView::Vecf32Dot(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::Vecf32(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::Vecf32L2(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::Vecf32(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::Vecf16Dot(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::Vecf16(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::Vecf16L2(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::Vecf16(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::Vecbf16Dot(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::Vecbf16(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::Vecbf16L2(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::Vecbf16(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::SVecf32Dot(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::SVecf32(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::SVecf32L2(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::SVecf32(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::SVecf16Dot(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::SVecf16(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::SVecf16L2(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::SVecf16(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::BVectorDot(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::BVector(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::BVectorHamming(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::BVector(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+            InstanceView::BVectorJaccard(x) => {
+                let vectors = borrow_all(vectors, |v| match v {
+                    OwnedVector::BVector(v) => Some(v.as_borrowed()),
+                    _ => None,
+                })?;
+                Ok(Box::new(x.vbase_multi(&vectors, opts)?.into_iter()))
+            }
+        }
+    }
+}
+
+fn borrow_all<'a, T>(
+    vectors: &'a [OwnedVector],
+    f: impl Fn(&'a OwnedVector) -> Option<T>,
+) -> Result<Vec<T>, VbaseError> {
+    vectors
+        .iter()
+        .map(|vector| f(vector).ok_or(VbaseError::InvalidVector))
+        .collect()
 }
 
 impl ViewListOperations for InstanceView {
@@ -254,8 +534,12 @@ impl ViewListOperations for InstanceView {
             InstanceView::Vecf32L2(x) => Ok(Box::new(x.list()?)),
             InstanceView::Vecf16Dot(x) => Ok(Box::new(x.list()?)),
             InstanceView::Vecf16L2(x) => Ok(Box::new(x.list()?)),
+            InstanceView::Vecbf16Dot(x) => Ok(Box::new(x.list()?)),
+            InstanceView::Vecbf16L2(x) => Ok(Box::new(x.list()?)),
             InstanceView::SVecf32Dot(x) => Ok(Box::new(x.list()?)),
             InstanceView::SVecf32L2(x) => Ok(Box::new(x.list()?)),
+            InstanceView::SVecf16Dot(x) => Ok(Box::new(x.list()?)),
+            InstanceView::SVecf16L2(x) => Ok(Box::new(x.list()?)),
             InstanceView::BVectorDot(x) => Ok(Box::new(x.list()?)),
             InstanceView::BVectorHamming(x) => Ok(Box::new(x.list()?)),
             InstanceView::BVectorJaccard(x) => Ok(Box::new(x.list()?)),
@@ -263,29 +547,76 @@ impl ViewListOperations for InstanceView {
     }
 }
 
+impl ViewTuneOperations for InstanceView {
+    fn tune<'a>(
+        &'a self,
+        opts: &PartialSearchOptions,
+        tune: &TuneOptions,
+    ) -> Result<Box<dyn Iterator<Item = Result<TuneTrial, TuneError>> + 'a>, TuneError> {
+        match self {
+            InstanceView::Vecf32Dot(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::Vecf32L2(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::Vecf16Dot(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::Vecf16L2(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::Vecbf16Dot(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::Vecbf16L2(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::SVecf32Dot(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::SVecf32L2(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::SVecf16Dot(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::SVecf16L2(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::BVectorDot(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::BVectorHamming(x) => Ok(Box::new(x.tune(opts, tune)?)),
+            InstanceView::BVectorJaccard(x) => Ok(Box::new(x.tune(opts, tune)?)),
+        }
+    }
+}
+
 impl InstanceView {
     pub fn insert(
         &self,
         vector: OwnedVector,
         pointer: Pointer,
+        partition: Option<u64>,
     ) -> Result<Result<(), OutdatedError>, InsertError> {
         match (self, vector) {
-            (InstanceView::Vecf32Dot(x), OwnedVector::Vecf32(vector)) => x.insert(vector, pointer),
-            (InstanceView::Vecf32L2(x), OwnedVector::Vecf32(vector)) => x.insert(vector, pointer),
-            (InstanceView::Vecf16Dot(x), OwnedVector::Vecf16(vector)) => x.insert(vector, pointer),
-            (InstanceView::Vecf16L2(x), OwnedVector::Vecf16(vector)) => x.insert(vector, pointer),
+            (InstanceView::Vecf32Dot(x), OwnedVector::Vecf32(vector)) => {
+                x.insert(vector, pointer, partition)
+            }
+            (InstanceView::Vecf32L2(x), OwnedVector::Vecf32(vector)) => {
+                x.insert(vector, pointer, partition)
+            }
+            (InstanceView::Vecf16Dot(x), OwnedVector::Vecf16(vector)) => {
+                x.insert(vector, pointer, partition)
+            }
+            (InstanceView::Vecf16L2(x), OwnedVector::Vecf16(vector)) => {
+                x.insert(vector, pointer, partition)
+            }
+            (InstanceView::Vecbf16Dot(x), OwnedVector::Vecbf16(vector)) => {
+                x.insert(vector, pointer, partition)
+            }
+            (InstanceView::Vecbf16L2(x), OwnedVector::Vecbf16(vector)) => {
+                x.insert(vector, pointer, partition)
+            }
             (InstanceView::SVecf32Dot(x), OwnedVector::SVecf32(vector)) => {
-                x.insert(vector, pointer)
+                x.insert(vector, pointer, partition)
+            }
+            (InstanceView::SVecf32L2(x), OwnedVector::SVecf32(vector)) => {
+                x.insert(vector, pointer, partition)
+            }
+            (InstanceView::SVecf16Dot(x), OwnedVector::SVecf16(vector)) => {
+                x.insert(vector, pointer, partition)
+            }
+            (InstanceView::SVecf16L2(x), OwnedVector::SVecf16(vector)) => {
+                x.insert(vector, pointer, partition)
             }
-            (InstanceView::SVecf32L2(x), OwnedVector::SVecf32(vector)) => x.insert(vector, pointer),
             (InstanceView::BVectorDot(x), OwnedVector::BVector(vector)) => {
-                x.insert(vector, pointer)
+                x.insert(vector, pointer, partition)
             }
             (InstanceView::BVectorHamming(x), OwnedVector::BVector(vector)) => {
-                x.insert(vector, pointer)
+                x.insert(vector, pointer, partition)
             }
             (InstanceView::BVectorJaccard(x), OwnedVector::BVector(vector)) => {
-                x.insert(vector, pointer)
+                x.insert(vector, pointer, partition)
             }
             _ => Err(InsertError::InvalidVector),
         }
@@ -296,8 +627,12 @@ impl InstanceView {
             InstanceView::Vecf32L2(x) => x.flush(),
             InstanceView::Vecf16Dot(x) => x.flush(),
             InstanceView::Vecf16L2(x) => x.flush(),
+            InstanceView::Vecbf16Dot(x) => x.flush(),
+            InstanceView::Vecbf16L2(x) => x.flush(),
             InstanceView::SVecf32Dot(x) => x.flush(),
             InstanceView::SVecf32L2(x) => x.flush(),
+            InstanceView::SVecf16Dot(x) => x.flush(),
+            InstanceView::SVecf16L2(x) => x.flush(),
             InstanceView::BVectorDot(x) => x.flush(),
             InstanceView::BVectorHamming(x) => x.flush(),
             InstanceView::BVectorJaccard(x) => x.flush(),
@@ -147,12 +147,13 @@ impl WorkerOperations for Worker {
         handle: Handle,
         vector: OwnedVector,
         pointer: Pointer,
+        partition: Option<u64>,
     ) -> Result<(), InsertError> {
         let view = self.view();
         let instance = view.get(handle).ok_or(InsertError::NotExist)?;
         loop {
             let view = instance.view();
-            match view.insert(vector.clone(), pointer)? {
+            match view.insert(vector.clone(), pointer, partition)? {
                 Ok(()) => break,
                 Err(OutdatedError) => {
                     instance.refresh();
@@ -188,6 +189,11 @@ impl WorkerOperations for Worker {
         let instance = view.get(handle).ok_or(AlterError::NotExist)?;
         instance.alter(key, value)
     }
+    fn view_tune(&self, handle: Handle) -> Result<impl ViewTuneOperations, TuneError> {
+        let view = self.view();
+        let instance = view.get(handle).ok_or(TuneError::NotExist)?;
+        Ok(instance.view())
+    }
     fn stop(&self, handle: Handle) -> Result<(), StopError> {
         let view = self.view();
         let instance = view.get(handle).ok_or(StopError::NotExist)?;
@@ -45,9 +45,9 @@ impl<O: OperatorSparseInvertedIndex> SparseInvertedIndex<O> {
 
     pub fn vbase<'a>(
         &'a self,
-        vector: Borrowed<'a, O>,
+        vector: Borrowed<'_, O>,
         _: &'a SearchOptions,
-    ) -> Box<dyn Iterator<Item = Element> + 'a> {
+    ) -> Box<dyn Iterator<Item = Element> + Send + 'a> {
         let mut doc_score = vec![ZERO; self.payloads.len()];
         for (token, val) in O::to_index_vec(vector) {
             let start = self.offsets[token as usize];
@@ -66,6 +66,7 @@ impl<O: OperatorSparseInvertedIndex> SparseInvertedIndex<O> {
             candidates.pop().map(|(distance, AlwaysEqual(u))| Element {
                 distance,
                 payload: AlwaysEqual(self.payload(u)),
+                key: AlwaysEqual(u),
             })
         }))
     }
@@ -5,6 +5,10 @@ use service::Worker;
 use std::convert::Infallible;
 use std::sync::Arc;
 
+// Pointers sent per packet for a range scan, so that a reply never exceeds
+// the buffer of the transport.
+const RANGE_BATCH: usize = 4096;
+
 pub fn normal(worker: Arc<Worker>) {
     std::thread::scope(|scope| {
         scope.spawn({
@@ -89,9 +93,10 @@ fn session(worker: Arc<Worker>, handler: ServerRpcHandler) -> Result<Infallible,
                 handle,
                 vector,
                 pointer,
+                partition,
                 x,
             } => {
-                handler = x.leave(worker.insert(handle, vector, pointer))?;
+                handler = x.leave(worker.insert(handle, vector, pointer, partition))?;
             }
             ServerRpcHandle::Delete { handle, pointer, x } => {
                 handler = x.leave(worker.delete(handle, pointer))?;
@@ -107,10 +112,44 @@ fn session(worker: Arc<Worker>, handler: ServerRpcHandler) -> Result<Infallible,
             } => {
                 handler = x.leave(worker.alter(handle, &key, &value))?;
             }
+            ServerRpcHandle::Tune {
+                handle,
+                opts,
+                tune,
+                x,
+            } => {
+                let v = match worker.view_tune(handle) {
+                    Ok(x) => x,
+                    Err(e) => {
+                        handler = x.error_err(e)?;
+                        continue;
+                    }
+                };
+                match v.tune(&opts, &tune) {
+                    Ok(mut iter) => {
+                        use crate::ipc::ServerTuneHandle;
+                        let mut x = x.error_ok()?;
+                        loop {
+                            match x.handle()? {
+                                ServerTuneHandle::Next { x: y } => {
+                                    x = y.leave(iter.next())?;
+                                }
+                                ServerTuneHandle::Leave { x } => {
+                                    handler = x;
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => handler = x.error_err(e)?,
+                };
+            }
             ServerRpcHandle::Vbase {
                 handle,
                 vector,
                 opts,
+                partitions,
+                fetch,
                 x,
             } => {
                 let v = match worker.view_vbase(handle) {
@@ -120,7 +159,22 @@ fn session(worker: Arc<Worker>, handler: ServerRpcHandler) -> Result<Infallible,
                         continue;
                     }
                 };
-                match v.vbase(&vector, &opts) {
+                let v = match partitions {
+                    Some(keys) => v.partition(&keys),
+                    None => v,
+                };
+                let opts = v.search_options(&opts);
+                let r = if fetch {
+                    v.vbase_vector(&vector, &opts).map(|iter| {
+                        Box::new(iter.map(|(d, p, v)| (d, p, Some(v))))
+                            as Box<dyn Iterator<Item = _>>
+                    })
+                } else {
+                    v.vbase(&vector, &opts).map(|iter| {
+                        Box::new(iter.map(|(d, p)| (d, p, None))) as Box<dyn Iterator<Item = _>>
+                    })
+                };
+                match r {
                     Ok(mut iter) => {
                         use crate::ipc::ServerVbaseHandle;
                         let mut x = x.error_ok()?;
@@ -139,6 +193,87 @@ fn session(worker: Arc<Worker>, handler: ServerRpcHandler) -> Result<Infallible,
                     Err(e) => handler = x.error_err(e)?,
                 };
             }
+            ServerRpcHandle::Range {
+                handle,
+                vector,
+                opts,
+                partitions,
+                threshold,
+                x,
+            } => {
+                let v = match worker.view_vbase(handle) {
+                    Ok(x) => x,
+                    Err(e) => {
+                        handler = x.error_err(e.into())?;
+                        continue;
+                    }
+                };
+                let v = match partitions {
+                    Some(keys) => v.partition(&keys),
+                    None => v,
+                };
+                let opts = v.search_options(&opts);
+                match v.vbase(&vector, &opts) {
+                    Ok(iter) => {
+                        use crate::ipc::ServerRangeHandle;
+                        let mut iter = iter
+                            .take_while(|&(distance, _)| distance < threshold)
+                            .map(|(_, pointer)| pointer);
+                        let mut x = x.error_ok()?;
+                        loop {
+                            match x.handle()? {
+                                ServerRangeHandle::Next { x: y } => {
+                                    let batch = iter.by_ref().take(RANGE_BATCH).collect::<Vec<_>>();
+                                    x = y.leave((!batch.is_empty()).then_some(batch))?;
+                                }
+                                ServerRangeHandle::Leave { x } => {
+                                    handler = x;
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => handler = x.error_err(e.into())?,
+                };
+            }
+            ServerRpcHandle::VbaseMulti {
+                handle,
+                vectors,
+                opts,
+                partitions,
+                x,
+            } => {
+                let v = match worker.view_vbase(handle) {
+                    Ok(x) => x,
+                    Err(e) => {
+                        handler = x.error_err(e.into())?;
+                        continue;
+                    }
+                };
+                let v = match partitions {
+                    Some(keys) => v.partition(&keys),
+                    None => v,
+                };
+                let opts = v.search_options(&opts);
+                match v.vbase_multi(&vectors, &opts) {
+                    Ok(mut iter) => {
+                        use crate::ipc::ServerVbaseMultiHandle;
+                        let mut x = x.error_ok()?;
+                        loop {
+                            match x.handle()? {
+                                ServerVbaseMultiHandle::Next { x: y } => {
+                                    x = y.leave(iter.next())?;
+                                }
+                                ServerVbaseMultiHandle::Leave { x } => {
+                                    handler = x;
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => handler = x.error_err(e.into())?,
+                };
+            }
             ServerRpcHandle::List { handle, x } => {
                 let v = match worker.view_list(handle) {
                     Ok(x) => x,
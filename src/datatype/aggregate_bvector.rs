@@ -0,0 +1,150 @@
+use crate::datatype::aggregate_vecf32::{deserialize_stype, serialize_stype};
+use crate::datatype::memory_bvector::{BVectorInput, BVectorOutput};
+use crate::error::*;
+use base::vector::*;
+use pgrx::datum::Internal;
+use serde::{Deserialize, Serialize};
+
+#[repr(C, align(8))]
+#[derive(Serialize, Deserialize)]
+pub struct BVectorAggregateMajorityStype {
+    dims: u32,
+    count: u64,
+    ones: Vec<u64>,
+}
+
+impl BVectorAggregateMajorityStype {
+    pub fn dims(&self) -> u32 {
+        self.dims
+    }
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl BVectorAggregateMajorityStype {
+    pub fn new(dims: u32) -> Self {
+        Self {
+            dims,
+            count: 0,
+            ones: vec![0; dims as usize],
+        }
+    }
+    /// Count the set bits of an input vector.
+    pub fn push(&mut self, vector: BVectBorrowed<'_>) {
+        for (x, y) in self.ones.iter_mut().zip(vector.iter()) {
+            *x += y as u64;
+        }
+        self.count += 1;
+    }
+    /// Merge another state into this one.
+    pub fn merge(&mut self, other: &Self) {
+        for (x, y) in self.ones.iter_mut().zip(other.ones.iter()) {
+            *x += *y;
+        }
+        self.count += other.count;
+    }
+    /// A bit is set if it is set in more than half of the inputs.
+    pub fn majority(&self) -> BVectOwned {
+        let mut data = vec![0_u64; self.dims.div_ceil(BVECTOR_WIDTH) as usize];
+        for (i, ones) in self.ones.iter().enumerate() {
+            if ones * 2 > self.count {
+                data[i / BVECTOR_WIDTH as usize] |= 1 << (i % BVECTOR_WIDTH as usize);
+            }
+        }
+        BVectOwned::new(self.dims, data)
+    }
+}
+
+/// accumulate intermediate state for bvector bitwise majority
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_bvector_aggregate_majority_sfunc(internal, bvector) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_bvector_aggregate_majority_sfunc(
+    current: Internal,
+    value: Option<BVectorInput<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    let Some(value) = value else { return current };
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match unsafe { current.get_mut::<BVectorAggregateMajorityStype>() } {
+        // if the state is empty, create it with the dimensions of the input vector
+        None => {
+            let mut state = BVectorAggregateMajorityStype::new(value.dims());
+            state.push(value.as_borrowed());
+            Internal::new(state)
+        }
+        Some(state) => {
+            check_matched_dims(state.dims(), value.dims());
+            state.push(value.as_borrowed());
+            current
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// combine two intermediate states for bvector bitwise majority
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_bvector_aggregate_majority_combinefunc(internal, internal) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_bvector_aggregate_majority_combinefunc(
+    state1: Internal,
+    state2: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match (
+        unsafe { state1.get_mut::<BVectorAggregateMajorityStype>() },
+        unsafe { state2.get_mut::<BVectorAggregateMajorityStype>() },
+    ) {
+        (_, None) => state1,
+        (None, Some(_)) => state2,
+        (Some(s1), Some(s2)) => {
+            check_matched_dims(s1.dims(), s2.dims());
+            s1.merge(s2);
+            state1
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// serialize the intermediate state for bvector bitwise majority
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_bvector_aggregate_majority_serialfunc(internal) RETURNS bytea IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_bvector_aggregate_majority_serialfunc(state: Internal) -> Vec<u8> {
+    serialize_stype::<BVectorAggregateMajorityStype>(state)
+}
+
+/// deserialize the intermediate state for bvector bitwise majority
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_bvector_aggregate_majority_deserialfunc(bytea, internal) RETURNS internal IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_bvector_aggregate_majority_deserialfunc(
+    bytes: &[u8],
+    _internal: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    deserialize_stype::<BVectorAggregateMajorityStype>(bytes, fcinfo)
+}
+
+/// finalize the intermediate state for bvector bitwise majority
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_bvector_aggregate_majority_finalfunc(state: Internal) -> Option<BVectorOutput> {
+    unsafe { state.get_mut::<BVectorAggregateMajorityStype>() }
+        .map(|state| BVectorOutput::new(state.majority().as_borrowed()))
+}
@@ -0,0 +1,185 @@
+use crate::datatype::aggregate_vecf32::*;
+use crate::datatype::memory_vecf16::{Vecf16Input, Vecf16Output};
+use crate::error::*;
+use base::vector::*;
+use half::f16;
+use pgrx::datum::Internal;
+
+// The intermediate states are accumulated in `f32` and shared with `vector`, so
+// the combine functions of `vector` aggregates are reused. A sum may exceed the
+// range of `f16`, so it is returned as `vector`.
+
+/// accumulate intermediate state for vecf16 average
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf16_aggregate_avg_sum_sfunc(internal, vecf16) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf16_aggregate_avg_sum_sfunc(
+    current: Internal,
+    value: Option<Vecf16Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    let Some(value) = value else { return current };
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match unsafe { current.get_mut::<Vecf32AggregateAvgSumStype>() } {
+        // if the state is empty, copy the input vector
+        None => {
+            let values = value.iter().map(|x| x.to_f32()).collect::<Vec<_>>();
+            Internal::new(Vecf32AggregateAvgSumStype::new_with_slice(1, &values))
+        }
+        Some(state) => {
+            check_matched_dims(state.dims(), value.dims());
+            state.accumulate(value.iter().map(|x| x.to_f32()));
+            current
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// finalize the intermediate state for vecf16 average
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_aggregate_avg_finalfunc(state: Internal) -> Option<Vecf16Output> {
+    let state = unsafe { state.get_mut::<Vecf32AggregateAvgSumStype>() }?;
+    let count = state.count() as f32;
+    let values = state
+        .slice()
+        .iter()
+        .map(|x| f16::from_f32(x / count))
+        .collect::<Vec<_>>();
+    Some(Vecf16Output::new(
+        VectBorrowed::new_checked(&values).unwrap(),
+    ))
+}
+
+/// accumulate intermediate state for vecf16 element-wise minimum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf16_aggregate_min_sfunc(internal, vecf16) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf16_aggregate_min_sfunc(
+    current: Internal,
+    value: Option<Vecf16Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    vecf16_aggregate_min_max_sfunc(current, value, fcinfo, f32::min)
+}
+
+/// accumulate intermediate state for vecf16 element-wise maximum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf16_aggregate_max_sfunc(internal, vecf16) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf16_aggregate_max_sfunc(
+    current: Internal,
+    value: Option<Vecf16Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    vecf16_aggregate_min_max_sfunc(current, value, fcinfo, f32::max)
+}
+
+fn vecf16_aggregate_min_max_sfunc(
+    current: Internal,
+    value: Option<Vecf16Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+    f: fn(f32, f32) -> f32,
+) -> Internal {
+    let Some(value) = value else { return current };
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match unsafe { current.get_mut::<Vecf32AggregateMinMaxStype>() } {
+        // if the state is empty, copy the input vector
+        None => Internal::new(Vecf32AggregateMinMaxStype::new_with_iter(
+            value.iter().map(|x| x.to_f32()),
+        )),
+        Some(state) => {
+            check_matched_dims(state.dims(), value.dims());
+            state.merge(value.iter().map(|x| x.to_f32()), f);
+            current
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// finalize the intermediate state for vecf16 element-wise minimum or maximum
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_aggregate_min_max_finalfunc(state: Internal) -> Option<Vecf16Output> {
+    let state = unsafe { state.get_mut::<Vecf32AggregateMinMaxStype>() }?;
+    // every element is copied from an input, so the conversion is exact
+    let values = state
+        .slice()
+        .iter()
+        .map(|x| f16::from_f32(*x))
+        .collect::<Vec<_>>();
+    Some(Vecf16Output::new(
+        VectBorrowed::new_checked(&values).unwrap(),
+    ))
+}
+
+/// accumulate intermediate state for vecf16 variance and standard deviation
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf16_aggregate_variance_sfunc(internal, vecf16) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf16_aggregate_variance_sfunc(
+    current: Internal,
+    value: Option<Vecf16Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    let Some(value) = value else { return current };
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match unsafe { current.get_mut::<Vecf32AggregateVarianceStype>() } {
+        // if the state is empty, create it with the dimensions of the input vector
+        None => {
+            let mut state = Vecf32AggregateVarianceStype::new(value.dims());
+            state.push(value.iter().map(|x| x.to_f32()));
+            Internal::new(state)
+        }
+        Some(state) => {
+            check_matched_dims(state.dims(), value.dims());
+            state.push(value.iter().map(|x| x.to_f32()));
+            current
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// finalize the intermediate state for vecf16 variance
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_aggregate_variance_finalfunc(state: Internal) -> Option<Vecf16Output> {
+    let variance = unsafe { state.get_mut::<Vecf32AggregateVarianceStype>() }?.variance()?;
+    let values = variance.into_iter().map(f16::from_f32).collect::<Vec<_>>();
+    Some(Vecf16Output::new(
+        VectBorrowed::new_checked(&values).unwrap(),
+    ))
+}
+
+/// finalize the intermediate state for vecf16 standard deviation
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_aggregate_stddev_finalfunc(state: Internal) -> Option<Vecf16Output> {
+    let variance = unsafe { state.get_mut::<Vecf32AggregateVarianceStype>() }?.variance()?;
+    let values = variance
+        .into_iter()
+        .map(|x| f16::from_f32(x.sqrt()))
+        .collect::<Vec<_>>();
+    Some(Vecf16Output::new(
+        VectBorrowed::new_checked(&values).unwrap(),
+    ))
+}
@@ -1,9 +1,15 @@
 use crate::datatype::memory_vecf32::{Vecf32Input, Vecf32Output};
 use crate::error::*;
 use base::vector::*;
+use common::vec2::Vec2;
 use pgrx::datum::Internal;
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 #[repr(C, align(8))]
+#[derive(Serialize, Deserialize)]
 pub struct Vecf32AggregateAvgSumStype {
     dims: u32,
     count: u64,
@@ -36,6 +42,13 @@ impl Vecf32AggregateAvgSumStype {
             values,
         }
     }
+    /// Add an input vector to the sum.
+    pub fn accumulate(&mut self, iter: impl Iterator<Item = f32>) {
+        for (x, y) in self.values.iter_mut().zip(iter) {
+            *x += y;
+        }
+        self.count += 1;
+    }
 }
 
 /// accumulate intermediate state for vector average
@@ -120,6 +133,24 @@ fn _vectors_vecf32_aggregate_avg_sum_combinefunc(
     result
 }
 
+/// serialize the intermediate state for vector average and sum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_avg_sum_serialfunc(internal) RETURNS bytea IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_avg_sum_serialfunc(state: Internal) -> Vec<u8> {
+    serialize_stype::<Vecf32AggregateAvgSumStype>(state)
+}
+
+/// deserialize the intermediate state for vector average and sum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_avg_sum_deserialfunc(bytea, internal) RETURNS internal IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_avg_sum_deserialfunc(
+    bytes: &[u8],
+    _internal: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    deserialize_stype::<Vecf32AggregateAvgSumStype>(bytes, fcinfo)
+}
+
 /// finalize the intermediate state for vector average
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vectors_vecf32_aggregate_avg_finalfunc(state: Internal) -> Option<Vecf32Output> {
@@ -144,3 +175,503 @@ fn _vectors_vecf32_aggregate_sum_finalfunc(state: Internal) -> Option<Vecf32Outp
     unsafe { state.get_mut::<Vecf32AggregateAvgSumStype>() }
         .map(|state| Vecf32Output::new(VectBorrowed::new_checked(state.slice()).unwrap()))
 }
+
+#[repr(C, align(8))]
+#[derive(Serialize, Deserialize)]
+pub struct Vecf32AggregateMinMaxStype {
+    dims: u32,
+    values: Vec<f32>,
+}
+
+impl Vecf32AggregateMinMaxStype {
+    pub fn dims(&self) -> u32 {
+        self.dims
+    }
+    pub fn slice(&self) -> &[f32] {
+        self.values.as_slice()
+    }
+    pub fn slice_mut(&mut self) -> &mut [f32] {
+        self.values.as_mut_slice()
+    }
+}
+
+impl Vecf32AggregateMinMaxStype {
+    pub fn new_with_iter(iter: impl Iterator<Item = f32>) -> Self {
+        let values = iter.collect::<Vec<_>>();
+        Self {
+            dims: values.len() as u32,
+            values,
+        }
+    }
+    /// Merge an input into the state, element by element.
+    pub fn merge(&mut self, iter: impl Iterator<Item = f32>, f: fn(f32, f32) -> f32) {
+        for (x, y) in self.values.iter_mut().zip(iter) {
+            *x = f(*x, y);
+        }
+    }
+}
+
+/// accumulate intermediate state for vector element-wise minimum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_min_sfunc(internal, vector) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_min_sfunc(
+    current: Internal,
+    value: Option<Vecf32Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    vecf32_aggregate_min_max_sfunc(current, value, fcinfo, f32::min)
+}
+
+/// accumulate intermediate state for vector element-wise maximum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_max_sfunc(internal, vector) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_max_sfunc(
+    current: Internal,
+    value: Option<Vecf32Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    vecf32_aggregate_min_max_sfunc(current, value, fcinfo, f32::max)
+}
+
+fn vecf32_aggregate_min_max_sfunc(
+    current: Internal,
+    value: Option<Vecf32Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+    f: fn(f32, f32) -> f32,
+) -> Internal {
+    let Some(value) = value else { return current };
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match unsafe { current.get_mut::<Vecf32AggregateMinMaxStype>() } {
+        // if the state is empty, copy the input vector
+        None => Internal::new(Vecf32AggregateMinMaxStype::new_with_iter(
+            value.iter().copied(),
+        )),
+        Some(state) => {
+            check_matched_dims(state.dims(), value.dims());
+            state.merge(value.iter().copied(), f);
+            current
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// combine two intermediate states for vector element-wise minimum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_min_combinefunc(internal, internal) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_min_combinefunc(
+    state1: Internal,
+    state2: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    vecf32_aggregate_min_max_combinefunc(state1, state2, fcinfo, f32::min)
+}
+
+/// combine two intermediate states for vector element-wise maximum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_max_combinefunc(internal, internal) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_max_combinefunc(
+    state1: Internal,
+    state2: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    vecf32_aggregate_min_max_combinefunc(state1, state2, fcinfo, f32::max)
+}
+
+fn vecf32_aggregate_min_max_combinefunc(
+    state1: Internal,
+    state2: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+    f: fn(f32, f32) -> f32,
+) -> Internal {
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match (
+        unsafe { state1.get_mut::<Vecf32AggregateMinMaxStype>() },
+        unsafe { state2.get_mut::<Vecf32AggregateMinMaxStype>() },
+    ) {
+        (_, None) => state1,
+        (None, Some(_)) => state2,
+        (Some(s1), Some(s2)) => {
+            check_matched_dims(s1.dims(), s2.dims());
+            s1.merge(s2.slice().iter().copied(), f);
+            state1
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// serialize the intermediate state for vector element-wise minimum and maximum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_min_max_serialfunc(internal) RETURNS bytea IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_min_max_serialfunc(state: Internal) -> Vec<u8> {
+    serialize_stype::<Vecf32AggregateMinMaxStype>(state)
+}
+
+/// deserialize the intermediate state for vector element-wise minimum and maximum
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_min_max_deserialfunc(bytea, internal) RETURNS internal IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_min_max_deserialfunc(
+    bytes: &[u8],
+    _internal: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    deserialize_stype::<Vecf32AggregateMinMaxStype>(bytes, fcinfo)
+}
+
+/// finalize the intermediate state for vector element-wise minimum or maximum
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf32_aggregate_min_max_finalfunc(state: Internal) -> Option<Vecf32Output> {
+    unsafe { state.get_mut::<Vecf32AggregateMinMaxStype>() }
+        .map(|state| Vecf32Output::new(VectBorrowed::new_checked(state.slice()).unwrap()))
+}
+
+/// Welford's online algorithm, computed in `f64` to limit cancellation.
+#[repr(C, align(8))]
+#[derive(Serialize, Deserialize)]
+pub struct Vecf32AggregateVarianceStype {
+    dims: u32,
+    count: u64,
+    mean: Vec<f64>,
+    m2: Vec<f64>,
+}
+
+impl Vecf32AggregateVarianceStype {
+    pub fn dims(&self) -> u32 {
+        self.dims
+    }
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl Vecf32AggregateVarianceStype {
+    pub fn new(dims: u32) -> Self {
+        Self {
+            dims,
+            count: 0,
+            mean: vec![0.0; dims as usize],
+            m2: vec![0.0; dims as usize],
+        }
+    }
+    /// Accumulate an input vector.
+    pub fn push(&mut self, iter: impl Iterator<Item = f32>) {
+        self.count += 1;
+        let count = self.count as f64;
+        for ((mean, m2), x) in self.mean.iter_mut().zip(self.m2.iter_mut()).zip(iter) {
+            let x = x as f64;
+            let delta = x - *mean;
+            *mean += delta / count;
+            *m2 += delta * (x - *mean);
+        }
+    }
+    /// Merge another state into this one.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        let n1 = self.count as f64;
+        let n2 = other.count as f64;
+        let n = n1 + n2;
+        for i in 0..self.dims as usize {
+            let delta = other.mean[i] - self.mean[i];
+            self.mean[i] += delta * n2 / n;
+            self.m2[i] += other.m2[i] + delta * delta * n1 * n2 / n;
+        }
+        self.count += other.count;
+    }
+    /// Sample variance of each element, or `None` if fewer than two inputs were accumulated.
+    pub fn variance(&self) -> Option<Vec<f32>> {
+        if self.count < 2 {
+            return None;
+        }
+        let n = (self.count - 1) as f64;
+        Some(self.m2.iter().map(|m2| (m2 / n) as f32).collect())
+    }
+}
+
+/// accumulate intermediate state for vector variance and standard deviation
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_variance_sfunc(internal, vector) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_variance_sfunc(
+    current: Internal,
+    value: Option<Vecf32Input<'_>>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    let Some(value) = value else { return current };
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match unsafe { current.get_mut::<Vecf32AggregateVarianceStype>() } {
+        // if the state is empty, create it with the dimensions of the input vector
+        None => {
+            let mut state = Vecf32AggregateVarianceStype::new(value.dims());
+            state.push(value.iter().copied());
+            Internal::new(state)
+        }
+        Some(state) => {
+            check_matched_dims(state.dims(), value.dims());
+            state.push(value.iter().copied());
+            current
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// combine two intermediate states for vector variance and standard deviation
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_variance_combinefunc(internal, internal) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_variance_combinefunc(
+    state1: Internal,
+    state2: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match (
+        unsafe { state1.get_mut::<Vecf32AggregateVarianceStype>() },
+        unsafe { state2.get_mut::<Vecf32AggregateVarianceStype>() },
+    ) {
+        (_, None) => state1,
+        (None, Some(_)) => state2,
+        (Some(s1), Some(s2)) => {
+            check_matched_dims(s1.dims(), s2.dims());
+            s1.merge(s2);
+            state1
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// serialize the intermediate state for vector variance and standard deviation
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_variance_serialfunc(internal) RETURNS bytea IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_variance_serialfunc(state: Internal) -> Vec<u8> {
+    serialize_stype::<Vecf32AggregateVarianceStype>(state)
+}
+
+/// deserialize the intermediate state for vector variance and standard deviation
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_variance_deserialfunc(bytea, internal) RETURNS internal IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_variance_deserialfunc(
+    bytes: &[u8],
+    _internal: Internal,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    deserialize_stype::<Vecf32AggregateVarianceStype>(bytes, fcinfo)
+}
+
+/// finalize the intermediate state for vector variance
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf32_aggregate_variance_finalfunc(state: Internal) -> Option<Vecf32Output> {
+    let variance = unsafe { state.get_mut::<Vecf32AggregateVarianceStype>() }?.variance()?;
+    Some(Vecf32Output::new(
+        VectBorrowed::new_checked(&variance).unwrap(),
+    ))
+}
+
+/// finalize the intermediate state for vector standard deviation
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf32_aggregate_stddev_finalfunc(state: Internal) -> Option<Vecf32Output> {
+    let mut stddev = unsafe { state.get_mut::<Vecf32AggregateVarianceStype>() }?.variance()?;
+    stddev.iter_mut().for_each(|x| *x = x.sqrt());
+    Some(Vecf32Output::new(
+        VectBorrowed::new_checked(&stddev).unwrap(),
+    ))
+}
+
+/// The largest number of clusters `vector_kmeans` accepts.
+const KMEANS_MAX_CLUSTERS: u32 = 4096;
+
+/// The number of floats kept as samples for `vector_kmeans`, rows beyond are reservoir-sampled.
+const KMEANS_SAMPLE_FLOATS: usize = 1 << 24;
+
+pub struct Vecf32AggregateKMeansStype {
+    dims: u32,
+    k: u32,
+    iterations: u32,
+    spherical: bool,
+    seen: u64,
+    rng: StdRng,
+    samples: Vec<f32>,
+}
+
+impl Vecf32AggregateKMeansStype {
+    pub fn dims(&self) -> u32 {
+        self.dims
+    }
+    pub fn count(&self) -> usize {
+        self.samples.len() / self.dims as usize
+    }
+    fn capacity(&self) -> usize {
+        std::cmp::max(KMEANS_SAMPLE_FLOATS / self.dims as usize, self.k as usize)
+    }
+    fn push(&mut self, value: &[f32]) {
+        let dims = self.dims as usize;
+        self.seen += 1;
+        if self.count() < self.capacity() {
+            self.samples.extend_from_slice(value);
+        } else {
+            let index = self.rng.gen_range(0..self.seen);
+            if let Ok(index) = usize::try_from(index) {
+                if index < self.count() {
+                    self.samples[index * dims..][..dims].copy_from_slice(value);
+                }
+            }
+        }
+    }
+}
+
+/// accumulate intermediate state for vector k-means, a bounded reservoir sample of the input is kept
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_kmeans_sfunc(internal, vector, integer, integer, boolean) RETURNS internal IMMUTABLE PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_kmeans_sfunc(
+    current: Internal,
+    value: Option<Vecf32Input<'_>>,
+    k: Option<i32>,
+    iterations: Option<i32>,
+    spherical: Option<bool>,
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    let Some(value) = value else { return current };
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = match unsafe { current.get_mut::<Vecf32AggregateKMeansStype>() } {
+        // if the state is empty, check the parameters and copy the input vector
+        None => {
+            let k = match k {
+                Some(k) if (1..=KMEANS_MAX_CLUSTERS as i32).contains(&k) => k as u32,
+                _ => pgrx::error!(
+                    "Bad input: number of clusters must be an integer between 1 and {}",
+                    KMEANS_MAX_CLUSTERS
+                ),
+            };
+            let iterations = match iterations {
+                Some(iterations) if iterations >= 0 => iterations as u32,
+                _ => pgrx::error!("Bad input: number of iterations must be a non-negative integer"),
+            };
+            let mut state = Vecf32AggregateKMeansStype {
+                dims: value.dims(),
+                k,
+                iterations,
+                spherical: spherical.unwrap_or(false),
+                seen: 0,
+                rng: base::rand::rng(Some(0)),
+                samples: Vec::new(),
+            };
+            state.push(value.slice());
+            Internal::new(state)
+        }
+        Some(state) => {
+            check_matched_dims(state.dims(), value.dims());
+            state.push(value.slice());
+            current
+        }
+    };
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
+
+/// finalize the intermediate state for vector k-means
+#[pgrx::pg_extern(sql = "\
+CREATE FUNCTION _vectors_vecf32_aggregate_kmeans_finalfunc(internal) RETURNS vector[] IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';")]
+fn _vectors_vecf32_aggregate_kmeans_finalfunc(state: Internal) -> Option<Vec<Vecf32Output>> {
+    let state = unsafe { state.get_mut::<Vecf32AggregateKMeansStype>() }?;
+    let (k, iterations, spherical) = (state.k, state.iterations, state.spherical);
+    if state.seen < k as u64 {
+        pgrx::error!(
+            "Bad input: number of clusters {} is greater than number of rows {}",
+            k,
+            state.seen
+        );
+    }
+    let samples = Vec2::from_vec(
+        (state.count(), state.dims() as usize),
+        state.samples.clone(),
+    );
+    // clustering runs on a single thread, so it does not take more than one core from the server
+    let centroids = stoppable_rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build_scoped(|pool| {
+            pool.install(|| {
+                k_means::k_means(
+                    k as usize,
+                    samples,
+                    spherical,
+                    iterations as usize,
+                    true,
+                    None,
+                )
+            })
+        })
+        .expect("failed to build the thread pool")
+        .expect("k-means is never stopped");
+    Some(
+        (0..centroids.shape_0())
+            .map(|i| Vecf32Output::new(VectBorrowed::new(&centroids[(i,)])))
+            .collect(),
+    )
+}
+
+/// Intermediate states are sent from parallel workers to the leader as `bytea`.
+pub fn serialize_stype<T: Serialize>(state: Internal) -> Vec<u8> {
+    let state = unsafe { state.get::<T>() }.expect("serializing an empty state");
+    bincode::serialize(state).expect("failed to serialize the state")
+}
+
+pub fn deserialize_stype<T: DeserializeOwned + 'static>(
+    bytes: &[u8],
+    fcinfo: pgrx::pg_sys::FunctionCallInfo,
+) -> Internal {
+    let state = bincode::deserialize::<T>(bytes).expect("failed to deserialize the state");
+    let old_context = unsafe {
+        let mut agg_context: *mut ::pgrx::pg_sys::MemoryContextData = std::ptr::null_mut();
+        if ::pgrx::pg_sys::AggCheckCallContext(fcinfo, &mut agg_context) == 0 {
+            ::pgrx::error!("aggregate function called in non-aggregate context");
+        }
+        ::pgrx::pg_sys::MemoryContextSwitchTo(agg_context)
+    };
+    let result = Internal::new(state);
+    unsafe {
+        ::pgrx::pg_sys::MemoryContextSwitchTo(old_context);
+    }
+    result
+}
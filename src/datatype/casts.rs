@@ -1,11 +1,13 @@
 use crate::datatype::memory_bvector::{BVectorInput, BVectorOutput};
+use crate::datatype::memory_svecf16::{SVecf16Input, SVecf16Output};
 use crate::datatype::memory_svecf32::{SVecf32Input, SVecf32Output};
+use crate::datatype::memory_vecbf16::{Vecbf16Input, Vecbf16Output};
 use crate::datatype::memory_vecf16::{Vecf16Input, Vecf16Output};
 use crate::datatype::memory_vecf32::{Vecf32Input, Vecf32Output};
 use crate::error::*;
 use base::scalar::*;
 use base::vector::*;
-use half::f16;
+use half::{bf16, f16};
 
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vectors_cast_array_to_vecf32(
@@ -52,6 +54,26 @@ fn _vectors_cast_vecf16_to_vecf32(
     Vecf32Output::new(VectBorrowed::new(&slice))
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_cast_vecf32_to_vecbf16(
+    vector: Vecf32Input<'_>,
+    _typmod: i32,
+    _explicit: bool,
+) -> Vecbf16Output {
+    let slice: Vec<bf16> = bf16::vector_from_f32(vector.slice());
+    Vecbf16Output::new(VectBorrowed::new(&slice))
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_cast_vecbf16_to_vecf32(
+    vector: Vecbf16Input<'_>,
+    _typmod: i32,
+    _explicit: bool,
+) -> Vecf32Output {
+    let slice: Vec<f32> = bf16::vector_to_f32(vector.slice());
+    Vecf32Output::new(VectBorrowed::new(&slice))
+}
+
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vectors_cast_vecf32_to_svecf32(
     vector: Vecf32Input<'_>,
@@ -86,6 +108,36 @@ fn _vectors_cast_svecf32_to_vecf32(
     Vecf32Output::new(VectBorrowed::new(&slice))
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_cast_svecf32_to_svecf16(
+    vector: SVecf32Input<'_>,
+    _typmod: i32,
+    _explicit: bool,
+) -> SVecf16Output {
+    let vector = vector.as_borrowed();
+    let mut indexes = Vec::new();
+    let mut values = Vec::new();
+    for (&index, &value) in vector.indexes().iter().zip(vector.values()) {
+        let value = f16::from_f32(value);
+        if value != f16::ZERO {
+            indexes.push(index);
+            values.push(value);
+        }
+    }
+    SVecf16Output::new(SVectBorrowed::new(vector.dims(), &indexes, &values))
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_cast_svecf16_to_svecf32(
+    vector: SVecf16Input<'_>,
+    _typmod: i32,
+    _explicit: bool,
+) -> SVecf32Output {
+    let vector = vector.as_borrowed();
+    let values: Vec<f32> = f16::vector_to_f32(vector.values());
+    SVecf32Output::new(SVectBorrowed::new(vector.dims(), vector.indexes(), &values))
+}
+
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vectors_cast_vecf32_to_bvector(
     vector: Vecf32Input<'_>,
@@ -0,0 +1,126 @@
+use super::memory_svecf16::*;
+use crate::error::*;
+use crate::utils::projection::with_projection;
+use base::scalar::*;
+use base::vector::*;
+use half::f16;
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_dims(vector: SVecf16Input<'_>) -> i32 {
+    vector.as_borrowed().dims() as i32
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_norm(vector: SVecf16Input<'_>) -> f32 {
+    vector.as_borrowed().norm().to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_normalize(vector: SVecf16Input<'_>) -> SVecf16Output {
+    SVecf16Output::new(vector.as_borrowed().function_normalize().as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_to_svecf16(
+    dims: i32,
+    index: pgrx::datum::Array<i32>,
+    value: pgrx::datum::Array<f32>,
+) -> SVecf16Output {
+    let dims = dims as u32;
+    check_value_dims_1048575(dims);
+    if index.len() != value.len() {
+        bad_literal("Lengths of index and value are not matched.");
+    }
+    if index.contains_nulls() || value.contains_nulls() {
+        bad_literal("Index or value contains nulls.");
+    }
+    let mut vector: Vec<(u32, f32)> = index
+        .iter_deny_null()
+        .zip(value.iter_deny_null())
+        .map(|(index, value)| {
+            if index < 0 || index as u32 >= dims {
+                bad_literal("Index out of bound.");
+            }
+            (index as u32, value)
+        })
+        .collect();
+    vector.sort_unstable_by_key(|x| x.0);
+    if vector.len() > 1 {
+        for i in 0..vector.len() - 1 {
+            if vector[i].0 == vector[i + 1].0 {
+                bad_literal("Duplicated index.");
+            }
+        }
+    }
+
+    let mut indexes = Vec::<u32>::with_capacity(vector.len());
+    let mut values = Vec::<f16>::with_capacity(vector.len());
+    for x in vector {
+        // values that underflow to zero in half precision are dropped
+        let value = f16::from_f32(x.1);
+        if value != f16::ZERO {
+            indexes.push(x.0);
+            values.push(value);
+        }
+    }
+    SVecf16Output::new(SVectBorrowed::new(dims, &indexes, &values))
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_abs(vector: SVecf16Input<'_>) -> SVecf16Output {
+    SVecf16Output::new(vector.as_borrowed().function_abs().as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_clamp(vector: SVecf16Input<'_>, min: f32, max: f32) -> SVecf16Output {
+    check_clamp_bounds(min, max);
+    SVecf16Output::new(vector.as_borrowed().function_clamp(min, max).as_borrowed())
+}
+
+/// Returns every `step`-th element of `[start, end)`, with the same bounds as subscripts.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_slice(
+    vector: SVecf16Input<'_>,
+    start: i32,
+    end: i32,
+    step: i32,
+) -> Option<SVecf16Output> {
+    let step = check_slice_step(step);
+    if start < 0 || end < 0 {
+        return None;
+    }
+    let output = vector
+        .as_borrowed()
+        .subvector_step(start as u32, end as u32, step)?;
+    Some(SVecf16Output::new(output.as_borrowed()))
+}
+
+/// Projects the vector onto `dims` rows of a random orthogonal matrix, which is
+/// the same for every call with the same dimensions and seed.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_random_projection(
+    vector: SVecf16Input<'_>,
+    dims: i32,
+    seed: i64,
+) -> SVecf16Output {
+    let target = check_projection_dims(vector.dims(), dims);
+    let vector = vector.as_borrowed();
+    let mut indexes = Vec::new();
+    let mut values = Vec::new();
+    with_projection(vector.dims(), target, seed, |projection| {
+        for (i, row) in projection.iter().enumerate() {
+            let value = vector
+                .indexes()
+                .iter()
+                .zip(vector.values())
+                .map(|(&index, &value)| row[index as usize] * value.to_f32())
+                .sum::<f32>();
+            let value = f16::from_f32(value);
+            if value != f16::ZERO {
+                indexes.push(i as u32);
+                values.push(value);
+            }
+        }
+    });
+    SVecf16Output::new(SVectBorrowed::new(target, &indexes, &values))
+}
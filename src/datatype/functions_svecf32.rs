@@ -1,5 +1,6 @@
 use super::memory_svecf32::*;
 use crate::error::*;
+use crate::utils::projection::with_projection;
 use base::scalar::*;
 use base::vector::*;
 
@@ -59,3 +60,61 @@ fn _vectors_to_svector(
     }
     SVecf32Output::new(SVectBorrowed::new(dims, &indexes, &values))
 }
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf32_abs(vector: SVecf32Input<'_>) -> SVecf32Output {
+    SVecf32Output::new(vector.as_borrowed().function_abs().as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf32_clamp(vector: SVecf32Input<'_>, min: f32, max: f32) -> SVecf32Output {
+    check_clamp_bounds(min, max);
+    SVecf32Output::new(vector.as_borrowed().function_clamp(min, max).as_borrowed())
+}
+
+/// Returns every `step`-th element of `[start, end)`, with the same bounds as subscripts.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf32_slice(
+    vector: SVecf32Input<'_>,
+    start: i32,
+    end: i32,
+    step: i32,
+) -> Option<SVecf32Output> {
+    let step = check_slice_step(step);
+    if start < 0 || end < 0 {
+        return None;
+    }
+    let output = vector
+        .as_borrowed()
+        .subvector_step(start as u32, end as u32, step)?;
+    Some(SVecf32Output::new(output.as_borrowed()))
+}
+
+/// Projects the vector onto `dims` rows of a random orthogonal matrix, which is
+/// the same for every call with the same dimensions and seed.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf32_random_projection(
+    vector: SVecf32Input<'_>,
+    dims: i32,
+    seed: i64,
+) -> SVecf32Output {
+    let target = check_projection_dims(vector.dims(), dims);
+    let vector = vector.as_borrowed();
+    let mut indexes = Vec::new();
+    let mut values = Vec::new();
+    with_projection(vector.dims(), target, seed, |projection| {
+        for (i, row) in projection.iter().enumerate() {
+            let value = vector
+                .indexes()
+                .iter()
+                .zip(vector.values())
+                .map(|(&index, &value)| row[index as usize] * value)
+                .sum::<f32>();
+            if value != 0.0 {
+                indexes.push(i as u32);
+                values.push(value);
+            }
+        }
+    });
+    SVecf32Output::new(SVectBorrowed::new(target, &indexes, &values))
+}
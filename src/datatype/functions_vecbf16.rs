@@ -0,0 +1,69 @@
+use crate::datatype::memory_vecbf16::*;
+use crate::error::*;
+use crate::utils::projection::with_projection;
+use base::scalar::*;
+use base::vector::*;
+use half::bf16;
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecbf16_dims(vector: Vecbf16Input<'_>) -> i32 {
+    vector.as_borrowed().dims() as i32
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecbf16_norm(vector: Vecbf16Input<'_>) -> f32 {
+    vector.as_borrowed().norm().to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecbf16_normalize(vector: Vecbf16Input<'_>) -> Vecbf16Output {
+    Vecbf16Output::new(vector.as_borrowed().function_normalize().as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecbf16_abs(vector: Vecbf16Input<'_>) -> Vecbf16Output {
+    Vecbf16Output::new(vector.as_borrowed().function_abs().as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecbf16_clamp(vector: Vecbf16Input<'_>, min: f32, max: f32) -> Vecbf16Output {
+    check_clamp_bounds(min, max);
+    Vecbf16Output::new(vector.as_borrowed().function_clamp(min, max).as_borrowed())
+}
+
+/// Returns every `step`-th element of `[start, end)`, with the same bounds as subscripts.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecbf16_slice(
+    vector: Vecbf16Input<'_>,
+    start: i32,
+    end: i32,
+    step: i32,
+) -> Option<Vecbf16Output> {
+    let step = check_slice_step(step);
+    if start < 0 || end < 0 {
+        return None;
+    }
+    let output = vector
+        .as_borrowed()
+        .subvector_step(start as u32, end as u32, step)?;
+    Some(Vecbf16Output::new(output.as_borrowed()))
+}
+
+/// Projects the vector onto `dims` rows of a random orthogonal matrix, which is
+/// the same for every call with the same dimensions and seed.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecbf16_random_projection(
+    vector: Vecbf16Input<'_>,
+    dims: i32,
+    seed: i64,
+) -> Vecbf16Output {
+    let target = check_projection_dims(vector.dims(), dims);
+    let input = bf16::vector_to_f32(vector.slice());
+    let slice = with_projection(vector.dims(), target, seed, |projection| {
+        projection
+            .iter()
+            .map(|row| f32::reduce_sum_of_xy(row, &input))
+            .collect::<Vec<_>>()
+    });
+    Vecbf16Output::new(VectBorrowed::new(&bf16::vector_from_f32(&slice)))
+}
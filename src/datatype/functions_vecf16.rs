@@ -1,6 +1,9 @@
 use crate::datatype::memory_vecf16::*;
+use crate::error::*;
+use crate::utils::projection::with_projection;
 use base::scalar::*;
 use base::vector::*;
+use half::f16;
 
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vectors_vecf16_dims(vector: Vecf16Input<'_>) -> i32 {
@@ -16,3 +19,51 @@ fn _vectors_vecf16_norm(vector: Vecf16Input<'_>) -> f32 {
 fn _vectors_vecf16_normalize(vector: Vecf16Input<'_>) -> Vecf16Output {
     Vecf16Output::new(vector.as_borrowed().function_normalize().as_borrowed())
 }
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_abs(vector: Vecf16Input<'_>) -> Vecf16Output {
+    Vecf16Output::new(vector.as_borrowed().function_abs().as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_clamp(vector: Vecf16Input<'_>, min: f32, max: f32) -> Vecf16Output {
+    check_clamp_bounds(min, max);
+    Vecf16Output::new(vector.as_borrowed().function_clamp(min, max).as_borrowed())
+}
+
+/// Returns every `step`-th element of `[start, end)`, with the same bounds as subscripts.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_slice(
+    vector: Vecf16Input<'_>,
+    start: i32,
+    end: i32,
+    step: i32,
+) -> Option<Vecf16Output> {
+    let step = check_slice_step(step);
+    if start < 0 || end < 0 {
+        return None;
+    }
+    let output = vector
+        .as_borrowed()
+        .subvector_step(start as u32, end as u32, step)?;
+    Some(Vecf16Output::new(output.as_borrowed()))
+}
+
+/// Projects the vector onto `dims` rows of a random orthogonal matrix, which is
+/// the same for every call with the same dimensions and seed.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_random_projection(
+    vector: Vecf16Input<'_>,
+    dims: i32,
+    seed: i64,
+) -> Vecf16Output {
+    let target = check_projection_dims(vector.dims(), dims);
+    let input = f16::vector_to_f32(vector.slice());
+    let slice = with_projection(vector.dims(), target, seed, |projection| {
+        projection
+            .iter()
+            .map(|row| f32::reduce_sum_of_xy(row, &input))
+            .collect::<Vec<_>>()
+    });
+    Vecf16Output::new(VectBorrowed::new(&f16::vector_from_f32(&slice)))
+}
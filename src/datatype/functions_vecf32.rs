@@ -1,6 +1,9 @@
 use crate::datatype::memory_vecf32::*;
+use crate::error::*;
+use crate::utils::projection::with_projection;
 use base::scalar::*;
 use base::vector::*;
+use common::vec2::Vec2;
 
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vectors_vecf32_dims(vector: Vecf32Input<'_>) -> i32 {
@@ -16,3 +19,72 @@ fn _vectors_vecf32_norm(vector: Vecf32Input<'_>) -> f32 {
 fn _vectors_vecf32_normalize(vector: Vecf32Input<'_>) -> Vecf32Output {
     Vecf32Output::new(vector.as_borrowed().function_normalize().as_borrowed())
 }
+
+/// Returns the 1-based position of the nearest centroid.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf32_kmeans_assign(
+    vector: Vecf32Input<'_>,
+    centroids: pgrx::datum::Array<'_, Vecf32Output>,
+) -> i32 {
+    let dims = vector.dims();
+    let mut flat = Vec::with_capacity(centroids.len() * dims as usize);
+    for centroid in centroids.iter() {
+        let Some(centroid) = centroid else {
+            pgrx::error!("Bad input: centroids must not contain NULL");
+        };
+        check_matched_dims(dims, centroid.dims());
+        flat.extend_from_slice(centroid.slice());
+    }
+    if flat.is_empty() {
+        pgrx::error!("Bad input: centroids must not be empty");
+    }
+    let centroids = Vec2::from_vec((flat.len() / dims as usize, dims as usize), flat);
+    k_means::k_means_lookup(vector.slice(), &centroids) as i32 + 1
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf32_abs(vector: Vecf32Input<'_>) -> Vecf32Output {
+    Vecf32Output::new(vector.as_borrowed().function_abs().as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf32_clamp(vector: Vecf32Input<'_>, min: f32, max: f32) -> Vecf32Output {
+    check_clamp_bounds(min, max);
+    Vecf32Output::new(vector.as_borrowed().function_clamp(min, max).as_borrowed())
+}
+
+/// Returns every `step`-th element of `[start, end)`, with the same bounds as subscripts.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf32_slice(
+    vector: Vecf32Input<'_>,
+    start: i32,
+    end: i32,
+    step: i32,
+) -> Option<Vecf32Output> {
+    let step = check_slice_step(step);
+    if start < 0 || end < 0 {
+        return None;
+    }
+    let output = vector
+        .as_borrowed()
+        .subvector_step(start as u32, end as u32, step)?;
+    Some(Vecf32Output::new(output.as_borrowed()))
+}
+
+/// Projects the vector onto `dims` rows of a random orthogonal matrix, which is
+/// the same for every call with the same dimensions and seed.
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf32_random_projection(
+    vector: Vecf32Input<'_>,
+    dims: i32,
+    seed: i64,
+) -> Vecf32Output {
+    let target = check_projection_dims(vector.dims(), dims);
+    let slice = with_projection(vector.dims(), target, seed, |projection| {
+        projection
+            .iter()
+            .map(|row| f32::reduce_sum_of_xy(row, vector.slice()))
+            .collect::<Vec<_>>()
+    });
+    Vecf32Output::new(VectBorrowed::new(&slice))
+}
@@ -1,29 +1,45 @@
+pub mod aggregate_bvector;
 pub mod aggregate_svecf32;
+pub mod aggregate_vecf16;
 pub mod aggregate_vecf32;
 pub mod binary;
 pub mod binary_bvector;
+pub mod binary_svecf16;
 pub mod binary_svecf32;
+pub mod binary_vecbf16;
 pub mod binary_vecf16;
 pub mod binary_vecf32;
 pub mod casts;
 pub mod functions_bvector;
+pub mod functions_svecf16;
 pub mod functions_svecf32;
+pub mod functions_vecbf16;
 pub mod functions_vecf16;
 pub mod functions_vecf32;
 pub mod memory_bvector;
+pub mod memory_svecf16;
 pub mod memory_svecf32;
+pub mod memory_vecbf16;
 pub mod memory_vecf16;
 pub mod memory_vecf32;
 pub mod operators_bvector;
+pub mod operators_multivecf32;
+pub mod operators_search;
+pub mod operators_svecf16;
 pub mod operators_svecf32;
+pub mod operators_vecbf16;
 pub mod operators_vecf16;
 pub mod operators_vecf32;
 pub mod subscript_bvector;
+pub mod subscript_svecf16;
 pub mod subscript_svecf32;
+pub mod subscript_vecbf16;
 pub mod subscript_vecf16;
 pub mod subscript_vecf32;
 pub mod text_bvector;
+pub mod text_svecf16;
 pub mod text_svecf32;
+pub mod text_vecbf16;
 pub mod text_vecf16;
 pub mod text_vecf32;
 pub mod typmod;
@@ -0,0 +1,88 @@
+use crate::datatype::memory_vecf32::Vecf32Output;
+use crate::error::*;
+use base::vector::*;
+use pgrx::datum::Array;
+use std::num::NonZero;
+
+/// Sums, over every vector of `rhs`, the nearest distance to a vector of `lhs`.
+fn multivector_distance(
+    lhs: impl IntoIterator<Item = Option<Vecf32Output>>,
+    rhs: impl IntoIterator<Item = Option<Vecf32Output>>,
+    f: impl Fn(VectBorrowed<'_, f32>, VectBorrowed<'_, f32>) -> f32,
+) -> f32 {
+    let lhs = lhs
+        .into_iter()
+        .map(|x| x.unwrap_or_else(|| pgrx::error!("Bad input: multivectors must not contain NULL")))
+        .collect::<Vec<_>>();
+    let mut sum = 0.0f32;
+    for r in rhs {
+        let Some(r) = r else {
+            pgrx::error!("Bad input: multivectors must not contain NULL");
+        };
+        let mut min = f32::INFINITY;
+        for l in lhs.iter() {
+            check_matched_dims(l.dims(), r.dims());
+            min = min.min(f(l.as_borrowed(), r.as_borrowed()));
+        }
+        sum += min;
+    }
+    sum
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_multivecf32_operator_maxsim(
+    lhs: Array<'_, Vecf32Output>,
+    rhs: Array<'_, Vecf32Output>,
+) -> f32 {
+    multivector_distance(lhs.iter(), rhs.iter(), |l, r| {
+        VectBorrowed::operator_dot(l, r).to_f32()
+    })
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_multivecf32_operator_chamfer(
+    lhs: Array<'_, Vecf32Output>,
+    rhs: Array<'_, Vecf32Output>,
+) -> f32 {
+    multivector_distance(lhs.iter(), rhs.iter(), |l, r| {
+        VectBorrowed::operator_l2(l, r).to_f32()
+    })
+}
+
+fn multivector_sphere(
+    rhs: pgrx::composite_type!("sphere_multivector"),
+) -> (Vec<Option<Vecf32Output>>, f32) {
+    let center: Vec<Option<Vecf32Output>> = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    (center, radius)
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_multivecf32_sphere_maxsim_in(
+    lhs: Array<'_, Vecf32Output>,
+    rhs: pgrx::composite_type!("sphere_multivector"),
+) -> bool {
+    let (center, radius) = multivector_sphere(rhs);
+    multivector_distance(lhs.iter(), center, |l, r| {
+        VectBorrowed::operator_dot(l, r).to_f32()
+    }) < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_multivecf32_sphere_chamfer_in(
+    lhs: Array<'_, Vecf32Output>,
+    rhs: pgrx::composite_type!("sphere_multivector"),
+) -> bool {
+    let (center, radius) = multivector_sphere(rhs);
+    multivector_distance(lhs.iter(), center, |l, r| {
+        VectBorrowed::operator_l2(l, r).to_f32()
+    }) < radius
+}
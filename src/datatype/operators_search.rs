@@ -0,0 +1,42 @@
+use crate::datatype::memory_bvector::{BVectorInput, BVectorOutput};
+use crate::datatype::memory_svecf16::{SVecf16Input, SVecf16Output};
+use crate::datatype::memory_svecf32::{SVecf32Input, SVecf32Output};
+use crate::datatype::memory_vecbf16::{Vecbf16Input, Vecbf16Output};
+use crate::datatype::memory_vecf16::{Vecf16Input, Vecf16Output};
+use crate::datatype::memory_vecf32::{Vecf32Input, Vecf32Output};
+use crate::error::*;
+use base::vector::*;
+use std::num::NonZero;
+
+// Distances between a vector and the query of a `search_*` composite. Search
+// options in the composite are only read by index scans.
+macro_rules! search {
+    ($($t:ident, $search:tt, $prefix:ident, $borrowed:ident, [$($operator:ident),*];)*) => {
+        paste::paste! {
+            $($(
+                #[pgrx::pg_extern(immutable, strict, parallel_safe)]
+                fn [<_vectors_ $t _search_ $operator>](
+                    lhs: [<$prefix Input>]<'_>,
+                    rhs: pgrx::composite_type!($search),
+                ) -> f32 {
+                    let query: [<$prefix Output>] = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+                        Ok(Some(s)) => s,
+                        Ok(None) => pgrx::error!("Bad input: empty query at search"),
+                        Err(_) => unreachable!(),
+                    };
+                    check_matched_dims(lhs.dims(), query.dims());
+                    $borrowed::[<operator_ $operator>](lhs.as_borrowed(), query.as_borrowed()).to_f32()
+                }
+            )*)*
+        }
+    };
+}
+
+search! {
+    vecf32, "search_vector", Vecf32, VectBorrowed, [l2, dot, cos];
+    vecf16, "search_vecf16", Vecf16, VectBorrowed, [l2, dot, cos];
+    vecbf16, "search_vecbf16", Vecbf16, VectBorrowed, [l2, dot, cos];
+    svecf32, "search_svector", SVecf32, SVectBorrowed, [l2, dot, cos];
+    svecf16, "search_svecf16", SVecf16, SVectBorrowed, [l2, dot, cos];
+    bvector, "search_bvector", BVector, BVectBorrowed, [dot, hamming, jaccard];
+}
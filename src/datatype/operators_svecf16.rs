@@ -0,0 +1,175 @@
+use crate::datatype::memory_svecf16::{SVecf16Input, SVecf16Output};
+use crate::error::*;
+use base::vector::*;
+use std::num::NonZero;
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_add(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> SVecf16Output {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    SVecf16Output::new(
+        lhs.as_borrowed()
+            .operator_add(rhs.as_borrowed())
+            .as_borrowed(),
+    )
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_sub(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> SVecf16Output {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    SVecf16Output::new(
+        lhs.as_borrowed()
+            .operator_sub(rhs.as_borrowed())
+            .as_borrowed(),
+    )
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_mul(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> SVecf16Output {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    SVecf16Output::new(
+        lhs.as_borrowed()
+            .operator_mul(rhs.as_borrowed())
+            .as_borrowed(),
+    )
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_lt(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> bool {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    lhs.as_borrowed() < rhs.as_borrowed()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_lte(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> bool {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    lhs.as_borrowed() <= rhs.as_borrowed()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_gt(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> bool {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    lhs.as_borrowed() > rhs.as_borrowed()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_gte(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> bool {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    lhs.as_borrowed() >= rhs.as_borrowed()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_eq(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> bool {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    lhs.as_borrowed() == rhs.as_borrowed()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_neq(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> bool {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    lhs.as_borrowed() != rhs.as_borrowed()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_dot(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> f32 {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    SVectBorrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed()).to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_l2(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> f32 {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    SVectBorrowed::operator_l2(lhs.as_borrowed(), rhs.as_borrowed()).to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_cos(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> f32 {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    SVectBorrowed::operator_cos(lhs.as_borrowed(), rhs.as_borrowed()).to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_sphere_dot_in(
+    lhs: SVecf16Input<'_>,
+    rhs: pgrx::composite_type!("sphere_svecf16"),
+) -> bool {
+    let center: SVecf16Output = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    check_matched_dims(lhs.dims(), center.dims());
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    SVectBorrowed::operator_dot(lhs.as_borrowed(), center.as_borrowed()).to_f32() < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_sphere_l2_in(
+    lhs: SVecf16Input<'_>,
+    rhs: pgrx::composite_type!("sphere_svecf16"),
+) -> bool {
+    let center: SVecf16Output = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    check_matched_dims(lhs.dims(), center.dims());
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    SVectBorrowed::operator_l2(lhs.as_borrowed(), center.as_borrowed()).to_f32() < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_sphere_cos_in(
+    lhs: SVecf16Input<'_>,
+    rhs: pgrx::composite_type!("sphere_svecf16"),
+) -> bool {
+    let center: SVecf16Output = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    check_matched_dims(lhs.dims(), center.dims());
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    SVectBorrowed::operator_cos(lhs.as_borrowed(), center.as_borrowed()).to_f32() < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_div(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> SVecf16Output {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    let rhs = rhs.as_borrowed();
+    check_divisor(rhs.values(), rhs.len() == rhs.dims());
+    let result = lhs.as_borrowed().operator_div(rhs);
+    check_quotient(result.values());
+    SVecf16Output::new(result.as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_mul_scalar(lhs: SVecf16Input<'_>, rhs: f32) -> SVecf16Output {
+    SVecf16Output::new(lhs.as_borrowed().function_mul_scalar(rhs).as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_scalar_mul(lhs: f32, rhs: SVecf16Input<'_>) -> SVecf16Output {
+    SVecf16Output::new(rhs.as_borrowed().function_mul_scalar(lhs).as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf16_operator_concat(lhs: SVecf16Input<'_>, rhs: SVecf16Input<'_>) -> SVecf16Output {
+    check_value_dims_1048575(lhs.dims() + rhs.dims());
+    SVecf16Output::new(
+        lhs.as_borrowed()
+            .function_concat(rhs.as_borrowed())
+            .as_borrowed(),
+    )
+}
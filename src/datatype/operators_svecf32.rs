@@ -143,3 +143,33 @@ fn _vectors_svecf32_sphere_cos_in(
     };
     SVectBorrowed::operator_cos(lhs.as_borrowed(), center.as_borrowed()).to_f32() < radius
 }
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf32_operator_div(lhs: SVecf32Input<'_>, rhs: SVecf32Input<'_>) -> SVecf32Output {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    let rhs = rhs.as_borrowed();
+    check_divisor(rhs.values(), rhs.len() == rhs.dims());
+    let result = lhs.as_borrowed().operator_div(rhs);
+    check_quotient(result.values());
+    SVecf32Output::new(result.as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf32_operator_mul_scalar(lhs: SVecf32Input<'_>, rhs: f32) -> SVecf32Output {
+    SVecf32Output::new(lhs.as_borrowed().function_mul_scalar(rhs).as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf32_operator_scalar_mul(lhs: f32, rhs: SVecf32Input<'_>) -> SVecf32Output {
+    SVecf32Output::new(rhs.as_borrowed().function_mul_scalar(lhs).as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_svecf32_operator_concat(lhs: SVecf32Input<'_>, rhs: SVecf32Input<'_>) -> SVecf32Output {
+    check_value_dims_1048575(lhs.dims() + rhs.dims());
+    SVecf32Output::new(
+        lhs.as_borrowed()
+            .function_concat(rhs.as_borrowed())
+            .as_borrowed(),
+    )
+}
@@ -143,3 +143,32 @@ fn _vectors_vecf16_sphere_cos_in(
     };
     VectBorrowed::operator_cos(lhs.as_borrowed(), center.as_borrowed()).to_f32() < radius
 }
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_operator_div(lhs: Vecf16Input<'_>, rhs: Vecf16Input<'_>) -> Vecf16Output {
+    check_matched_dims(lhs.dims(), rhs.dims());
+    check_divisor(rhs.slice(), true);
+    let result = lhs.as_borrowed().operator_div(rhs.as_borrowed());
+    check_quotient(result.slice());
+    Vecf16Output::new(result.as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_operator_mul_scalar(lhs: Vecf16Input<'_>, rhs: f32) -> Vecf16Output {
+    Vecf16Output::new(lhs.as_borrowed().function_mul_scalar(rhs).as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_operator_scalar_mul(lhs: f32, rhs: Vecf16Input<'_>) -> Vecf16Output {
+    Vecf16Output::new(rhs.as_borrowed().function_mul_scalar(lhs).as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vectors_vecf16_operator_concat(lhs: Vecf16Input<'_>, rhs: Vecf16Input<'_>) -> Vecf16Output {
+    check_value_dims_65535(lhs.dims() + rhs.dims());
+    Vecf16Output::new(
+        lhs.as_borrowed()
+            .function_concat(rhs.as_borrowed())
+            .as_borrowed(),
+    )
+}
@@ -1,4 +1,5 @@
 use crate::ipc::{ClientRpc, ConnectionError};
+use base::scalar::ScalarLike;
 use pgrx::error;
 use std::num::NonZeroU32;
 
@@ -73,6 +74,73 @@ INFORMATION: hint = {hint}"
     );
 }
 
+pub fn check_clamp_bounds(min: f32, max: f32) {
+    if !(min <= max) {
+        error!(
+            "\
+pgvecto.rs: Bounds of clamp are invalid.
+ADVICE: Check if the lower bound is not greater than the upper bound."
+        )
+    }
+}
+
+pub fn check_slice_step(step: i32) -> u32 {
+    if step <= 0 {
+        error!(
+            "\
+pgvecto.rs: Step of slice is invalid.
+ADVICE: Check if the step is a positive integer."
+        )
+    }
+    step as u32
+}
+
+pub fn check_divisor<S: ScalarLike>(values: &[S], dense: bool) {
+    if !dense
+        || values
+            .iter()
+            .any(|x| x.to_f32() == 0.0 || !x.to_f32().is_finite())
+    {
+        error!(
+            "\
+pgvecto.rs: Divisor of the division is invalid.
+ADVICE: Check if every element of the divisor is a non-zero finite number."
+        )
+    }
+}
+
+pub fn check_quotient<S: ScalarLike>(values: &[S]) {
+    if values.iter().any(|x| !x.to_f32().is_finite()) {
+        error!(
+            "\
+pgvecto.rs: Result of the division is out of range.
+ADVICE: Check if the divisor is too close to zero."
+        )
+    }
+}
+
+/// The largest number of elements of a projection, about 256 MiB of `f32`.
+const PROJECTION_MAX_ELEMENTS: u64 = 1 << 26;
+
+pub fn check_projection_dims(dims: u32, target: i32) -> u32 {
+    if !(1..=dims as i64).contains(&(target as i64)) {
+        error!(
+            "\
+pgvecto.rs: Dimensions of the projection is invalid.
+ADVICE: Check if dimensions of the projection are among 1 and {dims}."
+        )
+    }
+    if dims as u64 * target as u64 > PROJECTION_MAX_ELEMENTS {
+        error!(
+            "\
+pgvecto.rs: Dimensions of the projection is too large.
+ADVICE: Check if the product of dimensions of the vector and the projection \
+is not greater than {PROJECTION_MAX_ELEMENTS}."
+        )
+    }
+    target as u32
+}
+
 #[inline(always)]
 pub fn check_matched_dims(left_dims: u32, right_dims: u32) -> u32 {
     if left_dims != right_dims {
@@ -135,9 +203,25 @@ INFORMATION: reason = {reason:?}"
     )
 }
 
+pub fn bad_service_invalid_search_options(reason: &str) -> ! {
+    error!(
+        "\
+pgvecto.rs: The given search option is invalid.
+INFORMATION: reason = {reason:?}"
+    )
+}
+
 pub fn bad_service_invalid_vector() -> ! {
     error!(
         "\
 pgvecto.rs: The dimension of a vector does not matched that in a vector index column."
     )
 }
+
+pub fn bad_service_invalid_tune_options(reason: &str) -> ! {
+    error!(
+        "\
+pgvecto.rs: The given tune option is invalid.
+INFORMATION: reason = {reason:?}"
+    )
+}
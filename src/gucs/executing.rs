@@ -16,12 +16,36 @@ static PQ_FAST_SCAN: GucSetting<bool> =
 static RQ_FAST_SCAN: GucSetting<bool> =
     GucSetting::<bool>::new(SearchOptions::default_rq_fast_scan());
 
+static RQ_RERANK_SIZE: GucSetting<i32> =
+    GucSetting::<i32>::new(SearchOptions::default_rq_rerank_size() as i32);
+
+static BQ_RERANK_SIZE: GucSetting<i32> =
+    GucSetting::<i32>::new(SearchOptions::default_bq_rerank_size() as i32);
+
 static IVF_NPROBE: GucSetting<i32> =
     GucSetting::<i32>::new(SearchOptions::default_ivf_nprobe() as i32);
 
+static IVF_COARSE_NPROBE: GucSetting<i32> =
+    GucSetting::<i32>::new(SearchOptions::default_ivf_coarse_nprobe() as i32);
+
+static IVF_ADAPTIVE_PATIENCE: GucSetting<i32> =
+    GucSetting::<i32>::new(SearchOptions::default_ivf_adaptive_patience() as i32);
+
 static HNSW_EF_SEARCH: GucSetting<i32> =
     GucSetting::<i32>::new(SearchOptions::default_hnsw_ef_search() as i32);
 
+static HNSW_ADAPTIVE_PATIENCE: GucSetting<i32> =
+    GucSetting::<i32>::new(SearchOptions::default_hnsw_adaptive_patience() as i32);
+
+static ADAPTIVE_K: GucSetting<i32> =
+    GucSetting::<i32>::new(SearchOptions::default_adaptive_k() as i32);
+
+static TRUNCATION_RERANK_SIZE: GucSetting<i32> =
+    GucSetting::<i32>::new(SearchOptions::default_truncation_rerank_size() as i32);
+
+static MULTIVECTOR_CANDIDATES: GucSetting<i32> =
+    GucSetting::<i32>::new(SearchOptions::default_multivector_candidates() as i32);
+
 pub unsafe fn init() {
     GucRegistry::define_int_guc(
         "vectors.sq_rerank_size",
@@ -67,6 +91,26 @@ pub unsafe fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_int_guc(
+        "vectors.rq_rerank_size",
+        "RaBitQ reranker size, or 0 to rerank 1-bit codes by error bounds.",
+        "https://docs.pgvecto.rs/usage/search.html",
+        &RQ_RERANK_SIZE,
+        0,
+        65535,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        "vectors.bq_rerank_size",
+        "Binary quantization reranker size.",
+        "https://docs.pgvecto.rs/usage/search.html",
+        &BQ_RERANK_SIZE,
+        0,
+        65535,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
     GucRegistry::define_int_guc(
         "vectors.ivf_nprobe",
         "`nprobe` argument of IVF algorithm.",
@@ -77,6 +121,26 @@ pub unsafe fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_int_guc(
+        "vectors.ivf_coarse_nprobe",
+        "Number of coarse centroids probed by IVF with hierarchical centroids.",
+        "https://docs.pgvecto.rs/usage/search.html",
+        &IVF_COARSE_NPROBE,
+        1,
+        u16::MAX as _,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        "vectors.ivf_adaptive_patience",
+        "Number of probed lists without improvement after which IVF stops probing. 0 disables it.",
+        "https://docs.pgvecto.rs/usage/search.html",
+        &IVF_ADAPTIVE_PATIENCE,
+        0,
+        u16::MAX as _,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
     GucRegistry::define_int_guc(
         "vectors.hnsw_ef_search",
         "`ef_search` argument of HNSW algorithm.",
@@ -87,16 +151,104 @@ pub unsafe fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_int_guc(
+        "vectors.hnsw_adaptive_patience",
+        "Number of visited vertices without improvement after which HNSW stops searching. 0 disables it.",
+        "https://docs.pgvecto.rs/usage/search.html",
+        &HNSW_ADAPTIVE_PATIENCE,
+        0,
+        u16::MAX as _,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        "vectors.adaptive_k",
+        "Number of nearest candidates whose bound is watched by adaptive early termination.",
+        "https://docs.pgvecto.rs/usage/search.html",
+        &ADAPTIVE_K,
+        1,
+        u16::MAX as _,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        "vectors.truncation_rerank_size",
+        "Reranker size of indexes built on truncated vectors.",
+        "https://docs.pgvecto.rs/usage/search.html",
+        &TRUNCATION_RERANK_SIZE,
+        1,
+        u16::MAX as _,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        "vectors.multivector_candidates",
+        "Number of sub-vectors fetched for each query vector of a multivector search. Rows with none of them are not returned.",
+        "https://docs.pgvecto.rs/usage/search.html",
+        &MULTIVECTOR_CANDIDATES,
+        1,
+        u16::MAX as _,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+}
+
+/// Search options of the session. Options that are not set are taken from the
+/// `search` options of the index by the background worker.
+pub fn search_options() -> PartialSearchOptions {
+    let set = explicit_settings();
+    let number = |name: &str, setting: &GucSetting<i32>| {
+        set.iter().any(|x| x == name).then(|| setting.get() as u32)
+    };
+    let flag = |name: &str, setting: &GucSetting<bool>| {
+        set.iter().any(|x| x == name).then(|| setting.get())
+    };
+    PartialSearchOptions {
+        sq_rerank_size: number("vectors.sq_rerank_size", &SQ_RERANK_SIZE),
+        sq_fast_scan: flag("vectors.sq_fast_scan", &SQ_FAST_SCAN),
+        pq_rerank_size: number("vectors.pq_rerank_size", &PQ_RERANK_SIZE),
+        pq_fast_scan: flag("vectors.pq_fast_scan", &PQ_FAST_SCAN),
+        rq_fast_scan: flag("vectors.rq_fast_scan", &RQ_FAST_SCAN),
+        rq_rerank_size: number("vectors.rq_rerank_size", &RQ_RERANK_SIZE),
+        bq_rerank_size: number("vectors.bq_rerank_size", &BQ_RERANK_SIZE),
+        ivf_nprobe: number("vectors.ivf_nprobe", &IVF_NPROBE),
+        ivf_coarse_nprobe: number("vectors.ivf_coarse_nprobe", &IVF_COARSE_NPROBE),
+        ivf_adaptive_patience: number("vectors.ivf_adaptive_patience", &IVF_ADAPTIVE_PATIENCE),
+        hnsw_ef_search: number("vectors.hnsw_ef_search", &HNSW_EF_SEARCH),
+        hnsw_adaptive_patience: number("vectors.hnsw_adaptive_patience", &HNSW_ADAPTIVE_PATIENCE),
+        adaptive_k: number("vectors.adaptive_k", &ADAPTIVE_K),
+        truncation_rerank_size: number("vectors.truncation_rerank_size", &TRUNCATION_RERANK_SIZE),
+        multivector_candidates: number("vectors.multivector_candidates", &MULTIVECTOR_CANDIDATES),
+    }
 }
 
-pub fn search_options() -> SearchOptions {
-    SearchOptions {
-        sq_rerank_size: SQ_RERANK_SIZE.get() as u32,
-        sq_fast_scan: SQ_FAST_SCAN.get(),
-        pq_rerank_size: PQ_RERANK_SIZE.get() as u32,
-        pq_fast_scan: PQ_FAST_SCAN.get(),
-        rq_fast_scan: RQ_FAST_SCAN.get(),
-        ivf_nprobe: IVF_NPROBE.get() as u32,
-        hnsw_ef_search: HNSW_EF_SEARCH.get() as u32,
+/// Names of `vectors.*` settings whose values do not come from their defaults,
+/// so that a setting to its default value still overrides the index.
+fn explicit_settings() -> Vec<String> {
+    use pgrx::pg_sys::{config_generic, GucSource};
+    use std::ffi::CStr;
+    let variables: &[*mut config_generic] = unsafe {
+        #[cfg(any(feature = "pg14", feature = "pg15"))]
+        let (variables, n) = (
+            pgrx::pg_sys::get_guc_variables(),
+            pgrx::pg_sys::GetNumConfigOptions(),
+        );
+        #[cfg(any(feature = "pg16", feature = "pg17"))]
+        let (variables, n) = {
+            let mut n = 0;
+            (pgrx::pg_sys::get_guc_variables(&mut n), n)
+        };
+        std::slice::from_raw_parts(variables, n as usize)
+    };
+    let mut result = Vec::new();
+    for &variable in variables {
+        let (name, source) = unsafe { (CStr::from_ptr((*variable).name), (*variable).source) };
+        if source == GucSource::PGC_S_DEFAULT {
+            continue;
+        }
+        if let Some(name) = name.to_str().ok().filter(|x| x.starts_with("vectors.")) {
+            result.push(name.to_string());
+        }
     }
+    result
 }
@@ -1,18 +1,21 @@
+use super::am_build;
 use super::am_options;
-use super::am_options::Opfamily;
 use super::am_scan;
 use crate::error::*;
+use crate::gucs::executing::search_options;
 use crate::gucs::planning::ENABLE_INDEX;
 use crate::index::am_scan::Scanner;
 use crate::index::catalog::{on_index_build, on_index_write};
+use crate::index::compatibility::vec_from_list;
 use crate::index::utils::from_oid_to_handle;
 use crate::index::utils::{ctid_to_pointer, pointer_to_ctid};
-use crate::ipc::{client, ClientRpc};
+use crate::ipc::client;
 use crate::utils::cells::PgCell;
 use am_options::Reloption;
 use base::index::*;
 use pgrx::datum::Internal;
 use pgrx::pg_sys::Datum;
+use pgrx::IntoDatum;
 
 static RELOPT_KIND_VECTORS: PgCell<pgrx::pg_sys::relopt_kind::Type> = unsafe { PgCell::new(0) };
 
@@ -49,6 +52,12 @@ const AM_HANDLER: pgrx::pg_sys::IndexAmRoutine = {
     am_routine.type_ = pgrx::pg_sys::NodeTag::T_IndexAmRoutine;
 
     am_routine.amcanorderbyop = true;
+    // The second column, if any, is a partition key.
+    am_routine.amcanmulticol = true;
+    #[cfg(feature = "pg17")]
+    {
+        am_routine.amcanbuildparallel = true;
+    }
 
     // Index access methods that set `amoptionalkey` to `false`
     // must index all tuples, even if the first column is `NULL`.
@@ -69,9 +78,11 @@ const AM_HANDLER: pgrx::pg_sys::IndexAmRoutine = {
     am_routine.ambulkdelete = Some(ambulkdelete);
     am_routine.amvacuumcleanup = Some(amvacuumcleanup);
 
+    am_routine.amcanreturn = Some(amcanreturn);
     am_routine.ambeginscan = Some(ambeginscan);
     am_routine.amrescan = Some(amrescan);
     am_routine.amgettuple = Some(amgettuple);
+    am_routine.amgetbitmap = Some(amgetbitmap);
     am_routine.amendscan = Some(amendscan);
 
     am_routine
@@ -79,7 +90,9 @@ const AM_HANDLER: pgrx::pg_sys::IndexAmRoutine = {
 
 #[pgrx::pg_guard]
 pub unsafe extern "C" fn amvalidate(opclass_oid: pgrx::pg_sys::Oid) -> bool {
-    if am_options::convert_opclass_to_vd(opclass_oid).is_some() {
+    if am_options::convert_opclass_to_vd(opclass_oid).is_some()
+        || am_options::is_partition_opclass(opclass_oid)
+    {
         pgrx::info!("Vector indexes can only be built on built-in operator classes.");
         true
     } else {
@@ -114,9 +127,11 @@ pub unsafe extern "C" fn amcostestimate(
     index_pages: *mut f64,
 ) {
     unsafe {
-        if ((*path).indexorderbys.is_null() && (*path).indexclauses.is_null())
-            || !ENABLE_INDEX.get()
-        {
+        // Clauses on the partition key only narrow a vector search.
+        let searches = vec_from_list::<pgrx::pg_sys::IndexClause>((*path).indexclauses)
+            .into_iter()
+            .any(|clause| (*clause).indexcol == 0);
+        if ((*path).indexorderbys.is_null() && !searches) || !ENABLE_INDEX.get() {
             *index_startup_cost = f64::MAX;
             *index_total_cost = f64::MAX;
             *index_selectivity = 0.0;
@@ -138,11 +153,6 @@ pub unsafe extern "C" fn ambuild(
     index: pgrx::pg_sys::Relation,
     index_info: *mut pgrx::pg_sys::IndexInfo,
 ) -> *mut pgrx::pg_sys::IndexBuildResult {
-    pub struct Builder {
-        pub opfamily: Opfamily,
-        pub rpc: ClientRpc,
-        pub result: *mut pgrx::pg_sys::IndexBuildResult,
-    }
     let oid = unsafe { (*index).rd_id };
     let handle = from_oid_to_handle(oid);
     let (options, alterable_options) = unsafe { am_options::options(index) };
@@ -159,60 +169,13 @@ pub unsafe extern "C" fn ambuild(
         Ok(()) => (),
         Err(StopError::NotExist) => pgrx::error!("internal error"),
     }
-    let result = unsafe { pgrx::pgbox::PgBox::<pgrx::pg_sys::IndexBuildResult>::alloc0() };
-    let mut builder = Builder {
-        opfamily,
-        rpc,
-        result: result.as_ptr(),
-    };
-    let table_am = unsafe { &*(*heap).rd_tableam };
+    let mut builder = am_build::Builder::new(opfamily, rpc);
     unsafe {
-        table_am.index_build_range_scan.unwrap()(
-            heap,
-            index,
-            index_info,
-            true,
-            false,
-            true,
-            0,
-            pgrx::pg_sys::InvalidBlockNumber,
-            Some(callback),
-            (&mut builder) as *mut Builder as *mut std::os::raw::c_void,
-            std::ptr::null_mut(),
-        );
-    }
-    #[pgrx::pg_guard]
-    unsafe extern "C" fn callback(
-        index: pgrx::pg_sys::Relation,
-        ctid: pgrx::pg_sys::ItemPointer,
-        values: *mut Datum,
-        is_null: *mut bool,
-        _tuple_is_alive: bool,
-        state: *mut std::os::raw::c_void,
-    ) {
-        let state = unsafe { &mut *state.cast::<Builder>() };
-        let vector = unsafe {
-            state
-                .opfamily
-                .datum_to_vector(*values.add(0), *is_null.add(0))
-        };
-        if let Some(vector) = vector {
-            let oid = unsafe { (*index).rd_id };
-            let handle = from_oid_to_handle(oid);
-            let pointer = ctid_to_pointer(unsafe { ctid.read() });
-            match state.rpc.insert(handle, vector, pointer) {
-                Ok(()) => (),
-                Err(InsertError::NotExist) => bad_service_not_exist(),
-                Err(InsertError::InvalidVector) => bad_service_invalid_vector(),
-            }
-            unsafe {
-                (*state.result).index_tuples += 1.0;
-            }
-        }
-        unsafe {
-            (*state.result).heap_tuples += 1.0;
-        }
+        am_build::build(heap, index, index_info, &mut builder);
     }
+    let mut result = unsafe { pgrx::pgbox::PgBox::<pgrx::pg_sys::IndexBuildResult>::alloc0() };
+    result.heap_tuples = builder.heap_tuples as f64;
+    result.index_tuples = builder.index_tuples as f64;
     let mut rpc = builder.rpc;
     match rpc.start(handle) {
         Ok(()) => (),
@@ -262,23 +225,35 @@ pub unsafe extern "C" fn aminsert(
     let opfamily = unsafe { am_options::opfamily(index) };
     let oid = unsafe { (*index).rd_id };
     let handle = from_oid_to_handle(oid);
-    let vector = unsafe { opfamily.datum_to_vector(*values.add(0), *is_null.add(0)) };
-    if let Some(vector) = vector {
+    let vectors = unsafe { opfamily.datum_to_vectors(*values.add(0), *is_null.add(0)) };
+    if !vectors.is_empty() {
         let pointer = ctid_to_pointer(unsafe { heap_tid.read() });
+        let partition = unsafe { am_options::partition_key(index) }
+            .and_then(|key| unsafe { key.datum_to_partition(*values.add(1), *is_null.add(1)) });
 
         on_index_write(handle);
 
         let mut rpc = check_client(client());
 
-        match rpc.insert(handle, vector, pointer) {
-            Ok(()) => (),
-            Err(InsertError::NotExist) => bad_service_not_exist(),
-            Err(InsertError::InvalidVector) => bad_service_invalid_vector(),
+        for vector in vectors {
+            match rpc.insert(handle, vector, pointer, partition) {
+                Ok(()) => (),
+                Err(InsertError::NotExist) => bad_service_not_exist(),
+                Err(InsertError::InvalidVector) => bad_service_invalid_vector(),
+            }
         }
     }
     false
 }
 
+#[pgrx::pg_guard]
+pub unsafe extern "C" fn amcanreturn(
+    index: pgrx::pg_sys::Relation,
+    attno: std::os::raw::c_int,
+) -> bool {
+    attno == 1 && unsafe { am_options::opfamily(index).returnable() }
+}
+
 #[pgrx::pg_guard]
 pub unsafe extern "C" fn ambeginscan(
     index: pgrx::pg_sys::Relation,
@@ -289,7 +264,18 @@ pub unsafe extern "C" fn ambeginscan(
 
     let scan = unsafe { pgrx::pg_sys::RelationGetIndexScan(index, n_keys, n_orderbys) };
     unsafe {
-        let scanner = am_scan::scan_make(None, None, false);
+        // PostgreSQL reads distances returned by an index scan only if it
+        // rechecks the order, which is only needed by multivector indexes.
+        if n_orderbys > 0 && am_options::opfamily(index).is_multivector() {
+            let n = n_orderbys as usize;
+            (*scan).xs_orderbyvals = pgrx::pg_sys::palloc0(n * size_of::<Datum>()).cast::<Datum>();
+            (*scan).xs_orderbynulls = pgrx::pg_sys::palloc(n * size_of::<bool>()).cast::<bool>();
+            std::ptr::write_bytes((*scan).xs_orderbynulls, 1, n);
+        }
+        // Vectors returned by index-only scans may be larger than an index
+        // tuple, so they are returned as heap tuples.
+        (*scan).xs_hitupdesc = (*index).rd_att;
+        let scanner = am_scan::scan_make(None, None, false, search_options(), None, false);
         (*scan).opaque = CurrentMemoryContext.leak_and_drop_on_delete(scanner).cast();
     }
     scan
@@ -311,9 +297,50 @@ pub unsafe extern "C" fn amrescan(
             std::ptr::copy(orderbys, (*scan).orderByData, (*scan).numberOfOrderBys as _);
         }
         let opfamily = am_options::opfamily((*scan).indexRelation);
-        let (orderbys, spheres) = {
+        if opfamily.is_multivector() {
+            if (*scan).numberOfOrderBys == 0 && (*scan).numberOfKeys == 0 {
+                pgrx::error!(
+                    "multivector search with no WHERE clause and no ORDER BY clause is not supported"
+                );
+            }
             let mut orderbys = Vec::new();
             let mut spheres = Vec::new();
+            for i in 0..(*scan).numberOfOrderBys {
+                let data = (*scan).orderByData.add(i as usize);
+                let value = (*data).sk_argument;
+                let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
+                orderbys.push(opfamily.datum_to_vectors(value, is_null));
+            }
+            for i in 0..(*scan).numberOfKeys {
+                let data = (*scan).keyData.add(i as usize);
+                let value = (*data).sk_argument;
+                let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
+                match (*data).sk_strategy {
+                    2 => spheres.push(opfamily.datum_to_multivector_sphere(value, is_null)),
+                    4 => (),
+                    _ => unreachable!(),
+                }
+            }
+            let (vectors, threshold, recheck) = am_scan::scan_build_multi(orderbys, spheres);
+            let (partitions, partition_recheck) = scan_partitions(scan);
+            let scanner = (*scan).opaque.cast::<Scanner>().as_mut().unwrap_unchecked();
+            let scanner = std::mem::replace(
+                scanner,
+                am_scan::scan_make_multi(
+                    vectors,
+                    opfamily,
+                    threshold,
+                    partitions,
+                    recheck || partition_recheck,
+                ),
+            );
+            am_scan::scan_release(scanner);
+            return;
+        }
+        let (orderbys, spheres, searches) = {
+            let mut orderbys = Vec::new();
+            let mut spheres = Vec::new();
+            let mut searches = Vec::new();
             if (*scan).numberOfOrderBys == 0 && (*scan).numberOfKeys == 0 {
                 pgrx::error!(
                     "vector search with no WHERE clause and no ORDER BY clause is not supported"
@@ -325,6 +352,7 @@ pub unsafe extern "C" fn amrescan(
                 let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
                 match (*data).sk_strategy {
                     1 => orderbys.push(opfamily.datum_to_vector(value, is_null)),
+                    3 => searches.push(opfamily.datum_to_search(value, is_null)),
                     _ => unreachable!(),
                 }
             }
@@ -334,18 +362,57 @@ pub unsafe extern "C" fn amrescan(
                 let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
                 match (*data).sk_strategy {
                     2 => spheres.push(opfamily.datum_to_sphere(value, is_null)),
+                    4 => (),
                     _ => unreachable!(),
                 }
             }
-            (orderbys, spheres)
+            (orderbys, spheres, searches)
         };
-        let (vector, threshold, recheck) = am_scan::scan_build(orderbys, spheres, opfamily);
+        let (vector, threshold, recheck, opts) =
+            am_scan::scan_build(orderbys, spheres, searches, opfamily);
+        let (partitions, lossy) = scan_partitions(scan);
         let scanner = (*scan).opaque.cast::<Scanner>().as_mut().unwrap_unchecked();
-        let scanner = std::mem::replace(scanner, am_scan::scan_make(vector, threshold, recheck));
+        let scanner = std::mem::replace(
+            scanner,
+            am_scan::scan_make(
+                vector,
+                threshold,
+                recheck || lossy,
+                opts,
+                partitions,
+                (*scan).xs_want_itup,
+            ),
+        );
         am_scan::scan_release(scanner);
     }
 }
 
+/// Partitions to search, from equality clauses on the partition key, and
+/// whether rows of them are rechecked.
+unsafe fn scan_partitions(scan: pgrx::pg_sys::IndexScanDesc) -> (Option<Vec<u64>>, bool) {
+    unsafe {
+        let Some(key) = am_options::partition_key((*scan).indexRelation) else {
+            return (None, false);
+        };
+        let mut partitions: Option<Vec<u64>> = None;
+        for i in 0..(*scan).numberOfKeys {
+            let data = (*scan).keyData.add(i as usize);
+            if (*data).sk_strategy != 4 {
+                continue;
+            }
+            let value = (*data).sk_argument;
+            let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
+            let partition = key.datum_to_partition(value, is_null);
+            partitions = Some(match partitions {
+                Some(p) => p.into_iter().filter(|&x| Some(x) == partition).collect(),
+                None => partition.into_iter().collect(),
+            });
+        }
+        let recheck = partitions.is_some() && key.is_lossy();
+        (partitions, recheck)
+    }
+}
+
 #[pgrx::pg_guard]
 pub unsafe extern "C" fn amgettuple(
     scan: pgrx::pg_sys::IndexScanDesc,
@@ -365,11 +432,42 @@ pub unsafe extern "C" fn amgettuple(
     let scanner = unsafe { (*scan).opaque.cast::<Scanner>().as_mut().unwrap_unchecked() };
     let oid = unsafe { (*(*scan).indexRelation).rd_id };
     let handle = from_oid_to_handle(oid);
-    if let Some((pointer, recheck)) = am_scan::scan_next(scanner, handle) {
+    unsafe {
+        if !(*scan).xs_hitup.is_null() {
+            pgrx::pg_sys::heap_freetuple((*scan).xs_hitup);
+            (*scan).xs_hitup = std::ptr::null_mut();
+        }
+    }
+    let opfamily = unsafe { am_options::opfamily((*scan).indexRelation) };
+    if let Some((pointer, distance, vector, recheck)) = am_scan::scan_next(scanner, handle) {
         let ctid = pointer_to_ctid(pointer);
         unsafe {
             (*scan).xs_heaptid = ctid;
-            (*scan).xs_recheckorderby = false;
+            if let Some(vector) = vector {
+                // The partition key, if any, is not returned.
+                let natts = (*(*scan).xs_hitupdesc).natts as usize;
+                let mut values = vec![Datum::from(0); natts];
+                let mut is_null = vec![true; natts];
+                values[0] = opfamily.vector_to_datum(vector);
+                is_null[0] = false;
+                (*scan).xs_hitup = pgrx::pg_sys::heap_form_tuple(
+                    (*scan).xs_hitupdesc,
+                    values.as_mut_ptr(),
+                    is_null.as_mut_ptr(),
+                );
+                pgrx::pg_sys::pfree(values[0].cast_mut_ptr());
+            }
+            // Every index, quantized or not, reranks its candidates against the
+            // full-precision vectors, so the order is exact and distances are
+            // not returned. Multivector rows are ranked by a lower bound of
+            // their distances, so PostgreSQL reorders them on exact distances.
+            if opfamily.is_multivector() {
+                for i in 0..(*scan).numberOfOrderBys as usize {
+                    *(*scan).xs_orderbyvals.add(i) = distance.into_datum().unwrap();
+                    *(*scan).xs_orderbynulls.add(i) = false;
+                }
+            }
+            (*scan).xs_recheckorderby = opfamily.is_multivector();
             (*scan).xs_recheck = recheck;
         }
         true
@@ -378,11 +476,40 @@ pub unsafe extern "C" fn amgettuple(
     }
 }
 
+#[pgrx::pg_guard]
+pub unsafe extern "C" fn amgetbitmap(
+    scan: pgrx::pg_sys::IndexScanDesc,
+    tbm: *mut pgrx::pg_sys::TIDBitmap,
+) -> i64 {
+    if unsafe { (*(*scan).xs_snapshot).snapshot_type } != pgrx::pg_sys::SnapshotType::SNAPSHOT_MVCC
+    {
+        pgrx::error!("scanning with a non-MVCC-compliant snapshot is not supported");
+    }
+    let scanner = unsafe { (*scan).opaque.cast::<Scanner>().as_mut().unwrap_unchecked() };
+    let oid = unsafe { (*(*scan).indexRelation).rd_id };
+    let handle = from_oid_to_handle(oid);
+    let mut count = 0_i64;
+    am_scan::scan_range(scanner, handle, |pointers, recheck| {
+        let mut ctids = pointers
+            .into_iter()
+            .map(pointer_to_ctid)
+            .collect::<Vec<_>>();
+        unsafe {
+            pgrx::pg_sys::tbm_add_tuples(tbm, ctids.as_mut_ptr(), ctids.len() as _, recheck);
+        }
+        count += ctids.len() as i64;
+    });
+    count
+}
+
 #[pgrx::pg_guard]
 pub unsafe extern "C" fn amendscan(scan: pgrx::pg_sys::IndexScanDesc) {
     unsafe {
         let scanner = (*scan).opaque.cast::<Scanner>().as_mut().unwrap_unchecked();
-        let scanner = std::mem::replace(scanner, am_scan::scan_make(None, None, false));
+        let scanner = std::mem::replace(
+            scanner,
+            am_scan::scan_make(None, None, false, search_options(), None, false),
+        );
         am_scan::scan_release(scanner);
     }
 }
@@ -0,0 +1,258 @@
+use super::am_options;
+use super::am_options::Opfamily;
+use crate::error::*;
+use crate::index::utils::ctid_to_pointer;
+use crate::index::utils::from_oid_to_handle;
+use crate::ipc::{client, ClientRpc};
+use base::index::*;
+use pgrx::pg_sys::Datum;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const KEY_SHARED: u64 = 0xA000000000000001;
+const KEY_SCAN: u64 = 0xA000000000000002;
+
+pub struct Builder {
+    pub opfamily: Opfamily,
+    pub rpc: ClientRpc,
+    pub heap_tuples: u64,
+    pub index_tuples: u64,
+}
+
+impl Builder {
+    pub fn new(opfamily: Opfamily, rpc: ClientRpc) -> Self {
+        Self {
+            opfamily,
+            rpc,
+            heap_tuples: 0,
+            index_tuples: 0,
+        }
+    }
+}
+
+#[repr(C)]
+struct Shared {
+    heap: pgrx::pg_sys::Oid,
+    index: pgrx::pg_sys::Oid,
+    is_concurrent: bool,
+    heap_tuples: AtomicU64,
+    index_tuples: AtomicU64,
+}
+
+/// Scans the heap and sends every tuple to the background worker. The heap
+/// scan is split across parallel workers if PostgreSQL planned any for
+/// this build.
+pub unsafe fn build(
+    heap: pgrx::pg_sys::Relation,
+    index: pgrx::pg_sys::Relation,
+    index_info: *mut pgrx::pg_sys::IndexInfo,
+    builder: &mut Builder,
+) {
+    let workers = unsafe { workers(heap, index, index_info) };
+    if workers > 0 && unsafe { build_parallel(heap, index, index_info, builder, workers) } {
+        return;
+    }
+    unsafe {
+        build_scan(heap, index, index_info, std::ptr::null_mut(), true, builder);
+    }
+}
+
+#[cfg(feature = "pg17")]
+unsafe fn workers(
+    _heap: pgrx::pg_sys::Relation,
+    _index: pgrx::pg_sys::Relation,
+    index_info: *mut pgrx::pg_sys::IndexInfo,
+) -> i32 {
+    unsafe { (*index_info).ii_ParallelWorkers }
+}
+
+/// Before PostgreSQL 17, `index_build` plans parallel workers only for B-tree
+/// indexes, so they are planned here in the same way.
+#[cfg(not(feature = "pg17"))]
+unsafe fn workers(
+    heap: pgrx::pg_sys::Relation,
+    index: pgrx::pg_sys::Relation,
+    _index_info: *mut pgrx::pg_sys::IndexInfo,
+) -> i32 {
+    use pgrx::pg_sys::ProcessingMode::NormalProcessing;
+    unsafe {
+        if pgrx::pg_sys::Mode != NormalProcessing {
+            return 0;
+        }
+        pgrx::pg_sys::plan_create_index_workers((*heap).rd_id, (*index).rd_id)
+    }
+}
+
+unsafe fn build_scan(
+    heap: pgrx::pg_sys::Relation,
+    index: pgrx::pg_sys::Relation,
+    index_info: *mut pgrx::pg_sys::IndexInfo,
+    scan: pgrx::pg_sys::TableScanDesc,
+    progress: bool,
+    builder: &mut Builder,
+) {
+    let table_am = unsafe { &*(*heap).rd_tableam };
+    unsafe {
+        table_am.index_build_range_scan.unwrap()(
+            heap,
+            index,
+            index_info,
+            true,
+            false,
+            progress,
+            0,
+            pgrx::pg_sys::InvalidBlockNumber,
+            Some(callback),
+            (builder as *mut Builder).cast(),
+            scan,
+        );
+    }
+}
+
+#[pgrx::pg_guard]
+unsafe extern "C" fn callback(
+    index: pgrx::pg_sys::Relation,
+    ctid: pgrx::pg_sys::ItemPointer,
+    values: *mut Datum,
+    is_null: *mut bool,
+    _tuple_is_alive: bool,
+    state: *mut std::os::raw::c_void,
+) {
+    let state = unsafe { &mut *state.cast::<Builder>() };
+    let vectors = unsafe {
+        state
+            .opfamily
+            .datum_to_vectors(*values.add(0), *is_null.add(0))
+    };
+    if !vectors.is_empty() {
+        let oid = unsafe { (*index).rd_id };
+        let handle = from_oid_to_handle(oid);
+        let pointer = ctid_to_pointer(unsafe { ctid.read() });
+        let partition = unsafe { am_options::partition_key(index) }
+            .and_then(|key| unsafe { key.datum_to_partition(*values.add(1), *is_null.add(1)) });
+        for vector in vectors {
+            match state.rpc.insert(handle, vector, pointer, partition) {
+                Ok(()) => (),
+                Err(InsertError::NotExist) => bad_service_not_exist(),
+                Err(InsertError::InvalidVector) => bad_service_invalid_vector(),
+            }
+        }
+        state.index_tuples += 1;
+    }
+    state.heap_tuples += 1;
+}
+
+unsafe fn build_parallel(
+    heap: pgrx::pg_sys::Relation,
+    index: pgrx::pg_sys::Relation,
+    index_info: *mut pgrx::pg_sys::IndexInfo,
+    builder: &mut Builder,
+    workers: i32,
+) -> bool {
+    use pgrx::pg_sys::*;
+    unsafe {
+        EnterParallelMode();
+        let pcxt = CreateParallelContext(
+            c"vectors".as_ptr(),
+            c"_vectors_parallel_build_main".as_ptr(),
+            workers,
+        );
+        let is_concurrent = (*index_info).ii_Concurrent;
+        let snapshot = if is_concurrent {
+            RegisterSnapshot(GetTransactionSnapshot())
+        } else {
+            &raw mut SnapshotAnyData
+        };
+        let scan_size = table_parallelscan_estimate(heap, snapshot);
+        estimate_chunk(pcxt, size_of::<Shared>());
+        estimate_chunk(pcxt, scan_size);
+        estimate_keys(pcxt, 2);
+        InitializeParallelDSM(pcxt);
+        if (*pcxt).seg.is_null() {
+            // no dynamic shared memory is available, so fall back to a serial build
+            if is_concurrent {
+                UnregisterSnapshot(snapshot);
+            }
+            DestroyParallelContext(pcxt);
+            ExitParallelMode();
+            return false;
+        }
+        let shared = shm_toc_allocate((*pcxt).toc, size_of::<Shared>()).cast::<Shared>();
+        shared.write(Shared {
+            heap: (*heap).rd_id,
+            index: (*index).rd_id,
+            is_concurrent,
+            heap_tuples: AtomicU64::new(0),
+            index_tuples: AtomicU64::new(0),
+        });
+        let pscan = shm_toc_allocate((*pcxt).toc, scan_size).cast::<ParallelTableScanDescData>();
+        table_parallelscan_initialize(heap, pscan, snapshot);
+        shm_toc_insert((*pcxt).toc, KEY_SHARED, shared.cast());
+        shm_toc_insert((*pcxt).toc, KEY_SCAN, pscan.cast());
+        LaunchParallelWorkers(pcxt);
+        // the leader participates in the heap scan as well, unless it's
+        // disabled and any worker is launched
+        if parallel_leader_participation || (*pcxt).nworkers_launched == 0 {
+            let scan = table_beginscan_parallel(heap, pscan);
+            build_scan(heap, index, index_info, scan, true, builder);
+        }
+        WaitForParallelWorkersToFinish(pcxt);
+        builder.heap_tuples += (*shared).heap_tuples.load(Ordering::Relaxed);
+        builder.index_tuples += (*shared).index_tuples.load(Ordering::Relaxed);
+        if is_concurrent {
+            UnregisterSnapshot(snapshot);
+        }
+        DestroyParallelContext(pcxt);
+        ExitParallelMode();
+    }
+    true
+}
+
+#[pgrx::pg_guard]
+#[no_mangle]
+pub unsafe extern "C" fn _vectors_parallel_build_main(
+    _seg: *mut pgrx::pg_sys::dsm_segment,
+    toc: *mut pgrx::pg_sys::shm_toc,
+) {
+    use pgrx::pg_sys::*;
+    unsafe {
+        let shared = shm_toc_lookup(toc, KEY_SHARED, false).cast::<Shared>();
+        let pscan = shm_toc_lookup(toc, KEY_SCAN, false).cast::<ParallelTableScanDescData>();
+        let (heap_lockmode, index_lockmode) = if (*shared).is_concurrent {
+            (ShareUpdateExclusiveLock, RowExclusiveLock)
+        } else {
+            (ShareLock, AccessExclusiveLock)
+        };
+        let heap = table_open((*shared).heap, heap_lockmode as _);
+        let index = index_open((*shared).index, index_lockmode as _);
+        let index_info = BuildIndexInfo(index);
+        (*index_info).ii_Concurrent = (*shared).is_concurrent;
+        let opfamily = am_options::opfamily(index);
+        let mut builder = Builder::new(opfamily, check_client(client()));
+        let scan = table_beginscan_parallel(heap, pscan);
+        build_scan(heap, index, index_info, scan, false, &mut builder);
+        (*shared)
+            .heap_tuples
+            .fetch_add(builder.heap_tuples, Ordering::Relaxed);
+        (*shared)
+            .index_tuples
+            .fetch_add(builder.index_tuples, Ordering::Relaxed);
+        index_close(index, index_lockmode as _);
+        table_close(heap, heap_lockmode as _);
+    }
+}
+
+// `shm_toc_estimate_chunk` and `shm_toc_estimate_keys` are macros in C.
+
+unsafe fn estimate_chunk(pcxt: *mut pgrx::pg_sys::ParallelContext, size: usize) {
+    // BUFFERALIGN
+    const ALIGNOF_BUFFER: usize = 32;
+    unsafe {
+        (*pcxt).estimator.space_for_chunks += size.next_multiple_of(ALIGNOF_BUFFER);
+    }
+}
+
+unsafe fn estimate_keys(pcxt: *mut pgrx::pg_sys::ParallelContext, keys: usize) {
+    unsafe {
+        (*pcxt).estimator.number_of_keys += keys;
+    }
+}
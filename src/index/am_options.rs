@@ -1,7 +1,11 @@
 use crate::datatype::memory_bvector::BVectorInput;
 use crate::datatype::memory_bvector::BVectorOutput;
+use crate::datatype::memory_svecf16::SVecf16Input;
+use crate::datatype::memory_svecf16::SVecf16Output;
 use crate::datatype::memory_svecf32::SVecf32Input;
 use crate::datatype::memory_svecf32::SVecf32Output;
+use crate::datatype::memory_vecbf16::Vecbf16Input;
+use crate::datatype::memory_vecbf16::Vecbf16Output;
 use crate::datatype::memory_vecf16::Vecf16Input;
 use crate::datatype::memory_vecf16::Vecf16Output;
 use crate::datatype::memory_vecf32::Vecf32Input;
@@ -11,8 +15,11 @@ use crate::error::*;
 use base::distance::*;
 use base::index::*;
 use base::vector::*;
+use pgrx::datum::Array;
 use pgrx::datum::FromDatum;
+use pgrx::datum::IntoDatum;
 use pgrx::heap_tuple::PgHeapTuple;
+use pgrx::AllocatedByPostgres;
 use serde::Deserialize;
 use std::ffi::CStr;
 use std::num::NonZero;
@@ -48,6 +55,8 @@ pub enum PgDistanceKind {
     Cos,
     Hamming,
     Jaccard,
+    MaxSim,
+    Chamfer,
 }
 
 impl PgDistanceKind {
@@ -58,6 +67,8 @@ impl PgDistanceKind {
             PgDistanceKind::Cos => DistanceKind::Dot,
             PgDistanceKind::Hamming => DistanceKind::Hamming,
             PgDistanceKind::Jaccard => DistanceKind::Jaccard,
+            PgDistanceKind::MaxSim => DistanceKind::Dot,
+            PgDistanceKind::Chamfer => DistanceKind::L2,
         }
     }
 }
@@ -80,6 +91,22 @@ pub fn convert_opclass_to_vd(
     None
 }
 
+pub fn is_partition_opclass(opclass_oid: pgrx::pg_sys::Oid) -> bool {
+    let namespace =
+        pgrx::pg_catalog::PgNamespace::search_namespacename(crate::SCHEMA_C_STR).unwrap();
+    let namespace = namespace.get().expect("pgvecto.rs is not installed.");
+    let opclass = pgrx::pg_catalog::PgOpclass::search_claoid(opclass_oid).unwrap();
+    let opclass = opclass.get().expect("pg_catalog is broken.");
+    opclass.opcnamespace() == namespace.oid()
+        && matches!(
+            opclass.opcname().to_str(),
+            Ok("int2_partition_ops"
+                | "int4_partition_ops"
+                | "int8_partition_ops"
+                | "text_partition_ops")
+        )
+}
+
 pub fn convert_opfamily_to_vd(
     opfamily_oid: pgrx::pg_sys::Oid,
 ) -> Option<(VectorKind, PgDistanceKind)> {
@@ -106,24 +133,44 @@ fn convert_name_to_vd(name: &str) -> Option<(VectorKind, PgDistanceKind)> {
         Some("vecf16_l2") => Some((VectorKind::Vecf16, PgDistanceKind::L2)),
         Some("vecf16_dot") => Some((VectorKind::Vecf16, PgDistanceKind::Dot)),
         Some("vecf16_cos") => Some((VectorKind::Vecf16, PgDistanceKind::Cos)),
+        Some("vecbf16_l2") => Some((VectorKind::Vecbf16, PgDistanceKind::L2)),
+        Some("vecbf16_dot") => Some((VectorKind::Vecbf16, PgDistanceKind::Dot)),
+        Some("vecbf16_cos") => Some((VectorKind::Vecbf16, PgDistanceKind::Cos)),
         Some("svector_l2") => Some((VectorKind::SVecf32, PgDistanceKind::L2)),
         Some("svector_dot") => Some((VectorKind::SVecf32, PgDistanceKind::Dot)),
         Some("svector_cos") => Some((VectorKind::SVecf32, PgDistanceKind::Cos)),
+        Some("svecf16_l2") => Some((VectorKind::SVecf16, PgDistanceKind::L2)),
+        Some("svecf16_dot") => Some((VectorKind::SVecf16, PgDistanceKind::Dot)),
+        Some("svecf16_cos") => Some((VectorKind::SVecf16, PgDistanceKind::Cos)),
         Some("bvector_hamming") => Some((VectorKind::BVector, PgDistanceKind::Hamming)),
         Some("bvector_dot") => Some((VectorKind::BVector, PgDistanceKind::Dot)),
         Some("bvector_jaccard") => Some((VectorKind::BVector, PgDistanceKind::Jaccard)),
+        Some("vector_maxsim") => Some((VectorKind::Vecf32, PgDistanceKind::MaxSim)),
+        Some("vector_chamfer") => Some((VectorKind::Vecf32, PgDistanceKind::Chamfer)),
         _ => None,
     }
 }
 
 unsafe fn convert_reloptions_to_options(
     reloptions: *const pgrx::pg_sys::varlena,
-) -> (IndexingOptions, IndexAlterableOptions) {
+) -> (
+    IndexingOptions,
+    Option<TruncationOptions>,
+    Option<u64>,
+    Option<PartitionOptions>,
+    IndexAlterableOptions,
+) {
     #[derive(Debug, Clone, Deserialize, Default)]
     #[serde(deny_unknown_fields)]
     struct Parsed {
         #[serde(default)]
         indexing: IndexingOptions,
+        #[serde(default)]
+        truncation: Option<TruncationOptions>,
+        #[serde(default)]
+        seed: Option<u64>,
+        #[serde(default)]
+        partition: Option<PartitionOptions>,
         #[serde(flatten)]
         alterable: IndexAlterableOptions,
     }
@@ -133,7 +180,7 @@ unsafe fn convert_reloptions_to_options(
     }
     let s = unsafe { (*reloption).options() }.to_string_lossy();
     match toml::from_str::<Parsed>(&s) {
-        Ok(p) => (p.indexing, p.alterable),
+        Ok(p) => (p.indexing, p.truncation, p.seed, p.partition, p.alterable),
         Err(e) => pgrx::error!("failed to parse options: {}", e),
     }
 }
@@ -145,22 +192,103 @@ pub unsafe fn options(index: pgrx::pg_sys::Relation) -> (IndexOptions, IndexAlte
     if atts.is_empty() {
         pgrx::error!("indexing on no columns is not supported");
     }
-    if atts.len() != 1 {
-        pgrx::error!("multicolumn index is not supported");
+    if atts.len() > 2 {
+        pgrx::error!("multicolumn index with more than a partition key is not supported");
     }
+    // get v, d
+    let Some((v, pg_d)) = convert_opfamily_to_vd(opfamily) else {
+        pgrx::error!("the first column of a vector index must be a vector");
+    };
     // get dims
     let typmod = Typmod::parse_from_i32(atts[0].type_mod()).unwrap();
     let dims = check_column_dims(typmod.dims()).get();
-    // get v, d
-    let (v, pg_d) = convert_opfamily_to_vd(opfamily).unwrap();
     let vector = VectorOptions {
         dims,
         v,
         d: pg_d.to_distance(),
     };
-    // get indexing, segment, optimizing
-    let (indexing, alterable) = unsafe { convert_reloptions_to_options((*index).rd_options) };
-    (IndexOptions { vector, indexing }, alterable)
+    // get partition key
+    let key = match atts.get(1) {
+        Some(att) => match PartitionKey::from_type(att.atttypid) {
+            Some(key) => Some(key),
+            None => pgrx::error!("partition key must be of type int2, int4, int8 or text"),
+        },
+        None => None,
+    };
+    // get indexing, truncation, segment, optimizing
+    let (indexing, truncation, seed, partition, alterable) =
+        unsafe { convert_reloptions_to_options((*index).rd_options) };
+    if key.is_none() && partition.is_some() {
+        pgrx::error!("partition options are given without a partition key column");
+    }
+    (
+        IndexOptions {
+            vector,
+            indexing,
+            truncation,
+            seed,
+            partition: key.map(|_| partition.unwrap_or_default()),
+        },
+        alterable,
+    )
+}
+
+/// Type of the partition key, the optional second column of a vector index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionKey {
+    Int2,
+    Int4,
+    Int8,
+    Text,
+}
+
+impl PartitionKey {
+    fn from_type(oid: pgrx::pg_sys::Oid) -> Option<Self> {
+        use pgrx::pg_sys::{INT2OID, INT4OID, INT8OID, TEXTOID};
+        match oid {
+            x if x == INT2OID => Some(PartitionKey::Int2),
+            x if x == INT4OID => Some(PartitionKey::Int4),
+            x if x == INT8OID => Some(PartitionKey::Int8),
+            x if x == TEXTOID => Some(PartitionKey::Text),
+            _ => None,
+        }
+    }
+    pub unsafe fn datum_to_partition(
+        self,
+        datum: pgrx::pg_sys::Datum,
+        is_null: bool,
+    ) -> Option<u64> {
+        if is_null {
+            return None;
+        }
+        match self {
+            PartitionKey::Int2 => unsafe { i16::from_datum(datum, false) }.map(|x| x as i64 as u64),
+            PartitionKey::Int4 => unsafe { i32::from_datum(datum, false) }.map(|x| x as i64 as u64),
+            PartitionKey::Int8 => unsafe { i64::from_datum(datum, false) }.map(|x| x as u64),
+            PartitionKey::Text => {
+                unsafe { <&str>::from_datum(datum, false) }.map(|x| fnv1a(x.as_bytes()))
+            }
+        }
+    }
+    /// Text keys are hashed, so rows of different keys may share a partition.
+    pub fn is_lossy(self) -> bool {
+        matches!(self, PartitionKey::Text)
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325_u64;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+pub unsafe fn partition_key(index: pgrx::pg_sys::Relation) -> Option<PartitionKey> {
+    let att = unsafe { &*(*index).rd_att };
+    let atts = unsafe { att.attrs.as_slice(att.natts as _) };
+    PartitionKey::from_type(atts.get(1)?.atttypid)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -187,10 +315,18 @@ impl Opfamily {
                 let vector = unsafe { Vecf16Input::from_datum(datum, false).unwrap() };
                 self.preprocess(BorrowedVector::Vecf16(vector.as_borrowed()))
             }
+            VectorKind::Vecbf16 => {
+                let vector = unsafe { Vecbf16Input::from_datum(datum, false).unwrap() };
+                self.preprocess(BorrowedVector::Vecbf16(vector.as_borrowed()))
+            }
             VectorKind::SVecf32 => {
                 let vector = unsafe { SVecf32Input::from_datum(datum, false).unwrap() };
                 self.preprocess(BorrowedVector::SVecf32(vector.as_borrowed()))
             }
+            VectorKind::SVecf16 => {
+                let vector = unsafe { SVecf16Input::from_datum(datum, false).unwrap() };
+                self.preprocess(BorrowedVector::SVecf16(vector.as_borrowed()))
+            }
             VectorKind::BVector => {
                 let vector = unsafe { BVectorInput::from_datum(datum, false).unwrap() };
                 self.preprocess(BorrowedVector::BVector(vector.as_borrowed()))
@@ -198,6 +334,29 @@ impl Opfamily {
         };
         Some(vector)
     }
+    /// Every sub-vector of a multivector is indexed as a vector with the same
+    /// pointer, while a vector is indexed as itself.
+    pub unsafe fn datum_to_vectors(
+        self,
+        datum: pgrx::pg_sys::Datum,
+        is_null: bool,
+    ) -> Vec<OwnedVector> {
+        if !self.is_multivector() {
+            return unsafe { self.datum_to_vector(datum, is_null) }
+                .into_iter()
+                .collect();
+        }
+        if is_null || datum.is_null() {
+            return Vec::new();
+        }
+        // multivectors are arrays of `vector`
+        let array = unsafe { Array::<Vecf32Output>::from_datum(datum, false).unwrap() };
+        array
+            .iter()
+            .flatten()
+            .map(|vector| self.preprocess(BorrowedVector::Vecf32(vector.as_borrowed())))
+            .collect()
+    }
     pub unsafe fn datum_to_sphere(
         self,
         datum: pgrx::pg_sys::Datum,
@@ -207,7 +366,31 @@ impl Opfamily {
             return (None, None);
         }
         let tuple = unsafe { PgHeapTuple::from_composite_datum(datum) };
-        let center = match self.vector {
+        let center = self.composite_to_vector(&tuple);
+        let radius = tuple.get_by_index::<f32>(NonZero::new(2).unwrap()).unwrap();
+        (center, radius)
+    }
+    /// A query vector with inline search options, as a `search_*` composite.
+    pub unsafe fn datum_to_search(
+        self,
+        datum: pgrx::pg_sys::Datum,
+        is_null: bool,
+    ) -> (Option<OwnedVector>, Option<String>) {
+        if is_null || datum.is_null() {
+            return (None, None);
+        }
+        let tuple = unsafe { PgHeapTuple::from_composite_datum(datum) };
+        let query = self.composite_to_vector(&tuple);
+        let options = tuple
+            .get_by_index::<String>(NonZero::new(2).unwrap())
+            .unwrap();
+        (query, options)
+    }
+    fn composite_to_vector(
+        self,
+        tuple: &PgHeapTuple<'_, AllocatedByPostgres>,
+    ) -> Option<OwnedVector> {
+        match self.vector {
             VectorKind::Vecf32 => tuple
                 .get_by_index::<Vecf32Output>(NonZero::new(1).unwrap())
                 .unwrap()
@@ -216,18 +399,67 @@ impl Opfamily {
                 .get_by_index::<Vecf16Output>(NonZero::new(1).unwrap())
                 .unwrap()
                 .map(|vector| self.preprocess(BorrowedVector::Vecf16(vector.as_borrowed()))),
+            VectorKind::Vecbf16 => tuple
+                .get_by_index::<Vecbf16Output>(NonZero::new(1).unwrap())
+                .unwrap()
+                .map(|vector| self.preprocess(BorrowedVector::Vecbf16(vector.as_borrowed()))),
             VectorKind::SVecf32 => tuple
                 .get_by_index::<SVecf32Output>(NonZero::new(1).unwrap())
                 .unwrap()
                 .map(|vector| self.preprocess(BorrowedVector::SVecf32(vector.as_borrowed()))),
+            VectorKind::SVecf16 => tuple
+                .get_by_index::<SVecf16Output>(NonZero::new(1).unwrap())
+                .unwrap()
+                .map(|vector| self.preprocess(BorrowedVector::SVecf16(vector.as_borrowed()))),
             VectorKind::BVector => tuple
                 .get_by_index::<BVectorOutput>(NonZero::new(1).unwrap())
                 .unwrap()
                 .map(|vector| self.preprocess(BorrowedVector::BVector(vector.as_borrowed()))),
-        };
+        }
+    }
+    /// The center and the radius of a `sphere_multivector` composite.
+    pub unsafe fn datum_to_multivector_sphere(
+        self,
+        datum: pgrx::pg_sys::Datum,
+        is_null: bool,
+    ) -> (Vec<OwnedVector>, Option<f32>) {
+        if is_null || datum.is_null() {
+            return (Vec::new(), None);
+        }
+        let tuple = unsafe { PgHeapTuple::from_composite_datum(datum) };
+        let center = tuple
+            .get_by_index::<Vec<Option<Vecf32Output>>>(NonZero::new(1).unwrap())
+            .unwrap()
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(|vector| self.preprocess(BorrowedVector::Vecf32(vector.as_borrowed())))
+            .collect();
         let radius = tuple.get_by_index::<f32>(NonZero::new(2).unwrap()).unwrap();
         (center, radius)
     }
+    pub fn vector_to_datum(self, vector: OwnedVector) -> pgrx::pg_sys::Datum {
+        let datum = match vector {
+            OwnedVector::Vecf32(x) => Vecf32Output::new(x.as_borrowed()).into_datum(),
+            OwnedVector::Vecf16(x) => Vecf16Output::new(x.as_borrowed()).into_datum(),
+            OwnedVector::Vecbf16(x) => Vecbf16Output::new(x.as_borrowed()).into_datum(),
+            OwnedVector::SVecf32(x) => SVecf32Output::new(x.as_borrowed()).into_datum(),
+            OwnedVector::SVecf16(x) => SVecf16Output::new(x.as_borrowed()).into_datum(),
+            OwnedVector::BVector(x) => BVectorOutput::new(x.as_borrowed()).into_datum(),
+        };
+        datum.unwrap()
+    }
+    /// Vectors are stored after preprocessing, so they could be returned by
+    /// index-only scans only if preprocessing is an identity.
+    pub fn returnable(self) -> bool {
+        !matches!(self.pg_distance, PgDistanceKind::Cos) && !self.is_multivector()
+    }
+    pub fn is_multivector(self) -> bool {
+        matches!(
+            self.pg_distance,
+            PgDistanceKind::MaxSim | PgDistanceKind::Chamfer
+        )
+    }
     pub fn preprocess(self, vector: BorrowedVector<'_>) -> OwnedVector {
         use BorrowedVector as B;
         use OwnedVector as O;
@@ -236,8 +468,12 @@ impl Opfamily {
             (B::Vecf32(x), _) => O::Vecf32(x.own()),
             (B::Vecf16(x), PgDistanceKind::Cos) => O::Vecf16(x.function_normalize()),
             (B::Vecf16(x), _) => O::Vecf16(x.own()),
+            (B::Vecbf16(x), PgDistanceKind::Cos) => O::Vecbf16(x.function_normalize()),
+            (B::Vecbf16(x), _) => O::Vecbf16(x.own()),
             (B::SVecf32(x), PgDistanceKind::Cos) => O::SVecf32(x.function_normalize()),
             (B::SVecf32(x), _) => O::SVecf32(x.own()),
+            (B::SVecf16(x), PgDistanceKind::Cos) => O::SVecf16(x.function_normalize()),
+            (B::SVecf16(x), _) => O::SVecf16(x.own()),
             (B::BVector(x), _) => O::BVector(x.own()),
         }
     }
@@ -247,6 +483,12 @@ impl Opfamily {
             _ => f32::from(x),
         }
     }
+    pub fn unprocess(self, x: f32) -> Distance {
+        match self.pg_distance {
+            PgDistanceKind::Cos => Distance::from(x - 1.0f32),
+            _ => Distance::from(x),
+        }
+    }
 }
 
 pub unsafe fn opfamily(index: pgrx::pg_sys::Relation) -> Opfamily {
@@ -3,16 +3,20 @@ use crate::error::*;
 use crate::gucs::executing::search_options;
 use crate::gucs::planning::Mode;
 use crate::gucs::planning::SEARCH_MODE;
-use crate::ipc::{client, ClientVbase};
+use crate::ipc::{client, ClientVbase, ClientVbaseMulti};
 use base::index::*;
 use base::search::*;
 use base::vector::*;
+use validator::Validate;
 
 pub enum Scanner {
     Initial {
         vector: Option<(OwnedVector, Opfamily)>,
         threshold: Option<f32>,
         recheck: bool,
+        opts: PartialSearchOptions,
+        partitions: Option<Vec<u64>>,
+        fetch: bool,
     },
     Vbase {
         vbase: ClientVbase,
@@ -20,17 +24,48 @@ pub enum Scanner {
         recheck: bool,
         opfamily: Opfamily,
     },
+    InitialMulti {
+        vectors: Vec<OwnedVector>,
+        opfamily: Opfamily,
+        threshold: Option<f32>,
+        partitions: Option<Vec<u64>>,
+        recheck: bool,
+    },
+    VbaseMulti {
+        vbase: ClientVbaseMulti,
+        opfamily: Opfamily,
+        threshold: Option<f32>,
+        recheck: bool,
+    },
     Empty {},
 }
 
 pub fn scan_build(
     orderbys: Vec<Option<OwnedVector>>,
     spheres: Vec<(Option<OwnedVector>, Option<f32>)>,
+    searches: Vec<(Option<OwnedVector>, Option<String>)>,
     opfamily: Opfamily,
-) -> (Option<(OwnedVector, Opfamily)>, Option<f32>, bool) {
+) -> (
+    Option<(OwnedVector, Opfamily)>,
+    Option<f32>,
+    bool,
+    PartialSearchOptions,
+) {
     let mut pair = None;
     let mut threshold = None;
     let mut recheck = false;
+    let mut options = None;
+    for (search_vector, search_options) in searches {
+        if options.is_some() && options != search_options {
+            pgrx::error!("vector search with multiple search options is not supported");
+        }
+        options = search_options;
+        if pair.is_none() {
+            pair = search_vector;
+        } else if search_vector.is_some() && pair != search_vector {
+            pgrx::error!("vector search with multiple vectors is not supported");
+        }
+    }
     for orderby_vector in orderbys {
         if pair.is_none() {
             pair = orderby_vector;
@@ -51,26 +86,105 @@ pub fn scan_build(
             break;
         }
     }
-    (pair.map(|x| (x, opfamily)), threshold, recheck)
+    let opts = match options {
+        Some(options) => parse_search_options(&options).or_partial(&search_options()),
+        None => search_options(),
+    };
+    (pair.map(|x| (x, opfamily)), threshold, recheck, opts)
+}
+
+/// Parses search options given inline with `search()`. Options not given are
+/// taken from GUCs and then from the index.
+fn parse_search_options(options: &str) -> PartialSearchOptions {
+    let opts = match toml::from_str::<PartialSearchOptions>(options) {
+        Ok(opts) => opts,
+        Err(e) => pgrx::error!("failed to parse search options: {}", e),
+    };
+    if let Err(e) = opts.validate() {
+        bad_service_invalid_search_options(&e.to_string());
+    }
+    opts
 }
 
 pub fn scan_make(
     vector: Option<(OwnedVector, Opfamily)>,
     threshold: Option<f32>,
     recheck: bool,
+    opts: PartialSearchOptions,
+    partitions: Option<Vec<u64>>,
+    fetch: bool,
 ) -> Scanner {
+    if partitions.as_ref().is_some_and(|p| p.is_empty()) {
+        return Scanner::Empty {};
+    }
     Scanner::Initial {
         vector,
         threshold,
         recheck,
+        opts,
+        partitions,
+        fetch,
+    }
+}
+
+/// Multivector rows are returned by a lower bound of their distances, so rows
+/// in a sphere are always rechecked.
+pub fn scan_build_multi(
+    orderbys: Vec<Vec<OwnedVector>>,
+    spheres: Vec<(Vec<OwnedVector>, Option<f32>)>,
+) -> (Vec<OwnedVector>, Option<f32>, bool) {
+    let mut vectors = Vec::new();
+    let mut threshold = None;
+    let recheck = !spheres.is_empty();
+    for orderby_vectors in orderbys {
+        if vectors.is_empty() {
+            vectors = orderby_vectors;
+        } else if !orderby_vectors.is_empty() && vectors != orderby_vectors {
+            pgrx::error!("vector search with multiple vectors is not supported");
+        }
+    }
+    for (sphere_vectors, sphere_threshold) in spheres {
+        if vectors.is_empty() {
+            vectors = sphere_vectors;
+            threshold = sphere_threshold;
+        } else if vectors == sphere_vectors && (threshold.is_none() || sphere_threshold < threshold)
+        {
+            threshold = sphere_threshold;
+        }
+    }
+    (vectors, threshold, recheck)
+}
+
+pub fn scan_make_multi(
+    vectors: Vec<OwnedVector>,
+    opfamily: Opfamily,
+    threshold: Option<f32>,
+    partitions: Option<Vec<u64>>,
+    recheck: bool,
+) -> Scanner {
+    if vectors.is_empty() || partitions.as_ref().is_some_and(|p| p.is_empty()) {
+        return Scanner::Empty {};
+    }
+    Scanner::InitialMulti {
+        vectors,
+        opfamily,
+        threshold,
+        partitions,
+        recheck,
     }
 }
 
-pub fn scan_next(scanner: &mut Scanner, handle: Handle) -> Option<(Pointer, bool)> {
+pub fn scan_next(
+    scanner: &mut Scanner,
+    handle: Handle,
+) -> Option<(Pointer, f32, Option<OwnedVector>, bool)> {
     if let Scanner::Initial {
         vector,
         threshold,
         recheck,
+        opts,
+        partitions,
+        fetch,
     } = scanner
     {
         if let Some((vector, opfamily)) = vector.as_ref() {
@@ -78,12 +192,19 @@ pub fn scan_next(scanner: &mut Scanner, handle: Handle) -> Option<(Pointer, bool
 
             match SEARCH_MODE.get() {
                 Mode::basic | Mode::vbase => {
-                    let opts = search_options();
-                    let vbase = match rpc.vbase(handle, vector.clone(), opts) {
+                    let vbase = match rpc.vbase(
+                        handle,
+                        vector.clone(),
+                        opts.clone(),
+                        partitions.clone(),
+                        *fetch,
+                    ) {
                         Ok(x) => x,
                         Err((_, VbaseError::NotExist)) => bad_service_not_exist(),
                         Err((_, VbaseError::InvalidVector)) => bad_service_invalid_vector(),
-                        Err((_, VbaseError::InvalidSearchOptions { reason: _ })) => unreachable!(),
+                        Err((_, VbaseError::InvalidSearchOptions { reason })) => {
+                            bad_service_invalid_search_options(&reason)
+                        }
                     };
                     *scanner = Scanner::Vbase {
                         vbase,
@@ -97,19 +218,66 @@ pub fn scan_next(scanner: &mut Scanner, handle: Handle) -> Option<(Pointer, bool
             *scanner = Scanner::Empty {};
         }
     }
+    if let Scanner::InitialMulti {
+        vectors,
+        opfamily,
+        threshold,
+        partitions,
+        recheck,
+    } = scanner
+    {
+        let rpc = check_client(client());
+        let opts = search_options();
+        let vbase = match rpc.vbase_multi(handle, std::mem::take(vectors), opts, partitions.take())
+        {
+            Ok(x) => x,
+            Err((_, VbaseMultiError::NotExist)) => bad_service_not_exist(),
+            Err((_, VbaseMultiError::InvalidVector)) => bad_service_invalid_vector(),
+            Err((_, VbaseMultiError::InvalidSearchOptions { reason })) => {
+                bad_service_invalid_search_options(&reason)
+            }
+        };
+        *scanner = Scanner::VbaseMulti {
+            vbase,
+            opfamily: *opfamily,
+            threshold: *threshold,
+            recheck: *recheck,
+        };
+    }
     match scanner {
-        Scanner::Initial { .. } => unreachable!(),
+        Scanner::Initial { .. } | Scanner::InitialMulti { .. } => unreachable!(),
         Scanner::Vbase {
             vbase,
             threshold,
             recheck,
             opfamily,
+        } => match (
+            vbase.next().map(|(d, p, v)| (opfamily.process(d), p, v)),
+            threshold,
+        ) {
+            (Some((distance, ptr, vector)), None) => Some((ptr, distance, vector, *recheck)),
+            (Some((distance, ptr, vector)), Some(t)) if distance < *t => {
+                Some((ptr, distance, vector, *recheck))
+            }
+            _ => {
+                let scanner = std::mem::replace(scanner, Scanner::Empty {});
+                scan_release(scanner);
+                None
+            }
+        },
+        Scanner::VbaseMulti {
+            vbase,
+            opfamily,
+            threshold,
+            recheck,
         } => match (
             vbase.next().map(|(d, p)| (opfamily.process(d), p)),
             threshold,
         ) {
-            (Some((_, ptr)), None) => Some((ptr, *recheck)),
-            (Some((distance, ptr)), Some(t)) if distance < *t => Some((ptr, *recheck)),
+            (Some((distance, ptr)), None) => Some((ptr, distance, None, *recheck)),
+            (Some((distance, ptr)), Some(t)) if distance < *t => {
+                Some((ptr, distance, None, *recheck))
+            }
             _ => {
                 let scanner = std::mem::replace(scanner, Scanner::Empty {});
                 scan_release(scanner);
@@ -120,12 +288,65 @@ pub fn scan_next(scanner: &mut Scanner, handle: Handle) -> Option<(Pointer, bool
     }
 }
 
+pub fn scan_range(scanner: &mut Scanner, handle: Handle, mut f: impl FnMut(Vec<Pointer>, bool)) {
+    let mut scanner = std::mem::replace(scanner, Scanner::Empty {});
+    if let Scanner::InitialMulti {
+        threshold: Some(_),
+        recheck,
+        ..
+    } = scanner
+    {
+        // multivector rows in a sphere are found by a search stopping at the radius
+        let mut pointers = Vec::new();
+        while let Some((pointer, ..)) = scan_next(&mut scanner, handle) {
+            pointers.push(pointer);
+        }
+        f(pointers, recheck);
+        return;
+    }
+    let Scanner::Initial {
+        vector: Some((vector, opfamily)),
+        threshold: Some(threshold),
+        recheck,
+        opts,
+        partitions,
+        ..
+    } = scanner
+    else {
+        scan_release(scanner);
+        return;
+    };
+    let rpc = check_client(client());
+    let mut range = match rpc.range(
+        handle,
+        vector,
+        opts,
+        partitions,
+        opfamily.unprocess(threshold),
+    ) {
+        Ok(x) => x,
+        Err((_, RangeError::NotExist)) => bad_service_not_exist(),
+        Err((_, RangeError::InvalidVector)) => bad_service_invalid_vector(),
+        Err((_, RangeError::InvalidSearchOptions { reason })) => {
+            bad_service_invalid_search_options(&reason)
+        }
+    };
+    while let Some(pointers) = range.next() {
+        f(pointers, recheck);
+    }
+    range.leave();
+}
+
 pub fn scan_release(scanner: Scanner) {
     match scanner {
         Scanner::Initial { .. } => {}
         Scanner::Vbase { vbase, .. } => {
             vbase.leave();
         }
+        Scanner::InitialMulti { .. } => {}
+        Scanner::VbaseMulti { vbase, .. } => {
+            vbase.leave();
+        }
         Scanner::Empty {} => {}
     }
 }
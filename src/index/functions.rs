@@ -1,8 +1,10 @@
 use super::utils::from_oid_to_handle;
-use crate::error::check_client;
+use crate::error::*;
+use crate::gucs::executing::search_options;
 use crate::ipc::client;
-use base::index::StatError;
+use base::index::*;
 use pgrx::pg_sys::Oid;
+use pgrx::{name, TableIterator};
 
 #[pgrx::pg_extern(volatile, strict, parallel_safe)]
 fn _vectors_pgvectors_upgrade() {
@@ -39,3 +41,114 @@ fn _vectors_fence_vector_index(oid: Oid) {
         }
     }
 }
+
+#[allow(clippy::type_complexity)]
+#[pgrx::pg_extern(volatile, strict)]
+fn _vectors_tune_vector_index(
+    oid: Oid,
+    target_recall: f32,
+    k: i32,
+    queries: i32,
+    persist: bool,
+) -> TableIterator<
+    'static,
+    (
+        name!(hnsw_ef_search, i32),
+        name!(ivf_nprobe, i32),
+        name!(sq_rerank_size, i32),
+        name!(pq_rerank_size, i32),
+        name!(rq_rerank_size, i32),
+        name!(bq_rerank_size, i32),
+        name!(truncation_rerank_size, i32),
+        name!(recall, f32),
+        name!(latency_ms, f64),
+        name!(recommended, bool),
+    ),
+> {
+    let handle = from_oid_to_handle(oid);
+    let rpc = check_client(client());
+    let tune = TuneOptions {
+        queries: queries.try_into().unwrap_or(0),
+        k: k.try_into().unwrap_or(0),
+        target_recall,
+    };
+    let mut x = match rpc.tune(handle, search_options(), tune) {
+        Ok(x) => x,
+        Err((_, e)) => bad_service_tune(e),
+    };
+    let mut trials = Vec::new();
+    // trials are measured one by one, so that the query can be canceled between them
+    loop {
+        pgrx::check_for_interrupts!();
+        match x.next() {
+            Some(Ok(trial)) => trials.push(trial),
+            Some(Err(e)) => bad_service_tune(e),
+            None => break,
+        }
+    }
+    let mut rpc = x.leave();
+    // the cheapest trial that meets the target recall, which searches the
+    // fewest candidates, while latency is noisy and only reported
+    let recommended = trials
+        .iter()
+        .enumerate()
+        .filter(|(_, trial)| trial.recall >= target_recall)
+        .min_by_key(|(_, trial)| {
+            let x = &trial.options;
+            (
+                x.hnsw_ef_search,
+                x.ivf_nprobe,
+                x.sq_rerank_size,
+                x.pq_rerank_size,
+                x.rq_rerank_size,
+                x.bq_rerank_size,
+                x.truncation_rerank_size,
+            )
+        })
+        .map(|(i, _)| i);
+    if let Some(trial) = recommended.filter(|_| persist).map(|i| &trials[i]) {
+        let options: [(&str, fn(&SearchOptions) -> u32); 7] = [
+            ("hnsw_ef_search", |x| x.hnsw_ef_search),
+            ("ivf_nprobe", |x| x.ivf_nprobe),
+            ("sq_rerank_size", |x| x.sq_rerank_size),
+            ("pq_rerank_size", |x| x.pq_rerank_size),
+            ("rq_rerank_size", |x| x.rq_rerank_size),
+            ("bq_rerank_size", |x| x.bq_rerank_size),
+            ("truncation_rerank_size", |x| x.truncation_rerank_size),
+        ];
+        for (key, get) in options {
+            // only options that are tuned apply to the index, and they are the
+            // ones that differ between trials
+            let value = get(&trial.options);
+            if trials.iter().all(|x| get(&x.options) == value) {
+                continue;
+            }
+            if let Err(e) = rpc.alter(handle, format!("search.{key}"), value.to_string()) {
+                pgrx::error!("{}", e.to_string());
+            }
+        }
+    }
+    TableIterator::new(trials.into_iter().enumerate().map(move |(i, trial)| {
+        (
+            trial.options.hnsw_ef_search as i32,
+            trial.options.ivf_nprobe as i32,
+            trial.options.sq_rerank_size as i32,
+            trial.options.pq_rerank_size as i32,
+            trial.options.rq_rerank_size as i32,
+            trial.options.bq_rerank_size as i32,
+            trial.options.truncation_rerank_size as i32,
+            trial.recall,
+            trial.latency.as_secs_f64() * 1000.0,
+            Some(i) == recommended,
+        )
+    }))
+}
+
+fn bad_service_tune(e: TuneError) -> ! {
+    match e {
+        TuneError::NotExist => bad_service_not_exist(),
+        TuneError::InvalidVector => bad_service_invalid_vector(),
+        TuneError::InvalidSearchOptions { reason } => bad_service_invalid_search_options(&reason),
+        TuneError::InvalidTuneOptions { reason } => bad_service_invalid_tune_options(&reason),
+    }
+}
@@ -1,4 +1,5 @@
 mod am;
+mod am_build;
 mod am_options;
 mod am_scan;
 mod catalog;
@@ -324,12 +324,15 @@ defines! {
     unary create(handle: Handle, options: IndexOptions, alterable_options: IndexAlterableOptions) -> ();
     unary drop(handle: Handle) -> ();
     unary flush(handle: Handle) -> ();
-    unary insert(handle: Handle, vector: OwnedVector, pointer: Pointer) -> ();
+    unary insert(handle: Handle, vector: OwnedVector, pointer: Pointer, partition: Option<u64>) -> ();
     unary delete(handle: Handle, pointer: Pointer) -> ();
-    stream vbase(handle: Handle, vector: OwnedVector, opts: SearchOptions) -> (Distance, Pointer);
+    stream vbase(handle: Handle, vector: OwnedVector, opts: PartialSearchOptions, partitions: Option<Vec<u64>>, fetch: bool) -> (Distance, Pointer, Option<OwnedVector>);
+    stream range(handle: Handle, vector: OwnedVector, opts: PartialSearchOptions, partitions: Option<Vec<u64>>, threshold: Distance) -> Vec<Pointer>;
+    stream vbase_multi(handle: Handle, vectors: Vec<OwnedVector>, opts: PartialSearchOptions, partitions: Option<Vec<u64>>) -> (Distance, Pointer);
     stream list(handle: Handle) -> Pointer;
     unary stat(handle: Handle) -> IndexStat;
     unary alter(handle: Handle, key: String, value: String) -> ();
+    stream tune(handle: Handle, opts: PartialSearchOptions, tune: TuneOptions) -> Result<TuneTrial, TuneError>;
     unary stop(handle: Handle) -> ();
     unary start(handle: Handle) -> ();
 }
@@ -4,14 +4,19 @@
 
 CREATE TYPE vector;
 CREATE TYPE vecf16;
+CREATE TYPE vecbf16;
 CREATE TYPE svector;
+CREATE TYPE svecf16;
 CREATE TYPE bvector;
 
 CREATE TYPE vector_index_stat;
 
 CREATE TYPE sphere_vector;
 CREATE TYPE sphere_vecf16;
+CREATE TYPE sphere_vecbf16;
 CREATE TYPE sphere_svector;
+CREATE TYPE sphere_svecf16;
 CREATE TYPE sphere_bvector;
+CREATE TYPE sphere_multivector;
 
 -- bootstrap end
@@ -28,6 +28,19 @@ CREATE TYPE vecf16 (
     ALIGNMENT = double
 );
 
+CREATE TYPE vecbf16 (
+    INPUT = _vectors_vecbf16_in,
+    OUTPUT = _vectors_vecbf16_out,
+    RECEIVE = _vectors_vecbf16_recv,
+    SEND = _vectors_vecbf16_send,
+    SUBSCRIPT = _vectors_vecbf16_subscript,
+    TYPMOD_IN = _vectors_typmod_in_65535,
+    TYPMOD_OUT = _vectors_typmod_out,
+    STORAGE = EXTERNAL,
+    INTERNALLENGTH = VARIABLE,
+    ALIGNMENT = double
+);
+
 CREATE TYPE svector (
     INPUT = _vectors_svecf32_in,
     OUTPUT = _vectors_svecf32_out,
@@ -41,6 +54,19 @@ CREATE TYPE svector (
     ALIGNMENT = double
 );
 
+CREATE TYPE svecf16 (
+    INPUT = _vectors_svecf16_in,
+    OUTPUT = _vectors_svecf16_out,
+    RECEIVE = _vectors_svecf16_recv,
+    SEND = _vectors_svecf16_send,
+    SUBSCRIPT = _vectors_svecf16_subscript,
+    TYPMOD_IN = _vectors_typmod_in_1048575,
+    TYPMOD_OUT = _vectors_typmod_out,
+    STORAGE = EXTERNAL,
+    INTERNALLENGTH = VARIABLE,
+    ALIGNMENT = double
+);
+
 CREATE TYPE bvector (
     INPUT = _vectors_bvector_in,
     OUTPUT = _vectors_bvector_out,
@@ -75,16 +101,61 @@ CREATE TYPE sphere_vecf16 AS (
     radius REAL
 );
 
+CREATE TYPE sphere_vecbf16 AS (
+    center vecbf16,
+    radius REAL
+);
+
 CREATE TYPE sphere_svector AS (
     center svector,
     radius REAL
 );
 
+CREATE TYPE sphere_svecf16 AS (
+    center svecf16,
+    radius REAL
+);
+
 CREATE TYPE sphere_bvector AS (
     center bvector,
     radius REAL
 );
 
+CREATE TYPE sphere_multivector AS (
+    center vector[],
+    radius REAL
+);
+
+CREATE TYPE search_vector AS (
+    query vector,
+    options TEXT
+);
+
+CREATE TYPE search_vecf16 AS (
+    query vecf16,
+    options TEXT
+);
+
+CREATE TYPE search_vecbf16 AS (
+    query vecbf16,
+    options TEXT
+);
+
+CREATE TYPE search_svector AS (
+    query svector,
+    options TEXT
+);
+
+CREATE TYPE search_svecf16 AS (
+    query svecf16,
+    options TEXT
+);
+
+CREATE TYPE search_bvector AS (
+    query bvector,
+    options TEXT
+);
+
 -- List of operators
 
 CREATE OPERATOR + (
@@ -101,6 +172,13 @@ CREATE OPERATOR + (
     COMMUTATOR = +
 );
 
+CREATE OPERATOR + (
+    PROCEDURE = _vectors_vecbf16_operator_add,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = +
+);
+
 CREATE OPERATOR + (
     PROCEDURE = _vectors_svecf32_operator_add,
     LEFTARG = svector,
@@ -108,6 +186,13 @@ CREATE OPERATOR + (
     COMMUTATOR = +
 );
 
+CREATE OPERATOR + (
+    PROCEDURE = _vectors_svecf16_operator_add,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = +
+);
+
 CREATE OPERATOR - (
     PROCEDURE = _vectors_vecf32_operator_sub,
     LEFTARG = vector,
@@ -120,12 +205,24 @@ CREATE OPERATOR - (
     RIGHTARG = vecf16
 );
 
+CREATE OPERATOR - (
+    PROCEDURE = _vectors_vecbf16_operator_sub,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16
+);
+
 CREATE OPERATOR - (
     PROCEDURE = _vectors_svecf32_operator_sub,
     LEFTARG = svector,
     RIGHTARG = svector
 );
 
+CREATE OPERATOR - (
+    PROCEDURE = _vectors_svecf16_operator_sub,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16
+);
+
 CREATE OPERATOR * (
     PROCEDURE = _vectors_vecf32_operator_mul,
     LEFTARG = vector,
@@ -140,6 +237,13 @@ CREATE OPERATOR * (
     COMMUTATOR = *
 );
 
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_vecbf16_operator_mul,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = *
+);
+
 CREATE OPERATOR * (
     PROCEDURE = _vectors_svecf32_operator_mul,
     LEFTARG = svector,
@@ -147,6 +251,143 @@ CREATE OPERATOR * (
     COMMUTATOR = *
 );
 
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_svecf16_operator_mul,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_vecf32_operator_mul_scalar,
+    LEFTARG = vector,
+    RIGHTARG = real,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_vecf16_operator_mul_scalar,
+    LEFTARG = vecf16,
+    RIGHTARG = real,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_vecbf16_operator_mul_scalar,
+    LEFTARG = vecbf16,
+    RIGHTARG = real,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_svecf32_operator_mul_scalar,
+    LEFTARG = svector,
+    RIGHTARG = real,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_svecf16_operator_mul_scalar,
+    LEFTARG = svecf16,
+    RIGHTARG = real,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_vecf32_operator_scalar_mul,
+    LEFTARG = real,
+    RIGHTARG = vector,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_vecf16_operator_scalar_mul,
+    LEFTARG = real,
+    RIGHTARG = vecf16,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_vecbf16_operator_scalar_mul,
+    LEFTARG = real,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_svecf32_operator_scalar_mul,
+    LEFTARG = real,
+    RIGHTARG = svector,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR * (
+    PROCEDURE = _vectors_svecf16_operator_scalar_mul,
+    LEFTARG = real,
+    RIGHTARG = svecf16,
+    COMMUTATOR = *
+);
+
+CREATE OPERATOR / (
+    PROCEDURE = _vectors_vecf32_operator_div,
+    LEFTARG = vector,
+    RIGHTARG = vector
+);
+
+CREATE OPERATOR / (
+    PROCEDURE = _vectors_vecf16_operator_div,
+    LEFTARG = vecf16,
+    RIGHTARG = vecf16
+);
+
+CREATE OPERATOR / (
+    PROCEDURE = _vectors_vecbf16_operator_div,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16
+);
+
+CREATE OPERATOR / (
+    PROCEDURE = _vectors_svecf32_operator_div,
+    LEFTARG = svector,
+    RIGHTARG = svector
+);
+
+CREATE OPERATOR / (
+    PROCEDURE = _vectors_svecf16_operator_div,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16
+);
+
+CREATE OPERATOR || (
+    PROCEDURE = _vectors_vecf32_operator_concat,
+    LEFTARG = vector,
+    RIGHTARG = vector
+);
+
+CREATE OPERATOR || (
+    PROCEDURE = _vectors_vecf16_operator_concat,
+    LEFTARG = vecf16,
+    RIGHTARG = vecf16
+);
+
+CREATE OPERATOR || (
+    PROCEDURE = _vectors_vecbf16_operator_concat,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16
+);
+
+CREATE OPERATOR || (
+    PROCEDURE = _vectors_svecf32_operator_concat,
+    LEFTARG = svector,
+    RIGHTARG = svector
+);
+
+CREATE OPERATOR || (
+    PROCEDURE = _vectors_svecf16_operator_concat,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16
+);
+
 CREATE OPERATOR & (
     PROCEDURE = _vectors_bvector_operator_and,
     LEFTARG = bvector,
@@ -185,6 +426,16 @@ CREATE OPERATOR = (
     JOIN = eqjoinsel
 );
 
+CREATE OPERATOR = (
+    PROCEDURE = _vectors_vecbf16_operator_eq,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = =,
+    NEGATOR = <>,
+    RESTRICT = eqsel,
+    JOIN = eqjoinsel
+);
+
 CREATE OPERATOR = (
     PROCEDURE = _vectors_svecf32_operator_eq,
     LEFTARG = svector,
@@ -195,6 +446,16 @@ CREATE OPERATOR = (
     JOIN = eqjoinsel
 );
 
+CREATE OPERATOR = (
+    PROCEDURE = _vectors_svecf16_operator_eq,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = =,
+    NEGATOR = <>,
+    RESTRICT = eqsel,
+    JOIN = eqjoinsel
+);
+
 CREATE OPERATOR = (
     PROCEDURE = _vectors_bvector_operator_eq,
     LEFTARG = bvector,
@@ -225,6 +486,16 @@ CREATE OPERATOR <> (
     JOIN = eqjoinsel
 );
 
+CREATE OPERATOR <> (
+    PROCEDURE = _vectors_vecbf16_operator_neq,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = <>,
+    NEGATOR = =,
+    RESTRICT = eqsel,
+    JOIN = eqjoinsel
+);
+
 CREATE OPERATOR <> (
     PROCEDURE = _vectors_svecf32_operator_neq,
     LEFTARG = svector,
@@ -235,6 +506,16 @@ CREATE OPERATOR <> (
     JOIN = eqjoinsel
 );
 
+CREATE OPERATOR <> (
+    PROCEDURE = _vectors_svecf16_operator_neq,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = <>,
+    NEGATOR = =,
+    RESTRICT = eqsel,
+    JOIN = eqjoinsel
+);
+
 CREATE OPERATOR <> (
     PROCEDURE = _vectors_bvector_operator_neq,
     LEFTARG = bvector,
@@ -265,6 +546,16 @@ CREATE OPERATOR < (
     JOIN = scalarltjoinsel
 );
 
+CREATE OPERATOR < (
+    PROCEDURE = _vectors_vecbf16_operator_lt,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = >,
+    NEGATOR = >=,
+    RESTRICT = scalarltsel,
+    JOIN = scalarltjoinsel
+);
+
 CREATE OPERATOR < (
     PROCEDURE = _vectors_svecf32_operator_lt,
     LEFTARG = svector,
@@ -275,6 +566,16 @@ CREATE OPERATOR < (
     JOIN = scalarltjoinsel
 );
 
+CREATE OPERATOR < (
+    PROCEDURE = _vectors_svecf16_operator_lt,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = >,
+    NEGATOR = >=,
+    RESTRICT = scalarltsel,
+    JOIN = scalarltjoinsel
+);
+
 CREATE OPERATOR < (
     PROCEDURE = _vectors_bvector_operator_lt,
     LEFTARG = bvector,
@@ -305,6 +606,16 @@ CREATE OPERATOR > (
     JOIN = scalargtjoinsel
 );
 
+CREATE OPERATOR > (
+    PROCEDURE = _vectors_vecbf16_operator_gt,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = <,
+    NEGATOR = <=,
+    RESTRICT = scalargtsel,
+    JOIN = scalargtjoinsel
+);
+
 CREATE OPERATOR > (
     PROCEDURE = _vectors_svecf32_operator_gt,
     LEFTARG = svector,
@@ -315,6 +626,16 @@ CREATE OPERATOR > (
     JOIN = scalargtjoinsel
 );
 
+CREATE OPERATOR > (
+    PROCEDURE = _vectors_svecf16_operator_gt,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = <,
+    NEGATOR = <=,
+    RESTRICT = scalargtsel,
+    JOIN = scalargtjoinsel
+);
+
 CREATE OPERATOR > (
     PROCEDURE = _vectors_bvector_operator_gt,
     LEFTARG = bvector,
@@ -345,6 +666,16 @@ CREATE OPERATOR <= (
     JOIN = scalarltjoinsel
 );
 
+CREATE OPERATOR <= (
+    PROCEDURE = _vectors_vecbf16_operator_lte,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = >=,
+    NEGATOR = >,
+    RESTRICT = scalarltsel,
+    JOIN = scalarltjoinsel
+);
+
 CREATE OPERATOR <= (
     PROCEDURE = _vectors_svecf32_operator_lte,
     LEFTARG = svector,
@@ -355,6 +686,16 @@ CREATE OPERATOR <= (
     JOIN = scalarltjoinsel
 );
 
+CREATE OPERATOR <= (
+    PROCEDURE = _vectors_svecf16_operator_lte,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = >=,
+    NEGATOR = >,
+    RESTRICT = scalarltsel,
+    JOIN = scalarltjoinsel
+);
+
 CREATE OPERATOR <= (
     PROCEDURE = _vectors_bvector_operator_lte,
     LEFTARG = bvector,
@@ -385,6 +726,16 @@ CREATE OPERATOR >= (
     JOIN = scalargtjoinsel
 );
 
+CREATE OPERATOR >= (
+    PROCEDURE = _vectors_vecbf16_operator_gte,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = <=,
+    NEGATOR = <,
+    RESTRICT = scalargtsel,
+    JOIN = scalargtjoinsel
+);
+
 CREATE OPERATOR >= (
     PROCEDURE = _vectors_svecf32_operator_gte,
     LEFTARG = svector,
@@ -395,6 +746,16 @@ CREATE OPERATOR >= (
     JOIN = scalargtjoinsel
 );
 
+CREATE OPERATOR >= (
+    PROCEDURE = _vectors_svecf16_operator_gte,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = <=,
+    NEGATOR = <,
+    RESTRICT = scalargtsel,
+    JOIN = scalargtjoinsel
+);
+
 CREATE OPERATOR >= (
     PROCEDURE = _vectors_bvector_operator_gte,
     LEFTARG = bvector,
@@ -419,6 +780,13 @@ CREATE OPERATOR <-> (
     COMMUTATOR = <->
 );
 
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_vecbf16_operator_l2,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = <->
+);
+
 CREATE OPERATOR <-> (
     PROCEDURE = _vectors_svecf32_operator_l2,
     LEFTARG = svector,
@@ -426,6 +794,13 @@ CREATE OPERATOR <-> (
     COMMUTATOR = <->
 );
 
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_svecf16_operator_l2,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = <->
+);
+
 CREATE OPERATOR <-> (
     PROCEDURE = _vectors_bvector_operator_hamming,
     LEFTARG = bvector,
@@ -433,6 +808,12 @@ CREATE OPERATOR <-> (
     COMMUTATOR = <->
 );
 
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_multivecf32_operator_chamfer,
+    LEFTARG = vector[],
+    RIGHTARG = vector[]
+);
+
 CREATE OPERATOR <#> (
     PROCEDURE = _vectors_vecf32_operator_dot,
     LEFTARG = vector,
@@ -447,6 +828,13 @@ CREATE OPERATOR <#> (
     COMMUTATOR = <#>
 );
 
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_vecbf16_operator_dot,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = <#>
+);
+
 CREATE OPERATOR <#> (
     PROCEDURE = _vectors_svecf32_operator_dot,
     LEFTARG = svector,
@@ -454,6 +842,13 @@ CREATE OPERATOR <#> (
     COMMUTATOR = <#>
 );
 
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_svecf16_operator_dot,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = <#>
+);
+
 CREATE OPERATOR <#> (
     PROCEDURE = _vectors_bvector_operator_dot,
     LEFTARG = bvector,
@@ -461,6 +856,12 @@ CREATE OPERATOR <#> (
     COMMUTATOR = <#>
 );
 
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_multivecf32_operator_maxsim,
+    LEFTARG = vector[],
+    RIGHTARG = vector[]
+);
+
 CREATE OPERATOR <=> (
     PROCEDURE = _vectors_vecf32_operator_cos,
     LEFTARG = vector,
@@ -475,6 +876,13 @@ CREATE OPERATOR <=> (
     COMMUTATOR = <=>
 );
 
+CREATE OPERATOR <=> (
+    PROCEDURE = _vectors_vecbf16_operator_cos,
+    LEFTARG = vecbf16,
+    RIGHTARG = vecbf16,
+    COMMUTATOR = <=>
+);
+
 CREATE OPERATOR <=> (
     PROCEDURE = _vectors_svecf32_operator_cos,
     LEFTARG = svector,
@@ -482,6 +890,13 @@ CREATE OPERATOR <=> (
     COMMUTATOR = <=>
 );
 
+CREATE OPERATOR <=> (
+    PROCEDURE = _vectors_svecf16_operator_cos,
+    LEFTARG = svecf16,
+    RIGHTARG = svecf16,
+    COMMUTATOR = <=>
+);
+
 CREATE OPERATOR <~> (
     PROCEDURE = _vectors_bvector_operator_jaccard,
     LEFTARG = bvector,
@@ -503,6 +918,13 @@ CREATE OPERATOR <<->> (
     COMMUTATOR = <<->>
 );
 
+CREATE OPERATOR <<->> (
+    PROCEDURE = _vectors_vecbf16_sphere_l2_in,
+    LEFTARG = vecbf16,
+    RIGHTARG = sphere_vecbf16,
+    COMMUTATOR = <<->>
+);
+
 CREATE OPERATOR <<->> (
     PROCEDURE = _vectors_svecf32_sphere_l2_in,
     LEFTARG = svector,
@@ -510,6 +932,13 @@ CREATE OPERATOR <<->> (
     COMMUTATOR = <<->>
 );
 
+CREATE OPERATOR <<->> (
+    PROCEDURE = _vectors_svecf16_sphere_l2_in,
+    LEFTARG = svecf16,
+    RIGHTARG = sphere_svecf16,
+    COMMUTATOR = <<->>
+);
+
 CREATE OPERATOR <<->> (
     PROCEDURE = _vectors_bvector_sphere_hamming_in,
     LEFTARG = bvector,
@@ -517,6 +946,12 @@ CREATE OPERATOR <<->> (
     COMMUTATOR = <<->>
 );
 
+CREATE OPERATOR <<->> (
+    PROCEDURE = _vectors_multivecf32_sphere_chamfer_in,
+    LEFTARG = vector[],
+    RIGHTARG = sphere_multivector
+);
+
 CREATE OPERATOR <<#>> (
     PROCEDURE = _vectors_vecf32_sphere_dot_in,
     LEFTARG = vector,
@@ -531,6 +966,13 @@ CREATE OPERATOR <<#>> (
     COMMUTATOR = <<#>>
 );
 
+CREATE OPERATOR <<#>> (
+    PROCEDURE = _vectors_vecbf16_sphere_dot_in,
+    LEFTARG = vecbf16,
+    RIGHTARG = sphere_vecbf16,
+    COMMUTATOR = <<#>>
+);
+
 CREATE OPERATOR <<#>> (
     PROCEDURE = _vectors_svecf32_sphere_dot_in,
     LEFTARG = svector,
@@ -538,6 +980,13 @@ CREATE OPERATOR <<#>> (
     COMMUTATOR = <<#>>
 );
 
+CREATE OPERATOR <<#>> (
+    PROCEDURE = _vectors_svecf16_sphere_dot_in,
+    LEFTARG = svecf16,
+    RIGHTARG = sphere_svecf16,
+    COMMUTATOR = <<#>>
+);
+
 CREATE OPERATOR <<#>> (
     PROCEDURE = _vectors_bvector_sphere_dot_in,
     LEFTARG = bvector,
@@ -545,6 +994,12 @@ CREATE OPERATOR <<#>> (
     COMMUTATOR = <<#>>
 );
 
+CREATE OPERATOR <<#>> (
+    PROCEDURE = _vectors_multivecf32_sphere_maxsim_in,
+    LEFTARG = vector[],
+    RIGHTARG = sphere_multivector
+);
+
 CREATE OPERATOR <<=>> (
     PROCEDURE = _vectors_vecf32_sphere_cos_in,
     LEFTARG = vector,
@@ -559,6 +1014,13 @@ CREATE OPERATOR <<=>> (
     COMMUTATOR = <<=>>
 );
 
+CREATE OPERATOR <<=>> (
+    PROCEDURE = _vectors_vecbf16_sphere_cos_in,
+    LEFTARG = vecbf16,
+    RIGHTARG = sphere_vecbf16,
+    COMMUTATOR = <<=>>
+);
+
 CREATE OPERATOR <<=>> (
     PROCEDURE = _vectors_svecf32_sphere_cos_in,
     LEFTARG = svector,
@@ -566,6 +1028,13 @@ CREATE OPERATOR <<=>> (
     COMMUTATOR = <<=>>
 );
 
+CREATE OPERATOR <<=>> (
+    PROCEDURE = _vectors_svecf16_sphere_cos_in,
+    LEFTARG = svecf16,
+    RIGHTARG = sphere_svecf16,
+    COMMUTATOR = <<=>>
+);
+
 CREATE OPERATOR <<~>> (
     PROCEDURE = _vectors_bvector_sphere_jaccard_in,
     LEFTARG = bvector,
@@ -573,6 +1042,114 @@ CREATE OPERATOR <<~>> (
     COMMUTATOR = <<~>>
 );
 
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_vecf32_search_l2,
+    LEFTARG = vector,
+    RIGHTARG = search_vector
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_vecf16_search_l2,
+    LEFTARG = vecf16,
+    RIGHTARG = search_vecf16
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_vecbf16_search_l2,
+    LEFTARG = vecbf16,
+    RIGHTARG = search_vecbf16
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_svecf32_search_l2,
+    LEFTARG = svector,
+    RIGHTARG = search_svector
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_svecf16_search_l2,
+    LEFTARG = svecf16,
+    RIGHTARG = search_svecf16
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_vecf32_search_dot,
+    LEFTARG = vector,
+    RIGHTARG = search_vector
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_vecf16_search_dot,
+    LEFTARG = vecf16,
+    RIGHTARG = search_vecf16
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_vecbf16_search_dot,
+    LEFTARG = vecbf16,
+    RIGHTARG = search_vecbf16
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_svecf32_search_dot,
+    LEFTARG = svector,
+    RIGHTARG = search_svector
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_svecf16_search_dot,
+    LEFTARG = svecf16,
+    RIGHTARG = search_svecf16
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vectors_vecf32_search_cos,
+    LEFTARG = vector,
+    RIGHTARG = search_vector
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vectors_vecf16_search_cos,
+    LEFTARG = vecf16,
+    RIGHTARG = search_vecf16
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vectors_vecbf16_search_cos,
+    LEFTARG = vecbf16,
+    RIGHTARG = search_vecbf16
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vectors_svecf32_search_cos,
+    LEFTARG = svector,
+    RIGHTARG = search_svector
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vectors_svecf16_search_cos,
+    LEFTARG = svecf16,
+    RIGHTARG = search_svecf16
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vectors_bvector_search_hamming,
+    LEFTARG = bvector,
+    RIGHTARG = search_bvector
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vectors_bvector_search_dot,
+    LEFTARG = bvector,
+    RIGHTARG = search_bvector
+);
+
+CREATE OPERATOR <~> (
+    PROCEDURE = _vectors_bvector_search_jaccard,
+    LEFTARG = bvector,
+    RIGHTARG = search_bvector
+);
+
 -- List of functions
 
 CREATE FUNCTION pgvectors_upgrade() RETURNS void
@@ -598,15 +1175,25 @@ STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_alter_vector_ind
 CREATE FUNCTION fence_vector_index(oid) RETURNS void
 STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_fence_vector_index_wrapper';
 
+CREATE FUNCTION tune_vector_index("index" OID, "target_recall" REAL DEFAULT 0.95, "k" INT DEFAULT 10, "queries" INT DEFAULT 100, "persist" BOOL DEFAULT false)
+RETURNS TABLE (hnsw_ef_search INT, ivf_nprobe INT, sq_rerank_size INT, pq_rerank_size INT, rq_rerank_size INT, bq_rerank_size INT, truncation_rerank_size INT, recall REAL, latency_ms DOUBLE PRECISION, recommended BOOL)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_tune_vector_index_wrapper';
+
 CREATE FUNCTION vector_dims(vector) RETURNS INT
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf32_dims_wrapper';
 
 CREATE FUNCTION vector_dims(vecf16) RETURNS INT
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf16_dims_wrapper';
 
+CREATE FUNCTION vector_dims(vecbf16) RETURNS INT
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecbf16_dims_wrapper';
+
 CREATE FUNCTION vector_dims(svector) RETURNS INT
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf32_dims_wrapper';
 
+CREATE FUNCTION vector_dims(svecf16) RETURNS INT
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf16_dims_wrapper';
+
 CREATE FUNCTION vector_dims(bvector) RETURNS INT
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_bvector_dims_wrapper';
 
@@ -616,9 +1203,15 @@ IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf32
 CREATE FUNCTION vector_norm(vecf16) RETURNS real
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf16_norm_wrapper';
 
+CREATE FUNCTION vector_norm(vecbf16) RETURNS real
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecbf16_norm_wrapper';
+
 CREATE FUNCTION vector_norm(svector) RETURNS real
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf32_norm_wrapper';
 
+CREATE FUNCTION vector_norm(svecf16) RETURNS real
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf16_norm_wrapper';
+
 CREATE FUNCTION vector_norm(bvector) RETURNS real
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_bvector_norm_wrapper';
 
@@ -628,33 +1221,134 @@ IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf32
 CREATE FUNCTION vector_normalize(vecf16) RETURNS vecf16
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf16_normalize_wrapper';
 
+CREATE FUNCTION vector_normalize(vecbf16) RETURNS vecbf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecbf16_normalize_wrapper';
+
 CREATE FUNCTION vector_normalize(svector) RETURNS svector
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf32_normalize_wrapper';
 
+CREATE FUNCTION vector_normalize(svecf16) RETURNS svecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf16_normalize_wrapper';
+
+CREATE FUNCTION vector_abs(vector) RETURNS vector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf32_abs_wrapper';
+
+CREATE FUNCTION vector_abs(vecf16) RETURNS vecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf16_abs_wrapper';
+
+CREATE FUNCTION vector_abs(vecbf16) RETURNS vecbf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecbf16_abs_wrapper';
+
+CREATE FUNCTION vector_abs(svector) RETURNS svector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf32_abs_wrapper';
+
+CREATE FUNCTION vector_abs(svecf16) RETURNS svecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf16_abs_wrapper';
+
+CREATE FUNCTION vector_clamp(vector, "min" real, "max" real) RETURNS vector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf32_clamp_wrapper';
+
+CREATE FUNCTION vector_clamp(vecf16, "min" real, "max" real) RETURNS vecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf16_clamp_wrapper';
+
+CREATE FUNCTION vector_clamp(vecbf16, "min" real, "max" real) RETURNS vecbf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecbf16_clamp_wrapper';
+
+CREATE FUNCTION vector_clamp(svector, "min" real, "max" real) RETURNS svector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf32_clamp_wrapper';
+
+CREATE FUNCTION vector_clamp(svecf16, "min" real, "max" real) RETURNS svecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf16_clamp_wrapper';
+
+CREATE FUNCTION vector_slice(vector, "start" INT, "end" INT, "step" INT DEFAULT 1) RETURNS vector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf32_slice_wrapper';
+
+CREATE FUNCTION vector_slice(vecf16, "start" INT, "end" INT, "step" INT DEFAULT 1) RETURNS vecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf16_slice_wrapper';
+
+CREATE FUNCTION vector_slice(vecbf16, "start" INT, "end" INT, "step" INT DEFAULT 1) RETURNS vecbf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecbf16_slice_wrapper';
+
+CREATE FUNCTION vector_slice(svector, "start" INT, "end" INT, "step" INT DEFAULT 1) RETURNS svector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf32_slice_wrapper';
+
+CREATE FUNCTION vector_slice(svecf16, "start" INT, "end" INT, "step" INT DEFAULT 1) RETURNS svecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf16_slice_wrapper';
+
+CREATE FUNCTION vector_random_projection(vector, "dims" INT, "seed" BIGINT DEFAULT 0) RETURNS vector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf32_random_projection_wrapper';
+
+CREATE FUNCTION vector_random_projection(vecf16, "dims" INT, "seed" BIGINT DEFAULT 0) RETURNS vecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf16_random_projection_wrapper';
+
+CREATE FUNCTION vector_random_projection(vecbf16, "dims" INT, "seed" BIGINT DEFAULT 0) RETURNS vecbf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecbf16_random_projection_wrapper';
+
+CREATE FUNCTION vector_random_projection(svector, "dims" INT, "seed" BIGINT DEFAULT 0) RETURNS svector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf32_random_projection_wrapper';
+
+CREATE FUNCTION vector_random_projection(svecf16, "dims" INT, "seed" BIGINT DEFAULT 0) RETURNS svecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_svecf16_random_projection_wrapper';
+
 CREATE FUNCTION to_svector("dims" INT, "indexes" INT[], "values" real[]) RETURNS svector
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_to_svector_wrapper';
 
+CREATE FUNCTION to_svecf16("dims" INT, "indexes" INT[], "values" real[]) RETURNS svecf16
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_to_svecf16_wrapper';
+
 CREATE FUNCTION binarize("vector" vector) RETURNS bvector
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_binarize_wrapper';
 
+CREATE FUNCTION vector_kmeans_assign("vector" vector, "centroids" vector[]) RETURNS INT
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vectors_vecf32_kmeans_assign_wrapper';
+
 CREATE FUNCTION sphere(vector, real) RETURNS sphere_vector
 IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
 
 CREATE FUNCTION sphere(vecf16, real) RETURNS sphere_vecf16
 IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
 
+CREATE FUNCTION sphere(vecbf16, real) RETURNS sphere_vecbf16
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
 CREATE FUNCTION sphere(svector, real) RETURNS sphere_svector
 IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
 
+CREATE FUNCTION sphere(svecf16, real) RETURNS sphere_svecf16
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
 CREATE FUNCTION sphere(bvector, real) RETURNS sphere_bvector
 IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
 
+CREATE FUNCTION sphere(vector[], real) RETURNS sphere_multivector
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
+CREATE FUNCTION search(vector, text) RETURNS search_vector
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
+CREATE FUNCTION search(vecf16, text) RETURNS search_vecf16
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
+CREATE FUNCTION search(vecbf16, text) RETURNS search_vecbf16
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
+CREATE FUNCTION search(svector, text) RETURNS search_svector
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
+CREATE FUNCTION search(svecf16, text) RETURNS search_svecf16
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
+CREATE FUNCTION search(bvector, text) RETURNS search_bvector
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)';
+
 -- List of aggregates
 
 CREATE AGGREGATE avg(vector) (
     SFUNC = _vectors_vecf32_aggregate_avg_sum_sfunc,
     STYPE = internal,
     COMBINEFUNC = _vectors_vecf32_aggregate_avg_sum_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_avg_sum_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_avg_sum_deserialfunc,
     FINALFUNC = _vectors_vecf32_aggregate_avg_finalfunc,
     PARALLEL = SAFE
 );
@@ -663,6 +1357,8 @@ CREATE AGGREGATE sum(vector) (
     SFUNC = _vectors_vecf32_aggregate_avg_sum_sfunc,
     STYPE = internal,
     COMBINEFUNC = _vectors_vecf32_aggregate_avg_sum_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_avg_sum_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_avg_sum_deserialfunc,
     FINALFUNC = _vectors_vecf32_aggregate_sum_finalfunc,
     PARALLEL = SAFE
 );
@@ -683,6 +1379,123 @@ CREATE AGGREGATE sum(svector) (
     PARALLEL = SAFE
 );
 
+CREATE AGGREGATE min(vector) (
+    SFUNC = _vectors_vecf32_aggregate_min_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_min_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_min_max_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_min_max_deserialfunc,
+    FINALFUNC = _vectors_vecf32_aggregate_min_max_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE max(vector) (
+    SFUNC = _vectors_vecf32_aggregate_max_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_max_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_min_max_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_min_max_deserialfunc,
+    FINALFUNC = _vectors_vecf32_aggregate_min_max_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE variance(vector) (
+    SFUNC = _vectors_vecf32_aggregate_variance_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_variance_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_variance_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_variance_deserialfunc,
+    FINALFUNC = _vectors_vecf32_aggregate_variance_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE stddev(vector) (
+    SFUNC = _vectors_vecf32_aggregate_variance_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_variance_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_variance_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_variance_deserialfunc,
+    FINALFUNC = _vectors_vecf32_aggregate_stddev_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE avg(vecf16) (
+    SFUNC = _vectors_vecf16_aggregate_avg_sum_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_avg_sum_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_avg_sum_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_avg_sum_deserialfunc,
+    FINALFUNC = _vectors_vecf16_aggregate_avg_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE sum(vecf16) (
+    SFUNC = _vectors_vecf16_aggregate_avg_sum_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_avg_sum_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_avg_sum_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_avg_sum_deserialfunc,
+    FINALFUNC = _vectors_vecf32_aggregate_sum_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE min(vecf16) (
+    SFUNC = _vectors_vecf16_aggregate_min_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_min_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_min_max_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_min_max_deserialfunc,
+    FINALFUNC = _vectors_vecf16_aggregate_min_max_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE max(vecf16) (
+    SFUNC = _vectors_vecf16_aggregate_max_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_max_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_min_max_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_min_max_deserialfunc,
+    FINALFUNC = _vectors_vecf16_aggregate_min_max_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE variance(vecf16) (
+    SFUNC = _vectors_vecf16_aggregate_variance_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_variance_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_variance_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_variance_deserialfunc,
+    FINALFUNC = _vectors_vecf16_aggregate_variance_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE stddev(vecf16) (
+    SFUNC = _vectors_vecf16_aggregate_variance_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_vecf32_aggregate_variance_combinefunc,
+    SERIALFUNC = _vectors_vecf32_aggregate_variance_serialfunc,
+    DESERIALFUNC = _vectors_vecf32_aggregate_variance_deserialfunc,
+    FINALFUNC = _vectors_vecf16_aggregate_stddev_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE majority(bvector) (
+    SFUNC = _vectors_bvector_aggregate_majority_sfunc,
+    STYPE = internal,
+    COMBINEFUNC = _vectors_bvector_aggregate_majority_combinefunc,
+    SERIALFUNC = _vectors_bvector_aggregate_majority_serialfunc,
+    DESERIALFUNC = _vectors_bvector_aggregate_majority_deserialfunc,
+    FINALFUNC = _vectors_bvector_aggregate_majority_finalfunc,
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vector_kmeans(vector, "k" INT, "iterations" INT, "spherical" BOOLEAN) (
+    SFUNC = _vectors_vecf32_aggregate_kmeans_sfunc,
+    STYPE = internal,
+    FINALFUNC = _vectors_vecf32_aggregate_kmeans_finalfunc,
+    PARALLEL = SAFE
+);
+
 -- List of casts
 
 CREATE CAST (real[] AS vector)
@@ -697,12 +1510,24 @@ CREATE CAST (vector AS vecf16)
 CREATE CAST (vecf16 AS vector)
     WITH FUNCTION _vectors_cast_vecf16_to_vecf32(vecf16, integer, boolean);
 
+CREATE CAST (vector AS vecbf16)
+    WITH FUNCTION _vectors_cast_vecf32_to_vecbf16(vector, integer, boolean);
+
+CREATE CAST (vecbf16 AS vector)
+    WITH FUNCTION _vectors_cast_vecbf16_to_vecf32(vecbf16, integer, boolean);
+
 CREATE CAST (vector AS svector)
     WITH FUNCTION _vectors_cast_vecf32_to_svecf32(vector, integer, boolean);
 
 CREATE CAST (svector AS vector)
     WITH FUNCTION _vectors_cast_svecf32_to_vecf32(svector, integer, boolean);
 
+CREATE CAST (svector AS svecf16)
+    WITH FUNCTION _vectors_cast_svecf32_to_svecf16(svector, integer, boolean);
+
+CREATE CAST (svecf16 AS svector)
+    WITH FUNCTION _vectors_cast_svecf16_to_svecf32(svecf16, integer, boolean);
+
 CREATE CAST (vector AS bvector)
     WITH FUNCTION _vectors_cast_vecf32_to_bvector(vector, integer, boolean);
 
@@ -728,79 +1553,177 @@ CREATE OPERATOR FAMILY vecf16_dot_ops USING vectors;
 
 CREATE OPERATOR FAMILY vecf16_cos_ops USING vectors;
 
+CREATE OPERATOR FAMILY vecbf16_l2_ops USING vectors;
+
+CREATE OPERATOR FAMILY vecbf16_dot_ops USING vectors;
+
+CREATE OPERATOR FAMILY vecbf16_cos_ops USING vectors;
+
 CREATE OPERATOR FAMILY svector_l2_ops USING vectors;
 
+CREATE OPERATOR FAMILY svecf16_l2_ops USING vectors;
+
 CREATE OPERATOR FAMILY svector_dot_ops USING vectors;
 
+CREATE OPERATOR FAMILY svecf16_dot_ops USING vectors;
+
 CREATE OPERATOR FAMILY svector_cos_ops USING vectors;
 
+CREATE OPERATOR FAMILY svecf16_cos_ops USING vectors;
+
 CREATE OPERATOR FAMILY bvector_hamming_ops USING vectors;
 
 CREATE OPERATOR FAMILY bvector_dot_ops USING vectors;
 
 CREATE OPERATOR FAMILY bvector_jaccard_ops USING vectors;
 
+CREATE OPERATOR FAMILY vector_maxsim_ops USING vectors;
+
+CREATE OPERATOR FAMILY vector_chamfer_ops USING vectors;
+
+CREATE OPERATOR FAMILY int2_partition_ops USING vectors;
+
+CREATE OPERATOR FAMILY int4_partition_ops USING vectors;
+
+CREATE OPERATOR FAMILY int8_partition_ops USING vectors;
+
+CREATE OPERATOR FAMILY text_partition_ops USING vectors;
+
 -- List of operator classes
 
 CREATE OPERATOR CLASS vector_l2_ops
     FOR TYPE vector USING vectors FAMILY vector_l2_ops AS
     OPERATOR 1 <-> (vector, vector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<->> (vector, sphere_vector) FOR SEARCH;
+    OPERATOR 2 <<->> (vector, sphere_vector) FOR SEARCH,
+    OPERATOR 3 <-> (vector, search_vector) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS vector_dot_ops
     FOR TYPE vector USING vectors FAMILY vector_dot_ops AS
     OPERATOR 1 <#> (vector, vector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<#>> (vector, sphere_vector) FOR SEARCH;
+    OPERATOR 2 <<#>> (vector, sphere_vector) FOR SEARCH,
+    OPERATOR 3 <#> (vector, search_vector) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS vector_cos_ops
     FOR TYPE vector USING vectors FAMILY vector_cos_ops AS
     OPERATOR 1 <=> (vector, vector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<=>> (vector, sphere_vector) FOR SEARCH;
+    OPERATOR 2 <<=>> (vector, sphere_vector) FOR SEARCH,
+    OPERATOR 3 <=> (vector, search_vector) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS vecf16_l2_ops
     FOR TYPE vecf16 USING vectors FAMILY vecf16_l2_ops AS
     OPERATOR 1 <-> (vecf16, vecf16) FOR ORDER BY float_ops,
-    OPERATOR 2 <<->> (vecf16, sphere_vecf16) FOR SEARCH;
+    OPERATOR 2 <<->> (vecf16, sphere_vecf16) FOR SEARCH,
+    OPERATOR 3 <-> (vecf16, search_vecf16) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS vecf16_dot_ops
     FOR TYPE vecf16 USING vectors FAMILY vecf16_dot_ops AS
     OPERATOR 1 <#> (vecf16, vecf16) FOR ORDER BY float_ops,
-    OPERATOR 2 <<#>> (vecf16, sphere_vecf16) FOR SEARCH;
+    OPERATOR 2 <<#>> (vecf16, sphere_vecf16) FOR SEARCH,
+    OPERATOR 3 <#> (vecf16, search_vecf16) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS vecf16_cos_ops
     FOR TYPE vecf16 USING vectors FAMILY vecf16_cos_ops AS
     OPERATOR 1 <=> (vecf16, vecf16) FOR ORDER BY float_ops,
-    OPERATOR 2 <<=>> (vecf16, sphere_vecf16) FOR SEARCH;
+    OPERATOR 2 <<=>> (vecf16, sphere_vecf16) FOR SEARCH,
+    OPERATOR 3 <=> (vecf16, search_vecf16) FOR ORDER BY float_ops;
+
+CREATE OPERATOR CLASS vecbf16_l2_ops
+    FOR TYPE vecbf16 USING vectors FAMILY vecbf16_l2_ops AS
+    OPERATOR 1 <-> (vecbf16, vecbf16) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (vecbf16, sphere_vecbf16) FOR SEARCH,
+    OPERATOR 3 <-> (vecbf16, search_vecbf16) FOR ORDER BY float_ops;
+
+CREATE OPERATOR CLASS vecbf16_dot_ops
+    FOR TYPE vecbf16 USING vectors FAMILY vecbf16_dot_ops AS
+    OPERATOR 1 <#> (vecbf16, vecbf16) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (vecbf16, sphere_vecbf16) FOR SEARCH,
+    OPERATOR 3 <#> (vecbf16, search_vecbf16) FOR ORDER BY float_ops;
+
+CREATE OPERATOR CLASS vecbf16_cos_ops
+    FOR TYPE vecbf16 USING vectors FAMILY vecbf16_cos_ops AS
+    OPERATOR 1 <=> (vecbf16, vecbf16) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (vecbf16, sphere_vecbf16) FOR SEARCH,
+    OPERATOR 3 <=> (vecbf16, search_vecbf16) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS svector_l2_ops
     FOR TYPE svector USING vectors FAMILY svector_l2_ops AS
     OPERATOR 1 <-> (svector, svector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<=>> (svector, sphere_svector) FOR SEARCH;
+    OPERATOR 2 <<=>> (svector, sphere_svector) FOR SEARCH,
+    OPERATOR 3 <-> (svector, search_svector) FOR ORDER BY float_ops;
+
+CREATE OPERATOR CLASS svecf16_l2_ops
+    FOR TYPE svecf16 USING vectors FAMILY svecf16_l2_ops AS
+    OPERATOR 1 <-> (svecf16, svecf16) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (svecf16, sphere_svecf16) FOR SEARCH,
+    OPERATOR 3 <-> (svecf16, search_svecf16) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS svector_dot_ops
     FOR TYPE svector USING vectors FAMILY svector_dot_ops AS
     OPERATOR 1 <#> (svector, svector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<#>> (svector, sphere_svector) FOR SEARCH;
+    OPERATOR 2 <<#>> (svector, sphere_svector) FOR SEARCH,
+    OPERATOR 3 <#> (svector, search_svector) FOR ORDER BY float_ops;
+
+CREATE OPERATOR CLASS svecf16_dot_ops
+    FOR TYPE svecf16 USING vectors FAMILY svecf16_dot_ops AS
+    OPERATOR 1 <#> (svecf16, svecf16) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (svecf16, sphere_svecf16) FOR SEARCH,
+    OPERATOR 3 <#> (svecf16, search_svecf16) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS svector_cos_ops
     FOR TYPE svector USING vectors FAMILY svector_cos_ops AS
     OPERATOR 1 <=> (svector, svector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<=>> (svector, sphere_svector) FOR SEARCH;
+    OPERATOR 2 <<=>> (svector, sphere_svector) FOR SEARCH,
+    OPERATOR 3 <=> (svector, search_svector) FOR ORDER BY float_ops;
+
+CREATE OPERATOR CLASS svecf16_cos_ops
+    FOR TYPE svecf16 USING vectors FAMILY svecf16_cos_ops AS
+    OPERATOR 1 <=> (svecf16, svecf16) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (svecf16, sphere_svecf16) FOR SEARCH,
+    OPERATOR 3 <=> (svecf16, search_svecf16) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS bvector_hamming_ops
     FOR TYPE bvector USING vectors FAMILY bvector_hamming_ops AS
     OPERATOR 1 <-> (bvector, bvector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<->> (bvector, sphere_bvector) FOR SEARCH;
+    OPERATOR 2 <<->> (bvector, sphere_bvector) FOR SEARCH,
+    OPERATOR 3 <-> (bvector, search_bvector) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS bvector_dot_ops
     FOR TYPE bvector USING vectors FAMILY bvector_dot_ops AS
     OPERATOR 1 <#> (bvector, bvector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<#>> (bvector, sphere_bvector) FOR SEARCH;
+    OPERATOR 2 <<#>> (bvector, sphere_bvector) FOR SEARCH,
+    OPERATOR 3 <#> (bvector, search_bvector) FOR ORDER BY float_ops;
 
 CREATE OPERATOR CLASS bvector_jaccard_ops
     FOR TYPE bvector USING vectors FAMILY bvector_jaccard_ops AS
     OPERATOR 1 <~> (bvector, bvector) FOR ORDER BY float_ops,
-    OPERATOR 2 <<~>> (bvector, sphere_bvector) FOR SEARCH;
+    OPERATOR 2 <<~>> (bvector, sphere_bvector) FOR SEARCH,
+    OPERATOR 3 <~> (bvector, search_bvector) FOR ORDER BY float_ops;
+
+CREATE OPERATOR CLASS vector_maxsim_ops
+    FOR TYPE vector[] USING vectors FAMILY vector_maxsim_ops AS
+    OPERATOR 1 <#> (vector[], vector[]) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (vector[], sphere_multivector) FOR SEARCH;
+
+CREATE OPERATOR CLASS vector_chamfer_ops
+    FOR TYPE vector[] USING vectors FAMILY vector_chamfer_ops AS
+    OPERATOR 1 <-> (vector[], vector[]) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (vector[], sphere_multivector) FOR SEARCH;
+
+CREATE OPERATOR CLASS int2_partition_ops
+    DEFAULT FOR TYPE int2 USING vectors FAMILY int2_partition_ops AS
+    OPERATOR 4 = (int2, int2);
+
+CREATE OPERATOR CLASS int4_partition_ops
+    DEFAULT FOR TYPE int4 USING vectors FAMILY int4_partition_ops AS
+    OPERATOR 4 = (int4, int4);
+
+CREATE OPERATOR CLASS int8_partition_ops
+    DEFAULT FOR TYPE int8 USING vectors FAMILY int8_partition_ops AS
+    OPERATOR 4 = (int8, int8);
+
+CREATE OPERATOR CLASS text_partition_ops
+    DEFAULT FOR TYPE text USING vectors FAMILY text_partition_ops AS
+    OPERATOR 4 = (text, text);
 
 -- List of views
 
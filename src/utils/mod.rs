@@ -1,2 +1,3 @@
 pub mod cells;
 pub mod parse;
+pub mod projection;
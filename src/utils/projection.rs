@@ -0,0 +1,30 @@
+use crate::utils::cells::PgRefCell;
+use quantization::utils::random_orthonormal_rows;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+// dimensions, target dimensions, seed and the generated rows
+static CACHE: PgRefCell<Option<(u32, u32, i64, Vec<Vec<f32>>)>> = unsafe { PgRefCell::new(None) };
+
+/// Calls `f` with `target` random orthonormal rows of `dims` dimensions generated
+/// from `seed`. The rows are cached across calls, since generating them costs far
+/// more than a projection.
+pub fn with_projection<R>(
+    dims: u32,
+    target: u32,
+    seed: i64,
+    f: impl FnOnce(&[Vec<f32>]) -> R,
+) -> R {
+    let mut cache = CACHE.borrow_mut();
+    if !matches!(&*cache, Some((d, t, s, _)) if *d == dims && *t == target && *s == seed) {
+        let mut rng = ChaCha12Rng::seed_from_u64(seed as u64);
+        *cache = Some((
+            dims,
+            target,
+            seed,
+            random_orthonormal_rows(dims, target, &mut rng),
+        ));
+    }
+    let (_, _, _, rows) = cache.as_ref().unwrap();
+    f(rows)
+}